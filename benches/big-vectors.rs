@@ -79,7 +79,7 @@ fn big_u32(c: &mut Criterion) {
         );
 
         let id = BenchmarkId::new("L1", dimensionality);
-        bench_one(&mut group, id, &data[0], &data[1], manhattan);
+        bench_one(&mut group, id, &data[0], &data[1], manhattan::<_, u32>);
 
         for &(name, metric) in metrics {
             let id = BenchmarkId::new(name, dimensionality);
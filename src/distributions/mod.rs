@@ -0,0 +1,342 @@
+//! Distance functions for discrete probability distributions.
+//!
+//! Each distribution is represented as a slice of `Float`s giving the
+//! probability of each outcome, so the slices are expected to be non-negative
+//! and to sum to one. Inputs that do not sum to one, e.g. histograms of counts,
+//! can be normalized on the fly by wrapping a distance function with
+//! [`normalized`].
+//!
+//! The Wasserstein distances instead compare distributions over an ordered
+//! set of values, given either as samples or as histograms.
+//!
+//! # Potentially unexpected behaviors
+//! As with the [`crate::vectors`] functions, when one slice is shorter than the
+//! other, elements in the longer slice past the end of the shorter slice will
+//! be ignored. The exceptions are [`wasserstein_samples`], which accepts
+//! samples of different sizes, and [`wasserstein_histograms`], which panics on
+//! histograms of different lengths.
+
+mod wasserstein;
+
+use alloc::vec::Vec;
+
+use crate::number::Float;
+
+pub use wasserstein::{
+    try_wasserstein_histograms, wasserstein, wasserstein_histograms, wasserstein_samples,
+};
+
+/// Normalizes a slice of non-negative `Float`s so that it sums to one.
+///
+/// If the slice sums to zero, it is returned unchanged.
+///
+/// # Arguments
+///
+/// * `p`: A slice of non-negative numbers.
+///
+/// # Examples
+///
+/// ```
+/// use distances::distributions::normalize;
+///
+/// let p: Vec<f32> = vec![1.0, 3.0];
+///
+/// assert_eq!(normalize(&p), vec![0.25, 0.75]);
+/// ```
+pub fn normalize<T: Float>(p: &[T]) -> Vec<T> {
+    let sum = p.iter().copied().sum::<T>();
+    if sum == T::zero() {
+        p.to_vec()
+    } else {
+        p.iter().map(|&v| v / sum).collect()
+    }
+}
+
+/// Wraps a distance function so that both inputs are normalized, using
+/// [`normalize`], before the distance is computed.
+///
+/// # Arguments
+///
+/// * `metric`: The distance function to wrap.
+///
+/// # Examples
+///
+/// ```
+/// use distances::distributions::{normalized, total_variation};
+///
+/// let metric = normalized(total_variation::<f64, f64>);
+///
+/// let p: Vec<f64> = vec![1.0, 3.0];
+/// let q: Vec<f64> = vec![6.0, 2.0];
+///
+/// let distance: f64 = metric(&p, &q);
+///
+/// assert!((distance - 0.5).abs() <= f64::EPSILON);
+/// ```
+pub fn normalized<T: Float, U: Float>(
+    metric: impl Fn(&[T], &[T]) -> U,
+) -> impl Fn(&[T], &[T]) -> U {
+    move |p: &[T], q: &[T]| metric(&normalize(p), &normalize(q))
+}
+
+/// Computes the Bhattacharyya coefficient between two distributions.
+///
+/// This is the sum of the geometric means of the corresponding probabilities.
+/// It lies in `[0, 1]` and is `1` for identical distributions.
+fn bhattacharyya_coefficient<T: Float, U: Float>(p: &[T], q: &[T]) -> U {
+    p.iter()
+        .zip(q.iter())
+        .map(|(&a, &b)| (U::from(a) * U::from(b)).sqrt())
+        .sum()
+}
+
+/// Computes the Bhattacharyya distance between two distributions.
+///
+/// The Bhattacharyya distance is defined as the negative natural logarithm of
+/// the Bhattacharyya coefficient, i.e. the sum of the geometric means of the
+/// corresponding probabilities. It is not a metric, since it does not obey the
+/// triangle inequality. It is infinite for distributions with disjoint
+/// supports.
+///
+/// # Arguments
+///
+/// * `p`: A slice of probabilities.
+/// * `q`: A slice of probabilities.
+///
+/// # Examples
+///
+/// ```
+/// use distances::distributions::bhattacharyya;
+///
+/// let p: Vec<f64> = vec![0.5, 0.5];
+/// let q: Vec<f64> = vec![0.5, 0.5];
+///
+/// let distance: f64 = bhattacharyya(&p, &q);
+/// assert!(distance.abs() <= f64::EPSILON);
+///
+/// let q: Vec<f64> = vec![1.0, 0.0];
+///
+/// let distance: f64 = bhattacharyya(&p, &q);
+/// assert!((distance - 0.5_f64.sqrt().ln().abs()).abs() <= f64::EPSILON);
+/// ```
+///
+/// # References
+///
+/// * [Bhattacharyya distance](https://en.wikipedia.org/wiki/Bhattacharyya_distance)
+pub fn bhattacharyya<T: Float, U: Float>(p: &[T], q: &[T]) -> U {
+    let bc = bhattacharyya_coefficient::<T, U>(p, q);
+    if bc >= U::one() {
+        U::zero()
+    } else {
+        -bc.ln()
+    }
+}
+
+/// Computes the Hellinger distance between two distributions.
+///
+/// The Hellinger distance is defined as `1 / sqrt(2)` times the Euclidean
+/// distance between the element-wise square roots of the two distributions. It
+/// is a metric and lies in `[0, 1]`.
+///
+/// # Arguments
+///
+/// * `p`: A slice of probabilities.
+/// * `q`: A slice of probabilities.
+///
+/// # Examples
+///
+/// ```
+/// use distances::distributions::hellinger;
+///
+/// let p: Vec<f64> = vec![0.5, 0.5];
+/// let q: Vec<f64> = vec![0.5, 0.5];
+///
+/// let distance: f64 = hellinger(&p, &q);
+/// assert!(distance.abs() <= f64::EPSILON);
+///
+/// let p: Vec<f64> = vec![1.0, 0.0];
+/// let q: Vec<f64> = vec![0.0, 1.0];
+///
+/// let distance: f64 = hellinger(&p, &q);
+/// assert!((distance - 1.0).abs() <= f64::EPSILON);
+/// ```
+///
+/// # References
+///
+/// * [Hellinger distance](https://en.wikipedia.org/wiki/Hellinger_distance)
+pub fn hellinger<T: Float, U: Float>(p: &[T], q: &[T]) -> U {
+    let sum_sq = p
+        .iter()
+        .zip(q.iter())
+        .map(|(&a, &b)| U::from(a).sqrt() - U::from(b).sqrt())
+        .map(|v| v * v)
+        .sum::<U>();
+    (sum_sq / (U::one() + U::one())).sqrt()
+}
+
+/// Computes the Kullback-Leibler divergence of `q` from `p`, i.e. `KL(p || q)`.
+///
+/// The KL divergence is defined as the sum of `p_i * ln(p_i / q_i)`, with terms
+/// where `p_i` is zero contributing nothing. It is measured in nats. It is not
+/// symmetric and not a metric. It is infinite if some `q_i` is zero where the
+/// corresponding `p_i` is not.
+///
+/// # Arguments
+///
+/// * `p`: A slice of probabilities.
+/// * `q`: A slice of probabilities.
+///
+/// # Examples
+///
+/// ```
+/// use distances::distributions::kl_divergence;
+///
+/// let p: Vec<f64> = vec![0.5, 0.5];
+/// let q: Vec<f64> = vec![0.25, 0.75];
+///
+/// let divergence: f64 = kl_divergence(&p, &q);
+/// let expected = 0.5 * (2.0_f64).ln() + 0.5 * (2.0_f64 / 3.0).ln();
+///
+/// assert!((divergence - expected).abs() <= f64::EPSILON);
+/// ```
+///
+/// # References
+///
+/// * [Kullback-Leibler divergence](https://en.wikipedia.org/wiki/Kullback%E2%80%93Leibler_divergence)
+pub fn kl_divergence<T: Float, U: Float>(p: &[T], q: &[T]) -> U {
+    let d = p
+        .iter()
+        .zip(q.iter())
+        .map(|(&a, &b)| (U::from(a), U::from(b)))
+        .filter(|&(a, _)| a > U::zero())
+        .map(|(a, b)| a * (a / b).ln())
+        .sum::<U>();
+    if d < U::zero() {
+        U::zero()
+    } else {
+        d
+    }
+}
+
+/// Computes the Jensen-Shannon divergence between two distributions.
+///
+/// The Jensen-Shannon divergence is the mean of the KL divergences of each
+/// distribution from their mixture `m = (p + q) / 2`. Unlike the KL divergence,
+/// it is symmetric and always finite, being bounded above by `ln(2)`. It is not
+/// a metric, but its square root, [`jensen_shannon`], is.
+///
+/// # Arguments
+///
+/// * `p`: A slice of probabilities.
+/// * `q`: A slice of probabilities.
+///
+/// # Examples
+///
+/// ```
+/// use distances::distributions::jensen_shannon_divergence;
+///
+/// let p: Vec<f64> = vec![1.0, 0.0];
+/// let q: Vec<f64> = vec![0.0, 1.0];
+///
+/// let divergence: f64 = jensen_shannon_divergence(&p, &q);
+///
+/// assert!((divergence - (2.0_f64).ln()).abs() <= f64::EPSILON);
+/// ```
+///
+/// # References
+///
+/// * [Jensen-Shannon divergence](https://en.wikipedia.org/wiki/Jensen%E2%80%93Shannon_divergence)
+pub fn jensen_shannon_divergence<T: Float, U: Float>(p: &[T], q: &[T]) -> U {
+    let half = U::one() / (U::one() + U::one());
+    let d = p
+        .iter()
+        .zip(q.iter())
+        .map(|(&a, &b)| (U::from(a), U::from(b)))
+        .map(|(a, b)| {
+            let m = (a + b) * half;
+            let kl_a = if a > U::zero() {
+                a * (a / m).ln()
+            } else {
+                U::zero()
+            };
+            let kl_b = if b > U::zero() {
+                b * (b / m).ln()
+            } else {
+                U::zero()
+            };
+            kl_a + kl_b
+        })
+        .sum::<U>()
+        * half;
+    if d < U::zero() {
+        U::zero()
+    } else {
+        d
+    }
+}
+
+/// Computes the Jensen-Shannon distance between two distributions.
+///
+/// This is the square root of the [`jensen_shannon_divergence`] and, unlike
+/// the divergence, it is a metric.
+///
+/// # Arguments
+///
+/// * `p`: A slice of probabilities.
+/// * `q`: A slice of probabilities.
+///
+/// # Examples
+///
+/// ```
+/// use distances::distributions::jensen_shannon;
+///
+/// let p: Vec<f64> = vec![1.0, 0.0];
+/// let q: Vec<f64> = vec![0.0, 1.0];
+///
+/// let distance: f64 = jensen_shannon(&p, &q);
+///
+/// assert!((distance - (2.0_f64).ln().sqrt()).abs() <= f64::EPSILON);
+/// ```
+///
+/// # References
+///
+/// * [Jensen-Shannon divergence](https://en.wikipedia.org/wiki/Jensen%E2%80%93Shannon_divergence)
+pub fn jensen_shannon<T: Float, U: Float>(p: &[T], q: &[T]) -> U {
+    jensen_shannon_divergence::<T, U>(p, q).sqrt()
+}
+
+/// Computes the total variation distance between two distributions.
+///
+/// The total variation distance is the largest difference between the
+/// probabilities that the two distributions assign to the same event. For
+/// discrete distributions, this is half of the L1-norm of their difference. It
+/// is a metric and lies in `[0, 1]`.
+///
+/// # Arguments
+///
+/// * `p`: A slice of probabilities.
+/// * `q`: A slice of probabilities.
+///
+/// # Examples
+///
+/// ```
+/// use distances::distributions::total_variation;
+///
+/// let p: Vec<f64> = vec![0.25, 0.75];
+/// let q: Vec<f64> = vec![0.75, 0.25];
+///
+/// let distance: f64 = total_variation(&p, &q);
+///
+/// assert!((distance - 0.5).abs() <= f64::EPSILON);
+/// ```
+///
+/// # References
+///
+/// * [Total variation distance](https://en.wikipedia.org/wiki/Total_variation_distance_of_probability_measures)
+pub fn total_variation<T: Float, U: Float>(p: &[T], q: &[T]) -> U {
+    p.iter()
+        .zip(q.iter())
+        .map(|(&a, &b)| U::from(a).abs_diff(U::from(b)))
+        .sum::<U>()
+        / (U::one() + U::one())
+}
@@ -0,0 +1,286 @@
+//! The 1-D Wasserstein (Earth Mover's) distance.
+
+use alloc::vec::Vec;
+
+use crate::{number::Float, DistanceError, Number};
+
+/// Computes the 1-D Wasserstein-1 distance between two empirical
+/// distributions, each given as an unsorted slice of samples.
+///
+/// This is a shorthand for `wasserstein_samples(1)`. See
+/// [`wasserstein_samples`] for details.
+///
+/// # Arguments
+///
+/// * `x`: A slice of samples.
+/// * `y`: A slice of samples.
+///
+/// # Examples
+///
+/// ```
+/// use distances::distributions::wasserstein;
+///
+/// let x: Vec<u32> = vec![3, 1, 2];
+/// let y: Vec<u32> = vec![5, 6, 4];
+///
+/// let distance: f32 = wasserstein(&x, &y);
+///
+/// assert!((distance - 3.0).abs() <= f32::EPSILON);
+/// ```
+///
+/// # References
+///
+/// * [Wasserstein metric](https://en.wikipedia.org/wiki/Wasserstein_metric)
+pub fn wasserstein<T: Number, U: Float>(x: &[T], y: &[T]) -> U {
+    wasserstein_samples(1)(x, y)
+}
+
+/// Creates a function that computes the exact 1-D Wasserstein-p distance
+/// between two empirical distributions, each given as an unsorted slice of
+/// samples.
+///
+/// Each sample carries an equal share of its distribution's mass, so the two
+/// slices need not have the same length. The samples are sorted and the
+/// optimal transport plan, which in 1-D matches quantiles in order, is computed
+/// in a single merge pass. The cost is `O((n + m) log(n + m))`.
+///
+/// The Wasserstein-p distance is a metric for `p >= 1`. If either slice is
+/// empty, the distance is zero.
+///
+/// # Arguments
+///
+/// * `p`: The order of the distance. This should be at least `1`.
+///
+/// # Examples
+///
+/// ```
+/// use distances::distributions::wasserstein_samples;
+///
+/// let metric = wasserstein_samples(2);
+///
+/// let x: Vec<f64> = vec![0.0, 0.0];
+/// let y: Vec<f64> = vec![1.0, 3.0, 2.0, 0.0];
+///
+/// // Half of the mass of `x` moves to each of `0, 1` and `2, 3`.
+/// let distance: f64 = metric(&x, &y);
+///
+/// assert!((distance - 3.5_f64.sqrt()).abs() <= 1e-12);
+/// ```
+pub fn wasserstein_samples<T: Number, U: Float>(p: i32) -> impl Fn(&[T], &[T]) -> U {
+    move |x: &[T], y: &[T]| {
+        let x = uniform_masses::<T, U>(x);
+        let y = uniform_masses::<T, U>(y);
+        transport_cost(&x, &y, p).powf(U::one() / U::from(p))
+    }
+}
+
+/// Creates a function that computes the exact 1-D Wasserstein-p distance
+/// between two histograms over the same ordered bins.
+///
+/// The histograms are normalized to unit mass, so they may hold raw counts.
+/// Each bin's mass sits at the corresponding entry of `positions`, which must
+/// be sorted in ascending order. If `positions` is `None`, the bins are placed
+/// at `0, 1, 2, ...`. If either histogram has no mass, the distance is zero.
+///
+/// See [`try_wasserstein_histograms`] for a version that reports invalid
+/// inputs as errors instead of panicking.
+///
+/// # Arguments
+///
+/// * `p`: The order of the distance. This should be at least `1`.
+/// * `positions`: Optional positions of the bins.
+///
+/// # Panics
+///
+/// * If `positions` is not sorted in ascending order. This is checked once,
+///   when the function is created.
+/// * If the generated function is called with histograms of different
+///   lengths, or, when `positions` is given, with histograms whose length
+///   differs from that of `positions`.
+///
+/// # Examples
+///
+/// ```
+/// use distances::distributions::wasserstein_histograms;
+///
+/// let metric = wasserstein_histograms(1, None);
+///
+/// let x: Vec<u32> = vec![1, 0, 0];
+/// let y: Vec<u32> = vec![0, 0, 5];
+///
+/// let distance: f32 = metric(&x, &y);
+/// assert!((distance - 2.0).abs() <= f32::EPSILON);
+///
+/// let positions = [0.0, 0.5, 10.0];
+/// let metric = wasserstein_histograms(1, Some(&positions));
+///
+/// let distance: f32 = metric(&x, &y);
+/// assert!((distance - 10.0).abs() <= f32::EPSILON);
+/// ```
+pub fn wasserstein_histograms<T: Number, U: Float>(
+    p: i32,
+    positions: Option<&[U]>,
+) -> impl Fn(&[T], &[T]) -> U + '_ {
+    assert!(
+        positions.is_none_or(is_ascending),
+        "The positions of the bins must be sorted in ascending order."
+    );
+    move |x: &[T], y: &[T]| {
+        assert_eq!(
+            x.len(),
+            y.len(),
+            "The histograms must have the same length."
+        );
+        if let Some(positions) = positions {
+            assert_eq!(
+                positions.len(),
+                x.len(),
+                "The histograms must have one bin per position."
+            );
+        }
+        let x = histogram_masses(x, positions);
+        let y = histogram_masses(y, positions);
+        transport_cost(&x, &y, p).powf(U::one() / U::from(p))
+    }
+}
+
+/// Checked version of [`wasserstein_histograms`].
+///
+/// The generated function returns:
+///
+/// * [`DistanceError::EmptyInput`] if either histogram is empty.
+/// * [`DistanceError::LengthMismatch`] if the histograms have different
+///   lengths, or, when `positions` is given, if the histograms do not have one
+///   bin per position. The latter is reported as
+///   `(positions.len(), x.len())`.
+/// * [`DistanceError::NanInput`] if either histogram or `positions` contains
+///   a `NaN`.
+/// * [`DistanceError::UnsortedInput`] if `positions` is not sorted in
+///   ascending order.
+/// * [`DistanceError::ZeroNorm`] if either histogram has no mass.
+///
+/// # Examples
+///
+/// ```
+/// use distances::{distributions::try_wasserstein_histograms, DistanceError};
+///
+/// let positions = [0.0, 0.5, 10.0];
+/// let metric = try_wasserstein_histograms(1, Some(&positions));
+///
+/// let distance: Result<f32, _> = metric(&[1_u32, 0, 0], &[0, 0, 5]);
+/// assert_eq!(distance, Ok(10.0));
+///
+/// let distance: Result<f32, _> = metric(&[1_u32, 0], &[0, 5]);
+/// assert_eq!(distance, Err(DistanceError::LengthMismatch(3, 2)));
+///
+/// let distance: Result<f32, _> = metric(&[1_u32, 0, 0], &[0, 0, 0]);
+/// assert_eq!(distance, Err(DistanceError::ZeroNorm));
+///
+/// let positions = [0.0, 10.0, 0.5];
+/// let metric = try_wasserstein_histograms(1, Some(&positions));
+///
+/// let distance: Result<f32, _> = metric(&[1_u32, 0, 0], &[0, 0, 5]);
+/// assert_eq!(distance, Err(DistanceError::UnsortedInput));
+/// ```
+pub fn try_wasserstein_histograms<T: Number, U: Float>(
+    p: i32,
+    positions: Option<&[U]>,
+) -> impl Fn(&[T], &[T]) -> Result<U, DistanceError> + '_ {
+    let positions_error = positions.and_then(|positions| {
+        if positions.iter().any(|v| v.is_nan()) {
+            Some(DistanceError::NanInput)
+        } else if is_ascending(positions) {
+            None
+        } else {
+            Some(DistanceError::UnsortedInput)
+        }
+    });
+
+    move |x: &[T], y: &[T]| {
+        if x.is_empty() || y.is_empty() {
+            return Err(DistanceError::EmptyInput);
+        }
+        if x.len() != y.len() {
+            return Err(DistanceError::LengthMismatch(x.len(), y.len()));
+        }
+        if let Some(positions) = positions.filter(|positions| positions.len() != x.len()) {
+            return Err(DistanceError::LengthMismatch(positions.len(), x.len()));
+        }
+        if let Some(error) = positions_error {
+            return Err(error);
+        }
+        if x.iter().chain(y).any(|&v| U::from(v).is_nan()) {
+            return Err(DistanceError::NanInput);
+        }
+        let x = histogram_masses(x, positions);
+        let y = histogram_masses(y, positions);
+        if x.is_empty() || y.is_empty() {
+            return Err(DistanceError::ZeroNorm);
+        }
+        Ok(transport_cost(&x, &y, p).powf(U::one() / U::from(p)))
+    }
+}
+
+/// Whether the positions of the bins are sorted in ascending order. A `NaN`
+/// position makes this `false`.
+fn is_ascending<U: Float>(positions: &[U]) -> bool {
+    positions.windows(2).all(|w| w[0] <= w[1])
+}
+
+/// Converts samples into sorted `(position, mass)` pairs of equal mass.
+///
+/// `NaN` samples are sorted after every other value so that the comparator
+/// is a total order; they then propagate into a `NaN` distance.
+fn uniform_masses<T: Number, U: Float>(x: &[T]) -> Vec<(U, U)> {
+    let mass = U::one() / U::from(x.len());
+    let mut masses = x.iter().map(|&v| (U::from(v), mass)).collect::<Vec<_>>();
+    masses.sort_by(|(a, _), (b, _)| {
+        a.partial_cmp(b)
+            .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+    });
+    masses
+}
+
+/// Converts a histogram into `(position, mass)` pairs with unit total mass,
+/// dropping empty bins.
+fn histogram_masses<T: Number, U: Float>(x: &[T], positions: Option<&[U]>) -> Vec<(U, U)> {
+    let total = x.iter().map(|&v| U::from(v)).sum::<U>();
+    if total == U::zero() {
+        return Vec::new();
+    }
+
+    let masses = x.iter().map(|&v| U::from(v) / total);
+    let pairs: Vec<(U, U)> = match positions {
+        Some(positions) => positions.iter().copied().zip(masses).collect(),
+        None => (0..x.len()).map(U::from).zip(masses).collect(),
+    };
+    pairs.into_iter().filter(|&(_, m)| m > U::zero()).collect()
+}
+
+/// Computes the cost, i.e. the p-th power of the Wasserstein-p distance, of
+/// the optimal transport plan between two sorted lists of `(position, mass)`
+/// pairs with equal total mass.
+///
+/// In 1-D, the optimal plan moves mass between the two lists in sorted order,
+/// so we greedily move as much mass as possible from the current entry of one
+/// list to the current entry of the other.
+fn transport_cost<U: Float>(x: &[(U, U)], y: &[(U, U)], p: i32) -> U {
+    let mut cost = U::zero();
+    let (mut x_iter, mut y_iter) = (x.iter(), y.iter());
+    let (mut x_cur, mut y_cur) = (x_iter.next().copied(), y_iter.next().copied());
+
+    while let (Some((x_pos, x_mass)), Some((y_pos, y_mass))) = (x_cur, y_cur) {
+        let dist = x_pos.abs_diff(y_pos).powi(p);
+        if x_mass <= y_mass {
+            cost += x_mass * dist;
+            x_cur = x_iter.next().copied();
+            y_cur = Some((y_pos, y_mass - x_mass));
+        } else {
+            cost += y_mass * dist;
+            x_cur = Some((x_pos, x_mass - y_mass));
+            y_cur = y_iter.next().copied();
+        }
+    }
+
+    cost
+}
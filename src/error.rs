@@ -0,0 +1,31 @@
+//! Errors reported by the checked (`try_*`) distance functions.
+
+/// The reasons a distance could not be computed without guessing a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DistanceError {
+    /// The inputs have different lengths, given as `(x.len(), y.len())`.
+    LengthMismatch(usize, usize),
+    /// At least one of the inputs is empty.
+    EmptyInput,
+    /// At least one of the inputs contains a `NaN`.
+    NanInput,
+    /// At least one of the inputs has zero norm, so the distance is undefined.
+    ZeroNorm,
+    /// An input that must be sorted in ascending order is not.
+    UnsortedInput,
+}
+
+impl core::fmt::Display for DistanceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::LengthMismatch(x, y) => write!(f, "inputs have different lengths: {x} != {y}"),
+            Self::EmptyInput => write!(f, "at least one input is empty"),
+            Self::NanInput => write!(f, "at least one input contains a NaN"),
+            Self::ZeroNorm => write!(f, "at least one input has zero norm"),
+            Self::UnsortedInput => write!(f, "an input that must be sorted is not"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DistanceError {}
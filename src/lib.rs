@@ -14,19 +14,24 @@
     clippy::panic,
     clippy::cast_lossless
 )]
+#![allow(clippy::used_underscore_items)]
 #![doc = include_str!("../README.md")]
-// #![no_std]  // TODO: re-enable as an optional feature
+#![cfg_attr(not(feature = "std"), no_std)]
 
-// extern crate alloc;
+extern crate alloc;
 
+pub mod distributions;
+pub mod error;
 pub mod number;
 
+pub use error::DistanceError;
 pub use number::Number;
 
 pub mod sets;
 pub mod simd;
 pub mod strings;
+pub mod time_series;
 pub mod vectors;
 
 /// The version of the crate.
-pub const VERSION: &str = "1.6.2";
+pub const VERSION: &str = "2.0.0";
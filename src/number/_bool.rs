@@ -1,5 +1,7 @@
 //! `NumBool` is a `Number` that can be used as a boolean.
 
+use alloc::vec::Vec;
+
 use crate::Number;
 
 /// A `Number` that can be used as a boolean.
@@ -107,6 +109,8 @@ impl core::ops::SubAssign for Bool {
 }
 
 impl Number for Bool {
+    type Accumulator = Self;
+
     fn zero() -> Self {
         Self(0)
     }
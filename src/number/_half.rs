@@ -0,0 +1,134 @@
+//! `Number` and `Float` implementations for the half-precision types from the
+//! `half` crate.
+//!
+//! Arithmetic is performed by the `half` crate itself, while all other
+//! operations are computed in `f32`. Distances over half-precision slices
+//! accumulate in `f32`.
+
+use alloc::vec::Vec;
+
+use half::{bf16, f16};
+
+use crate::{number::Float, Number};
+
+/// A macro to implement the `Number` and `Float` traits for half-precision
+/// types.
+macro_rules! impl_half {
+    ($($ty:ty),*) => {
+        $(
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            impl Number for $ty {
+                type Accumulator = f32;
+
+                fn zero() -> Self {
+                    Self::ZERO
+                }
+
+                fn one() -> Self {
+                    Self::ONE
+                }
+
+                fn mul_add(self, a: Self, b: Self) -> Self {
+                    Self::from_f32(Number::mul_add(self.to_f32(), a.to_f32(), b.to_f32()))
+                }
+
+                fn mul_add_assign(&mut self, a: Self, b: Self) {
+                    *self = Number::mul_add(*self, a, b);
+                }
+
+                fn from<T: Number>(n: T) -> Self {
+                    Self::from_f32(n.as_f32())
+                }
+
+                fn as_f32(self) -> f32 {
+                    self.to_f32()
+                }
+
+                fn as_f64(self) -> f64 {
+                    self.to_f64()
+                }
+
+                fn as_u64(self) -> u64 {
+                    self.to_f32() as u64
+                }
+
+                fn as_i64(self) -> i64 {
+                    self.to_f32() as i64
+                }
+
+                fn is_nan(self) -> bool {
+                    self.is_nan()
+                }
+
+                fn abs(self) -> Self {
+                    // Clear the sign bit, which is the highest bit in both types.
+                    Self::from_bits(self.to_bits() & 0x7fff)
+                }
+
+                fn abs_diff(self, other: Self) -> Self {
+                    Number::abs(self - other)
+                }
+
+                fn powi(self, exp: i32) -> Self {
+                    Self::from_f32(Number::powi(self.to_f32(), exp))
+                }
+
+                fn num_bytes() -> usize {
+                    core::mem::size_of::<Self>()
+                }
+
+                fn from_le_bytes(bytes: &[u8]) -> Self {
+                    let mut ty_bytes = [0_u8; 2];
+                    ty_bytes.copy_from_slice(bytes);
+                    Self::from_le_bytes(ty_bytes)
+                }
+
+                fn to_le_bytes(self) -> Vec<u8> {
+                    self.to_le_bytes().to_vec()
+                }
+
+                fn from_be_bytes(bytes: &[u8]) -> Self {
+                    let mut ty_bytes = [0_u8; 2];
+                    ty_bytes.copy_from_slice(bytes);
+                    Self::from_be_bytes(ty_bytes)
+                }
+
+                fn to_be_bytes(self) -> Vec<u8> {
+                    self.to_be_bytes().to_vec()
+                }
+
+                fn epsilon() -> Self {
+                    Self::EPSILON
+                }
+
+                fn next_random<R: rand::Rng>(rng: &mut R) -> Self {
+                    Self::from_f32(rng.gen())
+                }
+            }
+
+            impl Float for $ty {
+                fn sqrt(self) -> Self {
+                    Self::from_f32(Float::sqrt(self.to_f32()))
+                }
+
+                fn cbrt(self) -> Self {
+                    Self::from_f32(Float::cbrt(self.to_f32()))
+                }
+
+                fn powf(self, exp: Self) -> Self {
+                    Self::from_f32(Float::powf(self.to_f32(), exp.to_f32()))
+                }
+
+                fn ln(self) -> Self {
+                    Self::from_f32(Float::ln(self.to_f32()))
+                }
+
+                fn exp(self) -> Self {
+                    Self::from_f32(Float::exp(self.to_f32()))
+                }
+            }
+        )*
+    }
+}
+
+impl_half!(f16, bf16);
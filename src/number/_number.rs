@@ -3,6 +3,7 @@
 //! We calculate distances over collections of `Number`s.
 //! Distance values are also represented as `Number`s.
 
+use alloc::vec::Vec;
 use core::{
     fmt::{Debug, Display},
     iter::Sum,
@@ -32,17 +33,25 @@ pub trait Number:
     + Display
     + Default
 {
+    /// A type wide enough to accumulate sums and products of `Self` without
+    /// overflowing in practice.
+    ///
+    /// Integers accumulate in the widest practical type of the same
+    /// signedness, e.g. `u8` in `u64` and `i32` in `i128`. Floats accumulate
+    /// in themselves.
+    type Accumulator: Number;
+
     /// Returns the additive identity.
     fn zero() -> Self;
 
     /// Returns the multiplicative identity.
     fn one() -> Self;
 
-    /// Returns `self + a * b`.
+    /// Returns `self * a + b`.
     #[must_use]
     fn mul_add(self, a: Self, b: Self) -> Self;
 
-    /// Replaces `self` with `self + a * b`.
+    /// Replaces `self` with `self * a + b`.
     fn mul_add_assign(&mut self, a: Self, b: Self);
 
     /// Casts a number to `Self`. This may be a lossy conversion.
@@ -60,11 +69,27 @@ pub trait Number:
     /// Returns the number as a `i64`. This may be a lossy conversion.
     fn as_i64(self) -> i64;
 
+    /// Returns the number as its `Accumulator` type.
+    fn as_accumulator(self) -> Self::Accumulator {
+        <Self::Accumulator as Number>::from(self)
+    }
+
+    /// Returns whether the number is `NaN`. This is always `false` for types
+    /// that cannot represent `NaN`.
+    #[must_use]
+    fn is_nan(self) -> bool {
+        false
+    }
+
     /// Returns the absolute value of a `Number`.
     #[must_use]
     fn abs(self) -> Self;
 
     /// Returns the absolute difference between two `Number`s.
+    ///
+    /// For signed integers, a difference too large for the type, e.g. between
+    /// `i8::MIN` and `i8::MAX`, saturates at its maximum. Compute it in the
+    /// `Accumulator` type to get the exact value.
     #[must_use]
     fn abs_diff(self, other: Self) -> Self;
 
@@ -108,6 +133,8 @@ pub trait Number:
 }
 
 impl Number for f32 {
+    type Accumulator = Self;
+
     fn zero() -> Self {
         0.0
     }
@@ -117,8 +144,14 @@ impl Number for f32 {
     }
 
     fn mul_add(self, a: Self, b: Self) -> Self {
-        // libm::fmaf(self, a, b)  // no-std
-        self.mul_add(a, b)
+        #[cfg(feature = "std")]
+        {
+            self.mul_add(a, b)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            libm::fmaf(self, a, b)
+        }
     }
 
     fn mul_add_assign(&mut self, a: Self, b: Self) {
@@ -148,9 +181,19 @@ impl Number for f32 {
         self as i64
     }
 
+    fn is_nan(self) -> bool {
+        self.is_nan()
+    }
+
     fn abs(self) -> Self {
-        // libm::fabsf(self)  // no-std
-        self.abs()
+        #[cfg(feature = "std")]
+        {
+            self.abs()
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            libm::fabsf(self)
+        }
     }
 
     fn abs_diff(self, other: Self) -> Self {
@@ -158,8 +201,14 @@ impl Number for f32 {
     }
 
     fn powi(self, exp: i32) -> Self {
-        // libm::powf(self, exp.as_f32())  // no-std
-        self.powi(exp)
+        #[cfg(feature = "std")]
+        {
+            self.powi(exp)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            libm::powf(self, exp.as_f32())
+        }
     }
 
     fn num_bytes() -> usize {
@@ -196,6 +245,8 @@ impl Number for f32 {
 }
 
 impl Number for f64 {
+    type Accumulator = Self;
+
     fn zero() -> Self {
         0.0
     }
@@ -205,8 +256,14 @@ impl Number for f64 {
     }
 
     fn mul_add(self, a: Self, b: Self) -> Self {
-        // libm::fma(self, a, b)  // no-std
-        self.mul_add(a, b)
+        #[cfg(feature = "std")]
+        {
+            self.mul_add(a, b)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            libm::fma(self, a, b)
+        }
     }
 
     fn mul_add_assign(&mut self, a: Self, b: Self) {
@@ -236,9 +293,19 @@ impl Number for f64 {
         self as i64
     }
 
+    fn is_nan(self) -> bool {
+        self.is_nan()
+    }
+
     fn abs(self) -> Self {
-        // libm::fabs(self)  // no-std
-        self.abs()
+        #[cfg(feature = "std")]
+        {
+            self.abs()
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            libm::fabs(self)
+        }
     }
 
     fn abs_diff(self, other: Self) -> Self {
@@ -246,8 +313,14 @@ impl Number for f64 {
     }
 
     fn powi(self, exp: i32) -> Self {
-        // libm::pow(self, exp.as_f64())  // no-std
-        self.powi(exp)
+        #[cfg(feature = "std")]
+        {
+            self.powi(exp)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            libm::pow(self, exp.as_f64())
+        }
     }
 
     fn num_bytes() -> usize {
@@ -285,9 +358,18 @@ impl Number for f64 {
 
 /// A macro to implement the `Number` trait for primitive types.
 macro_rules! impl_number_iint {
-    ($($ty:ty),*) => {
+    ($($ty:ty => $acc:ty),*) => {
         $(
+            #[allow(
+                clippy::cast_lossless,
+                clippy::cast_possible_truncation,
+                clippy::cast_possible_wrap,
+                clippy::cast_precision_loss,
+                clippy::cast_sign_loss
+            )]
             impl Number for $ty {
+                type Accumulator = $acc;
+
                 fn zero() -> Self {
                     0
                 }
@@ -297,11 +379,11 @@ macro_rules! impl_number_iint {
                 }
 
                 fn mul_add(self, a: Self, b: Self) -> Self {
-                    self + a * b
+                    self * a + b
                 }
 
                 fn mul_add_assign(&mut self, a: Self, b: Self) {
-                    *self += a * b;
+                    *self = *self * a + b;
                 }
 
                 fn from<T: Number>(n: T) -> Self {
@@ -329,7 +411,7 @@ macro_rules! impl_number_iint {
                 }
 
                 fn abs_diff(self, other: Self) -> Self {
-                    <$ty>::abs(self - other)
+                    <$ty>::try_from(<$ty>::abs_diff(self, other)).unwrap_or(<$ty>::MAX)
                 }
 
                 fn powi(self, exp: i32) -> Self {
@@ -372,13 +454,22 @@ macro_rules! impl_number_iint {
     }
 }
 
-impl_number_iint!(i8, i16, i32, i64, i128, isize);
+impl_number_iint!(i8 => i64, i16 => i64, i32 => i128, i64 => i128, i128 => i128, isize => i128);
 
 /// A macro to implement the `Number` trait for primitive types.
 macro_rules! impl_number_uint {
-    ($($ty:ty),*) => {
+    ($($ty:ty => $acc:ty),*) => {
         $(
+            #[allow(
+                clippy::cast_lossless,
+                clippy::cast_possible_truncation,
+                clippy::cast_possible_wrap,
+                clippy::cast_precision_loss,
+                clippy::cast_sign_loss
+            )]
             impl Number for $ty {
+                type Accumulator = $acc;
+
                 fn zero() -> Self {
                     0
                 }
@@ -388,11 +479,11 @@ macro_rules! impl_number_uint {
                 }
 
                 fn mul_add(self, a: Self, b: Self) -> Self {
-                    self + a * b
+                    self * a + b
                 }
 
                 fn mul_add_assign(&mut self, a: Self, b: Self) {
-                    *self += a * b;
+                    *self = *self * a + b;
                 }
 
                 fn from<T: Number>(n: T) -> Self {
@@ -463,4 +554,4 @@ macro_rules! impl_number_uint {
     }
 }
 
-impl_number_uint!(u8, u16, u32, u64, u128, usize);
+impl_number_uint!(u8 => u64, u16 => u64, u32 => u128, u64 => u128, u128 => u128, usize => u128);
@@ -45,6 +45,7 @@ pub trait UInt: Number + Hash + Eq + Ord {
 macro_rules! impl_uint {
     ($($ty:ty),*) => {
         $(
+            #[allow(clippy::cast_lossless, clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
             impl UInt for $ty {
                 fn as_i64(self) -> i64 {
                     self as i64
@@ -79,27 +80,81 @@ pub trait Float: Number + core::ops::Neg<Output = Self> {
     /// Returns `self` raised to the power of `exp`.
     #[must_use]
     fn powf(self, exp: Self) -> Self;
+
+    /// Returns the natural logarithm of a `Float`.
+    #[must_use]
+    fn ln(self) -> Self;
+
+    /// Returns `e` raised to the power of `self`.
+    #[must_use]
+    fn exp(self) -> Self;
 }
 
-/// Macro to implement `UIntNumber` for all unsigned integer types.
+/// Macro to implement `Float` for all floating point types.
+///
+/// Without the `std` feature, the `libm` functions named after the type are
+/// used instead of the inherent methods.
 macro_rules! impl_float {
-    ($($ty:ty),*) => {
+    ($($ty:ty: $sqrt:ident, $cbrt:ident, $powf:ident, $ln:ident, $exp:ident);*) => {
         $(
             impl Float for $ty {
                 fn sqrt(self) -> Self {
-                    Self::sqrt(self)
+                    #[cfg(feature = "std")]
+                    {
+                        Self::sqrt(self)
+                    }
+                    #[cfg(not(feature = "std"))]
+                    {
+                        libm::$sqrt(self)
+                    }
                 }
 
                 fn cbrt(self) -> Self {
-                    Self::cbrt(self)
+                    #[cfg(feature = "std")]
+                    {
+                        Self::cbrt(self)
+                    }
+                    #[cfg(not(feature = "std"))]
+                    {
+                        libm::$cbrt(self)
+                    }
                 }
 
                 fn powf(self, exp: Self) -> Self {
-                    Self::powf(self, exp)
+                    #[cfg(feature = "std")]
+                    {
+                        Self::powf(self, exp)
+                    }
+                    #[cfg(not(feature = "std"))]
+                    {
+                        libm::$powf(self, exp)
+                    }
+                }
+
+                fn ln(self) -> Self {
+                    #[cfg(feature = "std")]
+                    {
+                        Self::ln(self)
+                    }
+                    #[cfg(not(feature = "std"))]
+                    {
+                        libm::$ln(self)
+                    }
+                }
+
+                fn exp(self) -> Self {
+                    #[cfg(feature = "std")]
+                    {
+                        Self::exp(self)
+                    }
+                    #[cfg(not(feature = "std"))]
+                    {
+                        libm::$exp(self)
+                    }
                 }
             }
         )*
     }
 }
 
-impl_float!(f32, f64);
+impl_float!(f32: sqrtf, cbrtf, powf, logf, expf; f64: sqrt, cbrt, pow, log, exp);
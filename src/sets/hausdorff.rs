@@ -0,0 +1,135 @@
+//! Hausdorff distance between sets of points.
+
+use crate::Number;
+
+/// Creates a function that computes the directed Hausdorff distance from one
+/// set of points to another.
+///
+/// The directed Hausdorff distance from `x` to `y` is the largest distance
+/// from a point in `x` to its nearest neighbor in `y`. It is not symmetric,
+/// and so it is not a metric. See [`hausdorff`] for the symmetric version.
+///
+/// The search for each nearest neighbor stops as soon as a point in `y` is
+/// found that is closer than the largest nearest-neighbor distance seen so
+/// far, since such a point in `x` cannot change the result. The search for
+/// each point also starts from the nearest neighbor of the previous point, so
+/// sets whose points are stored in a spatially coherent order, e.g. sampled
+/// along a curve, need very few distance computations. In the worst case, the
+/// cost is `O(n * m)` distance computations.
+///
+/// If either set is empty, the distance is zero.
+///
+/// # Arguments
+///
+/// * `metric`: The distance function between two points, e.g.
+///   [`crate::vectors::euclidean`].
+///
+/// # Examples
+///
+/// ```
+/// use distances::{sets::directed_hausdorff, vectors::euclidean};
+///
+/// let metric = directed_hausdorff(euclidean::<f32, f32>);
+///
+/// let x: Vec<Vec<f32>> = vec![vec![0.0, 0.0], vec![1.0, 0.0]];
+/// let y: Vec<Vec<f32>> = vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![5.0, 0.0]];
+///
+/// let distance: f32 = metric(&x, &y);
+/// assert!(distance.abs() <= f32::EPSILON);
+///
+/// let distance: f32 = metric(&y, &x);
+/// assert!((distance - 4.0).abs() <= f32::EPSILON);
+/// ```
+///
+/// # References
+///
+/// * [An Efficient Algorithm for Calculating the Exact Hausdorff Distance](https://doi.org/10.1109/TPAMI.2015.2408351)
+pub fn directed_hausdorff<T: Number, U: Number, P: AsRef<[T]>>(
+    metric: impl Fn(&[T], &[T]) -> U,
+) -> impl Fn(&[P], &[P]) -> U {
+    move |x: &[P], y: &[P]| _directed_hausdorff(x, y, &metric)
+}
+
+/// Creates a function that computes the Hausdorff distance between two sets
+/// of points.
+///
+/// The Hausdorff distance is the larger of the two [`directed_hausdorff`]
+/// distances between the sets. It is a metric on non-empty finite sets when
+/// the distance between points is a metric.
+///
+/// If either set is empty, the distance is zero.
+///
+/// # Arguments
+///
+/// * `metric`: The distance function between two points, e.g.
+///   [`crate::vectors::euclidean`].
+///
+/// # Examples
+///
+/// ```
+/// use distances::{sets::hausdorff, vectors::manhattan};
+///
+/// let metric = hausdorff(manhattan::<u32, u32>);
+///
+/// let x: Vec<[u32; 2]> = vec![[0, 0], [2, 2]];
+/// let y: Vec<[u32; 2]> = vec![[0, 1], [2, 2], [3, 3]];
+///
+/// let distance: u32 = metric(&x, &y);
+/// assert_eq!(distance, 2);
+/// ```
+///
+/// # References
+///
+/// * [Hausdorff distance](https://en.wikipedia.org/wiki/Hausdorff_distance)
+pub fn hausdorff<T: Number, U: Number, P: AsRef<[T]>>(
+    metric: impl Fn(&[T], &[T]) -> U,
+) -> impl Fn(&[P], &[P]) -> U {
+    move |x: &[P], y: &[P]| {
+        let d_xy = _directed_hausdorff(x, y, &metric);
+        let d_yx = _directed_hausdorff(y, x, &metric);
+        if d_xy < d_yx {
+            d_yx
+        } else {
+            d_xy
+        }
+    }
+}
+
+/// Computes the directed Hausdorff distance from `x` to `y` with early breaks.
+fn _directed_hausdorff<T: Number, U: Number, P: AsRef<[T]>>(
+    x: &[P],
+    y: &[P],
+    metric: impl Fn(&[T], &[T]) -> U,
+) -> U {
+    let mut c_max = U::zero();
+    if y.is_empty() {
+        return c_max;
+    }
+
+    // The index in `y` of the nearest neighbor of the previous point in `x`.
+    let mut start = 0;
+    for a in x {
+        let a = a.as_ref();
+        let mut c_min: Option<U> = None;
+
+        for k in (start..y.len()).chain(0..start) {
+            let d = metric(a, y[k].as_ref());
+            if c_min.is_none_or(|c| d < c) {
+                c_min = Some(d);
+                start = k;
+            }
+            if d < c_max {
+                // This point cannot increase the directed distance.
+                break;
+            }
+        }
+
+        if let Some(c) = c_min {
+            if c > c_max {
+                c_max = c;
+            }
+        }
+    }
+
+    c_max
+}
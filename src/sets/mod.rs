@@ -1,9 +1,15 @@
 //! Distance functions for sets.
 
-// use alloc::collections::btree_set::BTreeSet;  // no-std
-use std::collections::BTreeSet;
+mod hausdorff;
 
-use crate::number::{Float, Int};
+use alloc::collections::BTreeSet;
+
+use crate::{
+    number::{Float, Int},
+    DistanceError,
+};
+
+pub use hausdorff::{directed_hausdorff, hausdorff};
 
 /// Jaccard distance.
 ///
@@ -46,3 +52,29 @@ pub fn jaccard<T: Int, U: Float>(x: &[T], y: &[T]) -> U {
         U::one() - intersection / union
     }
 }
+
+/// Checked version of [`jaccard`].
+///
+/// # Errors
+///
+/// * [`DistanceError::EmptyInput`] if either set is empty.
+///
+/// # Examples
+///
+/// ```
+/// use distances::{sets::try_jaccard, DistanceError};
+///
+/// let x: Vec<u32> = vec![1, 2, 3];
+/// let y: Vec<u32> = vec![];
+///
+/// let distance: Result<f32, _> = try_jaccard(&x, &y);
+///
+/// assert_eq!(distance, Err(DistanceError::EmptyInput));
+/// ```
+pub fn try_jaccard<T: Int, U: Float>(x: &[T], y: &[T]) -> Result<U, DistanceError> {
+    if x.is_empty() || y.is_empty() {
+        Err(DistanceError::EmptyInput)
+    } else {
+        Ok(jaccard(x, y))
+    }
+}
@@ -1,4 +1,4 @@
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 
 define_ty!(F32x4, f32, f32, f32, f32);
 impl_minimal!(F32x4, f32, 4, x0, x1, x2, x3);
@@ -1,4 +1,4 @@
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 
 define_ty!(F64x8, f64, f64, f64, f64, f64, f64, f64, f64);
 impl_minimal!(F64x8, f64, 8, x0, x1, x2, x3, x4, x5, x6, x7);
@@ -167,6 +167,7 @@ macro_rules! impl_op16 {
 macro_rules! impl_distances {
     ($name:ident, $ty:ty) => {
         use super::Naive;
+        use crate::number::Float;
         impl $name {
             /// Calculate the squared distance between two SIMD lane-slices
             pub fn euclidean_inner(a: &[$ty], b: &[$ty]) -> $name {
@@ -209,7 +210,7 @@ macro_rules! impl_distances {
             }
 
             pub fn euclidean(a: &[$ty], b: &[$ty]) -> $ty {
-                $name::squared_euclidean(a, b).sqrt()
+                Float::sqrt($name::squared_euclidean(a, b))
             }
 
             pub fn cosine_acc(a: &[$ty], b: &[$ty]) -> [$ty; 3] {
@@ -248,7 +249,7 @@ macro_rules! impl_distances {
                 if xx < eps || yy < eps || xy < eps {
                     1 as $ty
                 } else {
-                    let d = 1 as $ty - xy / (xx * yy).sqrt();
+                    let d = 1 as $ty - xy / Float::sqrt(xx * yy);
                     if d < eps {
                         0 as $ty
                     } else {
@@ -277,15 +278,20 @@ macro_rules! impl_naive {
             }
 
             fn euclidean(self, other: Self) -> Self::Output {
-                Naive::squared_euclidean(self, other).sqrt()
+                Float::sqrt(Naive::squared_euclidean(self, other))
             }
 
             fn cosine_acc(self, other: Self) -> [Self::Output; 3] {
-                self.iter()
-                    .zip(other.iter())
-                    .fold([0 as Self::Output; 3], |[xx, yy, xy], (&a, &b)| {
-                        [a.mul_add(a, xx), b.mul_add(b, yy), a.mul_add(b, xy)]
-                    })
+                self.iter().zip(other.iter()).fold(
+                    [0 as Self::Output; 3],
+                    |[xx, yy, xy], (&a, &b)| {
+                        [
+                            Number::mul_add(a, a, xx),
+                            Number::mul_add(b, b, yy),
+                            Number::mul_add(a, b, xy),
+                        ]
+                    },
+                )
             }
 
             fn cosine(self, other: Self) -> Self::Output {
@@ -294,7 +300,7 @@ macro_rules! impl_naive {
                 if xx < eps || yy < eps || xy < eps {
                     1 as Self::Output
                 } else {
-                    let d = 1 as Self::Output - xy / (xx * yy).sqrt();
+                    let d = 1 as Self::Output - xy / Float::sqrt(xx * yy);
                     if d < eps {
                         0 as Self::Output
                     } else {
@@ -318,15 +324,20 @@ macro_rules! impl_naive {
             }
 
             fn euclidean(self, other: Self) -> $ty2 {
-                Naive::squared_euclidean(self, other).sqrt()
+                Float::sqrt(Naive::squared_euclidean(self, other))
             }
 
             fn cosine_acc(self, other: Self) -> [Self::Output; 3] {
-                self.iter()
-                    .zip(other.iter())
-                    .fold([0 as Self::Output; 3], |[xx, yy, xy], (&a, &b)| {
-                        [a.mul_add(a, xx), b.mul_add(b, yy), a.mul_add(b, xy)]
-                    })
+                self.iter().zip(other.iter()).fold(
+                    [0 as Self::Output; 3],
+                    |[xx, yy, xy], (&a, &b)| {
+                        [
+                            Number::mul_add(a, a, xx),
+                            Number::mul_add(b, b, yy),
+                            Number::mul_add(a, b, xy),
+                        ]
+                    },
+                )
             }
 
             fn cosine(self, other: Self) -> Self::Output {
@@ -335,7 +346,7 @@ macro_rules! impl_naive {
                 if xx < eps || yy < eps || xy < eps {
                     1 as Self::Output
                 } else {
-                    let d = 1 as Self::Output - xy / (xx * yy).sqrt();
+                    let d = 1 as Self::Output - xy / Float::sqrt(xx * yy);
                     if d < eps {
                         0 as Self::Output
                     } else {
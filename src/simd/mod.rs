@@ -1,10 +1,16 @@
 //! Provides simd-accelerated euclidean distance functions for vectors.
 #![allow(
     missing_docs,
+    clippy::cast_lossless,
+    clippy::cast_precision_loss,
     clippy::missing_docs_in_private_items,
     clippy::must_use_candidate
 )]
 
+use alloc::vec::Vec;
+
+use crate::{number::Float, Number};
+
 /// Computes the euclidean distance between two vectors.
 #[must_use]
 pub fn euclidean_f32(a: &[f32], b: &[f32]) -> f32 {
@@ -65,6 +71,7 @@ pub(crate) trait Naive {
 
     fn squared_euclidean(self, other: Self) -> Self::Output;
     fn euclidean(self, other: Self) -> Self::Output;
+    #[allow(dead_code)]
     fn cosine(self, other: Self) -> Self::Output;
     fn cosine_acc(self, other: Self) -> [Self::Output; 3];
 }
@@ -112,7 +119,7 @@ impl Vectorized for &[f32] {
     }
 
     fn euclidean(self, other: Self) -> Self::Output {
-        Vectorized::squared_euclidean(self, other).sqrt()
+        Float::sqrt(Vectorized::squared_euclidean(self, other))
     }
 
     fn cosine(self, other: Self) -> Self::Output {
@@ -135,7 +142,7 @@ impl Vectorized for &Vec<f32> {
     }
 
     fn euclidean(self, other: Self) -> Self::Output {
-        Vectorized::squared_euclidean(self, other).sqrt()
+        Float::sqrt(Vectorized::squared_euclidean(self, other))
     }
 
     fn cosine(self, other: Self) -> Self::Output {
@@ -158,7 +165,7 @@ impl Vectorized for &[f64] {
     }
 
     fn euclidean(self, other: Self) -> Self::Output {
-        Vectorized::squared_euclidean(self, other).sqrt()
+        Float::sqrt(Vectorized::squared_euclidean(self, other))
     }
 
     fn cosine(self, other: Self) -> Self::Output {
@@ -181,7 +188,7 @@ impl Vectorized for &Vec<f64> {
     }
 
     fn euclidean(self, other: Self) -> Self::Output {
-        Vectorized::squared_euclidean(self, other).sqrt()
+        Float::sqrt(Vectorized::squared_euclidean(self, other))
     }
 
     fn cosine(self, other: Self) -> Self::Output {
@@ -0,0 +1,157 @@
+//! Bit-parallel Levenshtein distance.
+
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+use crate::number::UInt;
+
+/// Computes the Levenshtein distance between two strings with Myers'
+/// bit-parallel algorithm, using Hyyrö's blocking for strings longer than 64
+/// characters.
+///
+/// The columns of the Wagner-Fischer table are encoded as bit-vectors of the
+/// vertical differences between adjacent cells, so that each column is
+/// computed with a handful of word operations per 64 characters of the shorter
+/// string. This takes `O(ceil(m / 64) * n)` time for strings with `n` and `m`
+/// characters, where `m <= n`.
+///
+/// The result is identical to that of [`levenshtein`](crate::strings::levenshtein),
+/// which dispatches to this function, and is computed over Unicode scalar
+/// values.
+///
+/// # Arguments
+///
+/// * `x`: The first string.
+/// * `y`: The second string.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::levenshtein_bit_parallel;
+///
+/// let x = "NAJIBEATSPEPPERS";
+/// let y = "NAJIBPEPPERSEATS";
+///
+/// let distance: u16 = levenshtein_bit_parallel(x, y);
+///
+/// assert_eq!(distance, 8);
+///
+/// let x = "ACGT".repeat(50);
+/// let y = "ACGA".repeat(50);
+///
+/// let distance: u16 = levenshtein_bit_parallel(&x, &y);
+///
+/// assert_eq!(distance, 50);
+/// ```
+///
+/// # References
+///
+/// * [A fast bit-vector algorithm for approximate string matching based on dynamic programming](https://doi.org/10.1145/316542.316550)
+/// * [A bit-vector algorithm for computing Levenshtein and Damerau edit distances](http://www.stringology.org/event/2002/p6.html)
+#[must_use]
+pub fn levenshtein_bit_parallel<U: UInt>(x: &str, y: &str) -> U {
+    let x = x.chars().collect::<Vec<_>>();
+    let y = y.chars().collect::<Vec<_>>();
+
+    // The shorter string is encoded in the bit-vectors.
+    let (text, pattern) = if x.len() < y.len() { (y, x) } else { (x, y) };
+
+    if pattern.is_empty() {
+        return U::from(text.len());
+    }
+
+    U::from(_myers(&text, &pattern))
+}
+
+/// The bit-masks of the positions at which each character occurs in a
+/// pattern, split into blocks of 64 characters.
+struct PatternMasks {
+    /// The number of 64-bit blocks.
+    blocks: usize,
+    /// The masks of ASCII characters, stored contiguously by character.
+    ascii: Vec<u64>,
+    /// The masks of all other characters.
+    other: BTreeMap<char, Vec<u64>>,
+}
+
+impl PatternMasks {
+    /// Builds the masks for a non-empty pattern.
+    fn new(pattern: &[char]) -> Self {
+        let blocks = pattern.len().div_ceil(64);
+        let mut ascii = vec![0; 128 * blocks];
+        let mut other = BTreeMap::new();
+
+        for (i, &c) in pattern.iter().enumerate() {
+            let (block, bit) = (i / 64, 1 << (i % 64));
+            if c.is_ascii() {
+                ascii[c as usize * blocks + block] |= bit;
+            } else {
+                other.entry(c).or_insert_with(|| vec![0; blocks])[block] |= bit;
+            }
+        }
+
+        Self {
+            blocks,
+            ascii,
+            other,
+        }
+    }
+
+    /// Returns the mask of `c` in the given block.
+    fn get(&self, c: char, block: usize) -> u64 {
+        if c.is_ascii() {
+            self.ascii[c as usize * self.blocks + block]
+        } else {
+            self.other.get(&c).map_or(0, |masks| masks[block])
+        }
+    }
+}
+
+/// Computes the Levenshtein distance between a text and a non-empty pattern.
+///
+/// The names of the bit-vectors follow Hyyrö's paper.
+#[allow(clippy::similar_names)]
+fn _myers(text: &[char], pattern: &[char]) -> usize {
+    let masks = PatternMasks::new(pattern);
+    let last_block = masks.blocks - 1;
+    let last_bit = 1 << ((pattern.len() - 1) % 64);
+
+    // The vertical positive and negative differences of the current column.
+    let mut vp = vec![!0_u64; masks.blocks];
+    let mut vn = vec![0_u64; masks.blocks];
+    let mut score = pattern.len();
+
+    for &c in text {
+        // The top row of the table increases by one in each column.
+        let (mut hp_carry, mut hn_carry) = (1, 0);
+
+        for block in 0..masks.blocks {
+            let x = masks.get(c, block) | hn_carry;
+            let d0 = (((x & vp[block]).wrapping_add(vp[block])) ^ vp[block]) | x | vn[block];
+
+            let mut hp = vn[block] | !(d0 | vp[block]);
+            let mut hn = d0 & vp[block];
+
+            let (hp_in, hn_in) = (hp_carry, hn_carry);
+            if block == last_block {
+                hp_carry = u64::from(hp & last_bit != 0);
+                hn_carry = u64::from(hn & last_bit != 0);
+            } else {
+                hp_carry = hp >> 63;
+                hn_carry = hn >> 63;
+            }
+
+            hp = (hp << 1) | hp_in;
+            hn = (hn << 1) | hn_in;
+
+            vp[block] = hn | !(d0 | hp);
+            vn[block] = hp & d0;
+        }
+
+        // The carries out of the last block are the horizontal differences in
+        // the bottom row.
+        score += usize::from(hp_carry != 0);
+        score -= usize::from(hn_carry != 0);
+    }
+
+    score
+}
@@ -0,0 +1,166 @@
+//! Levenshtein distance with a threshold.
+
+use alloc::{vec, vec::Vec};
+
+use crate::number::UInt;
+
+use super::Penalties;
+
+/// Computes the Levenshtein distance between two strings if it is at most
+/// `k`, and returns `None` otherwise.
+///
+/// This is much faster than computing the full distance when `k` is small
+/// relative to the lengths of the strings. See [`levenshtein_bounded_custom`]
+/// for details.
+///
+/// # Arguments
+///
+/// * `x`: The first string.
+/// * `y`: The second string.
+/// * `k`: The largest distance of interest.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::levenshtein_bounded;
+///
+/// let x = "NAJIBEATSPEPPERS";
+/// let y = "NAJIBPEPPERSEATS";
+///
+/// let distance: Option<u16> = levenshtein_bounded(x, y, 8);
+/// assert_eq!(distance, Some(8));
+///
+/// let distance: Option<u16> = levenshtein_bounded(x, y, 7);
+/// assert_eq!(distance, None);
+/// ```
+#[must_use]
+pub fn levenshtein_bounded<U: UInt>(x: &str, y: &str, k: U) -> Option<U> {
+    levenshtein_bounded_custom(Penalties::default())(x, y, k)
+}
+
+/// Creates a function to compute the Levenshtein distance between two strings
+/// using a custom set of penalties, if that distance is at most `k`.
+///
+/// The generated function takes the two strings and the threshold `k`, and
+/// returns `None` if the distance exceeds `k`.
+///
+/// Since every gap costs `penalties.gap`, an alignment that strays more than
+/// `k / gap` cells from the main diagonal of the dynamic programming table
+/// costs more than `k`. We therefore only fill a diagonal band of width
+/// `2 * k / gap + 1` (Ukkonen's cut-off), which takes `O(k / gap * n)` time.
+/// We return `None` without filling the table if the lengths of the strings
+/// differ by more than `k / gap`, and stop as soon as every cell in a row of
+/// the band exceeds `k`. If the gap penalty is zero, the whole table is
+/// filled but we still stop early.
+///
+/// # Arguments
+///
+/// * `penalties`: the set of penalties to use
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::{levenshtein_bounded_custom, Penalties};
+///
+/// let metric = levenshtein_bounded_custom(Penalties::new(0, 1, 2));
+///
+/// let x = "NAJIB";
+/// let y = "NAJIBEATS";
+///
+/// let distance: Option<u16> = metric(x, y, 8);
+/// assert_eq!(distance, Some(8));
+///
+/// let distance: Option<u16> = metric(x, y, 7);
+/// assert_eq!(distance, None);
+/// ```
+///
+/// # References
+///
+/// * [Algorithms for approximate string matching](https://doi.org/10.1016/S0019-9958(85)80046-2)
+pub fn levenshtein_bounded_custom<U: UInt>(
+    penalties: Penalties<U>,
+) -> impl Fn(&str, &str, U) -> Option<U> {
+    move |x: &str, y: &str, k: U| {
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+        _levenshtein_bounded(&x, &y, penalties, UInt::as_u64(k)).map(U::from)
+    }
+}
+
+/// Helper for the bounded Levenshtein distance.
+///
+/// This fills the band of the dynamic programming table around the main
+/// diagonal in `u64`, capping each cell at `k + 1` so that the arithmetic
+/// cannot overflow.
+fn _levenshtein_bounded<U: UInt>(
+    x: &[char],
+    y: &[char],
+    penalties: Penalties<U>,
+    k: u64,
+) -> Option<u64> {
+    let (match_, mismatch, gap) = (
+        UInt::as_u64(penalties.match_),
+        UInt::as_u64(penalties.mismatch),
+        UInt::as_u64(penalties.gap),
+    );
+    let (x_len, y_len) = (x.len(), y.len());
+    let cap = k.saturating_add(1);
+
+    // The number of diagonals on either side of the main diagonal that an
+    // alignment of cost at most `k` may reach.
+    let max_len = x_len.max(y_len);
+    let radius = k
+        .checked_div(gap)
+        .and_then(|r| usize::try_from(r).ok())
+        .map_or(max_len, |r| r.min(max_len));
+    if x_len.abs_diff(y_len) > radius {
+        return None;
+    }
+
+    // Cells outside the band of the previous row are never read.
+    let mut prev = (0..=y_len.min(radius))
+        .map(|j| (gap.saturating_mul(j as u64)).min(cap))
+        .chain(core::iter::repeat(cap))
+        .take(y_len + 1)
+        .collect::<Vec<_>>();
+    let mut cur = vec![cap; y_len + 1];
+
+    for (i, &c_x) in x.iter().enumerate().map(|(i, c)| (i + 1, c)) {
+        let lo = i.saturating_sub(radius);
+        let hi = (i + radius).min(y_len);
+        let prev_hi = (i - 1 + radius).min(y_len);
+
+        let mut row_min = cap;
+        for j in lo..=hi {
+            let value = if j == 0 {
+                gap.saturating_mul(i as u64)
+            } else {
+                // deletion
+                let deletion = if j <= prev_hi {
+                    prev[j].saturating_add(gap)
+                } else {
+                    cap
+                };
+                // insertion
+                let insertion = if j > lo {
+                    cur[j - 1].saturating_add(gap)
+                } else {
+                    cap
+                };
+                // match or substitution
+                let substitution =
+                    prev[j - 1].saturating_add(if c_x == y[j - 1] { match_ } else { mismatch });
+                deletion.min(insertion).min(substitution)
+            };
+            cur[j] = value.min(cap);
+            row_min = row_min.min(cur[j]);
+        }
+
+        if row_min > k {
+            return None;
+        }
+        core::mem::swap(&mut prev, &mut cur);
+    }
+
+    Some(prev[y_len]).filter(|&d| d <= k)
+}
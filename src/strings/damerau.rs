@@ -0,0 +1,290 @@
+//! Optimal String Alignment and Damerau-Levenshtein distances.
+
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+use crate::number::UInt;
+
+use super::Penalties;
+
+/// Penalties to use in the Optimal String Alignment and Damerau-Levenshtein
+/// distance calculations.
+///
+/// These extend [`Penalties`] with the penalty for transposing two adjacent
+/// characters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DamerauPenalties<U: UInt> {
+    /// Penalty for a match.
+    pub(crate) match_: U,
+    /// Penalty for a mis-match.
+    pub(crate) mismatch: U,
+    /// Penalty for a gap.
+    pub(crate) gap: U,
+    /// Penalty for a transposition of two adjacent characters.
+    pub(crate) transposition: U,
+}
+
+impl<U: UInt> Default for DamerauPenalties<U> {
+    fn default() -> Self {
+        Self {
+            match_: U::zero(),
+            mismatch: U::one(),
+            gap: U::one(),
+            transposition: U::one(),
+        }
+    }
+}
+
+impl<U: UInt> DamerauPenalties<U> {
+    /// Create a set of penalties to use for the OSA and Damerau-Levenshtein
+    /// distances.
+    pub const fn new(match_: U, mismatch: U, gap: U, transposition: U) -> Self {
+        Self {
+            match_,
+            mismatch,
+            gap,
+            transposition,
+        }
+    }
+
+    /// Extend a set of `Penalties` with a transposition penalty.
+    pub const fn from_penalties(penalties: Penalties<U>, transposition: U) -> Self {
+        Self::new(
+            penalties.match_,
+            penalties.mismatch,
+            penalties.gap,
+            transposition,
+        )
+    }
+
+    /// Returns the penalty for aligning `a` with `b`.
+    const fn substitution(&self, a: char, b: char) -> U {
+        if a == b {
+            self.match_
+        } else {
+            self.mismatch
+        }
+    }
+}
+
+/// Computes the Optimal String Alignment (OSA) distance between two strings.
+///
+/// The OSA distance, also called the restricted Damerau-Levenshtein
+/// distance, is the minimum number of insertions, deletions, substitutions
+/// and transpositions of adjacent characters needed to transform one string
+/// into the other, under the restriction that no substring is edited more
+/// than once. For example, "CA" cannot be turned into "ABC" by transposing
+/// to "AC" and then inserting a "B" between the transposed characters.
+///
+/// Because of this restriction, the OSA distance does not obey the triangle
+/// inequality, and so it is not a metric. See [`damerau_levenshtein`] for
+/// the unrestricted distance.
+///
+/// The input strings are not required to be of the same length, and the
+/// distance is computed over Unicode scalar values.
+///
+/// # Arguments
+///
+/// * `x`: The first string.
+/// * `y`: The second string.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::osa;
+///
+/// let distance: u16 = osa("NAJIB", "NAIJB");
+/// assert_eq!(distance, 1);
+///
+/// let distance: u16 = osa("CA", "ABC");
+/// assert_eq!(distance, 3);
+/// ```
+///
+/// # References
+///
+/// * [Optimal string alignment distance](https://en.wikipedia.org/wiki/Damerau%E2%80%93Levenshtein_distance#Optimal_string_alignment_distance)
+#[must_use]
+pub fn osa<U: UInt>(x: &str, y: &str) -> U {
+    osa_custom(DamerauPenalties::default())(x, y)
+}
+
+/// Creates a function to compute the Optimal String Alignment distance
+/// between two strings using a custom set of penalties. The generated
+/// function will have the same signature as [`osa`].
+///
+/// # Arguments
+///
+/// * `penalties`: the set of penalties to use
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::{osa_custom, DamerauPenalties};
+///
+/// let metric = osa_custom(DamerauPenalties::new(0, 1, 1, 2));
+///
+/// let distance: u16 = metric("NAJIB", "NAIJB");
+/// assert_eq!(distance, 2);
+/// ```
+pub fn osa_custom<U: UInt>(penalties: DamerauPenalties<U>) -> impl Fn(&str, &str) -> U {
+    move |x: &str, y: &str| {
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+        _osa(&x, &y, penalties)
+    }
+}
+
+/// Helper for the OSA distance.
+///
+/// This keeps the last three rows of the dynamic programming table.
+fn _osa<U: UInt>(x: &[char], y: &[char], penalties: DamerauPenalties<U>) -> U {
+    let gap = penalties.gap;
+    let mut prev_prev = vec![U::zero(); y.len() + 1];
+    let mut prev = (0..=y.len()).map(|j| gap * U::from(j)).collect::<Vec<_>>();
+    let mut cur = vec![U::zero(); y.len() + 1];
+
+    for i in 1..=x.len() {
+        cur[0] = gap * U::from(i);
+        for j in 1..=y.len() {
+            let mut value = core::cmp::min(
+                // deletion and insertion
+                core::cmp::min(prev[j], cur[j - 1]) + gap,
+                // match or substitution
+                prev[j - 1] + penalties.substitution(x[i - 1], y[j - 1]),
+            );
+            if i > 1 && j > 1 && x[i - 1] == y[j - 2] && x[i - 2] == y[j - 1] {
+                // transposition
+                value = core::cmp::min(value, prev_prev[j - 2] + penalties.transposition);
+            }
+            cur[j] = value;
+        }
+        core::mem::swap(&mut prev_prev, &mut prev);
+        core::mem::swap(&mut prev, &mut cur);
+    }
+
+    prev[y.len()]
+}
+
+/// Computes the Damerau-Levenshtein distance between two strings.
+///
+/// The Damerau-Levenshtein distance is the minimum number of insertions,
+/// deletions, substitutions and transpositions of adjacent characters needed
+/// to transform one string into the other. Unlike the [`osa`] distance, a
+/// substring may be edited more than once, e.g. "CA" can be turned into
+/// "ABC" by transposing to "AC" and then inserting a "B". It is a metric.
+///
+/// We use the algorithm of Lowrance and Wagner, which takes `O(n * m)` time
+/// and memory.
+///
+/// The input strings are not required to be of the same length, and the
+/// distance is computed over Unicode scalar values.
+///
+/// # Arguments
+///
+/// * `x`: The first string.
+/// * `y`: The second string.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::damerau_levenshtein;
+///
+/// let distance: u16 = damerau_levenshtein("NAJIB", "NAIJB");
+/// assert_eq!(distance, 1);
+///
+/// let distance: u16 = damerau_levenshtein("CA", "ABC");
+/// assert_eq!(distance, 2);
+/// ```
+///
+/// # References
+///
+/// * [Damerau-Levenshtein distance](https://en.wikipedia.org/wiki/Damerau%E2%80%93Levenshtein_distance)
+/// * [An Extension of the String-to-String Correction Problem](https://doi.org/10.1145/321879.321880)
+#[must_use]
+pub fn damerau_levenshtein<U: UInt>(x: &str, y: &str) -> U {
+    damerau_levenshtein_custom(DamerauPenalties::default())(x, y)
+}
+
+/// Creates a function to compute the Damerau-Levenshtein distance between two
+/// strings using a custom set of penalties. The generated function will have
+/// the same signature as [`damerau_levenshtein`].
+///
+/// The algorithm is only guaranteed to find the optimal alignment when the
+/// transposition penalty is at least the gap penalty, as required by Lowrance
+/// and Wagner. This holds for the default penalties.
+///
+/// # Arguments
+///
+/// * `penalties`: the set of penalties to use
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::{damerau_levenshtein_custom, DamerauPenalties};
+///
+/// let metric = damerau_levenshtein_custom(DamerauPenalties::new(0, 2, 1, 1));
+///
+/// let distance: u16 = metric("CA", "ABC");
+/// assert_eq!(distance, 2);
+///
+/// let distance: u16 = metric("NAJIB", "NAJIC");
+/// assert_eq!(distance, 2);
+/// ```
+pub fn damerau_levenshtein_custom<U: UInt>(
+    penalties: DamerauPenalties<U>,
+) -> impl Fn(&str, &str) -> U {
+    move |x: &str, y: &str| {
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+        _damerau_levenshtein(&x, &y, penalties)
+    }
+}
+
+/// Helper for the Damerau-Levenshtein distance.
+///
+/// `table[i][j]` holds the distance between the first `i` characters of `x`
+/// and the first `j` characters of `y`.
+fn _damerau_levenshtein<U: UInt>(x: &[char], y: &[char], penalties: DamerauPenalties<U>) -> U {
+    let gap = penalties.gap;
+    let mut table = vec![vec![U::zero(); y.len() + 1]; x.len() + 1];
+    for (j, cell) in table[0].iter_mut().enumerate() {
+        *cell = gap * U::from(j);
+    }
+
+    // The last row in which each character of `x` was seen.
+    let mut last_row = BTreeMap::new();
+
+    for i in 1..=x.len() {
+        table[i][0] = gap * U::from(i);
+        // The last column in this row in which `x[i - 1]` matched.
+        let mut last_match_col = 0;
+
+        for j in 1..=y.len() {
+            let k = last_row.get(&y[j - 1]).copied().unwrap_or(0);
+            let l = last_match_col;
+            if x[i - 1] == y[j - 1] {
+                last_match_col = j;
+            }
+
+            let mut value = core::cmp::min(
+                // deletion and insertion
+                core::cmp::min(table[i - 1][j], table[i][j - 1]) + gap,
+                // match or substitution
+                table[i - 1][j - 1] + penalties.substitution(x[i - 1], y[j - 1]),
+            );
+            if k > 0 && l > 0 {
+                // transpose `x[k - 1]` and `x[i - 1]`, deleting the characters
+                // between them in `x` and inserting those between them in `y`
+                let gaps = U::from((i - k - 1) + (j - l - 1));
+                value = core::cmp::min(
+                    value,
+                    table[k - 1][l - 1] + penalties.transposition + gap * gaps,
+                );
+            }
+            table[i][j] = value;
+        }
+
+        last_row.insert(x[i - 1], i);
+    }
+
+    table[x.len()][y.len()]
+}
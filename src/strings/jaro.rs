@@ -0,0 +1,173 @@
+//! Jaro and Jaro-Winkler distances.
+
+use alloc::{vec, vec::Vec};
+
+use crate::number::Float;
+
+/// The longest common prefix that is rewarded by the Jaro-Winkler similarity.
+const MAX_PREFIX: usize = 4;
+
+/// Computes the Jaro distance between two strings.
+///
+/// The Jaro similarity counts the characters that the two strings have in
+/// common within a window of half the length of the longer string, and the
+/// number of transpositions needed to put those characters in the same order.
+/// With `m` common characters and `t` transpositions, the similarity is the
+/// mean of `m / |x|`, `m / |y|` and `(m - t) / m`, or zero if `m` is zero. We
+/// return the distance, i.e. one minus the similarity, which lies in `[0, 1]`.
+///
+/// The Jaro distance is not a metric, since it does not obey the triangle
+/// inequality. Two empty strings are at distance zero, and an empty string is
+/// at distance one from any other string. The distance is computed over
+/// Unicode scalar values.
+///
+/// # Arguments
+///
+/// * `x`: The first string.
+/// * `y`: The second string.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::jaro;
+///
+/// let distance: f64 = jaro("MARTHA", "MARHTA");
+/// assert!((distance - (1.0 - 17.0 / 18.0)).abs() <= f64::EPSILON);
+///
+/// let distance: f64 = jaro("ÉCOLE", "ÉCLOE");
+/// assert!((distance - (1.0 - 14.0 / 15.0)).abs() <= f64::EPSILON);
+/// ```
+///
+/// # References
+///
+/// * [Jaro-Winkler distance](https://en.wikipedia.org/wiki/Jaro%E2%80%93Winkler_distance)
+#[must_use]
+pub fn jaro<U: Float>(x: &str, y: &str) -> U {
+    let x = x.chars().collect::<Vec<_>>();
+    let y = y.chars().collect::<Vec<_>>();
+    U::one() - _jaro_similarity(&x, &y)
+}
+
+/// Computes the Jaro-Winkler distance between two strings, using the
+/// standard prefix scale of `0.1` and boost threshold of `0.7`.
+///
+/// This is a shorthand for `jaro_winkler_custom(0.1, 0.7)`. See
+/// [`jaro_winkler_custom`] for details.
+///
+/// # Arguments
+///
+/// * `x`: The first string.
+/// * `y`: The second string.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::jaro_winkler;
+///
+/// let distance: f64 = jaro_winkler("MARTHA", "MARHTA");
+/// assert!((distance - (1.0 - 0.961_111_111_111_111)).abs() <= 1e-12);
+/// ```
+#[must_use]
+pub fn jaro_winkler<U: Float>(x: &str, y: &str) -> U {
+    let prefix_scale = U::one() / U::from(10);
+    let boost_threshold = U::from(7) / U::from(10);
+    jaro_winkler_custom(prefix_scale, boost_threshold)(x, y)
+}
+
+/// Creates a function that computes the Jaro-Winkler distance between two
+/// strings with a custom prefix scale and boost threshold.
+///
+/// The Jaro-Winkler similarity boosts the [`jaro`] similarity `s` of strings
+/// sharing a common prefix of length `l`, up to four characters, to
+/// `s + l * prefix_scale * (1 - s)`. The boost is only applied when `s` is
+/// above the boost threshold. We return the distance, i.e. one minus the
+/// similarity, which lies in `[0, 1]` as long as the prefix scale is at most
+/// `0.25`.
+///
+/// Like the Jaro distance, the Jaro-Winkler distance is not a metric.
+///
+/// # Arguments
+///
+/// * `prefix_scale`: How much each character of the common prefix boosts the
+///   similarity.
+/// * `boost_threshold`: The Jaro similarity above which the boost is applied.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::jaro_winkler_custom;
+///
+/// let metric = jaro_winkler_custom(0.2, 0.0);
+///
+/// let distance: f64 = metric("DIXON", "DICKSONX");
+/// assert!((distance - 0.6 * (1.0 - 0.766_666_666_666_666_7)).abs() <= 1e-12);
+///
+/// let metric = jaro_winkler_custom(0.2, 0.8);
+///
+/// let distance: f64 = metric("DIXON", "DICKSONX");
+/// assert!((distance - (1.0 - 0.766_666_666_666_666_7)).abs() <= 1e-12);
+/// ```
+pub fn jaro_winkler_custom<U: Float>(
+    prefix_scale: U,
+    boost_threshold: U,
+) -> impl Fn(&str, &str) -> U {
+    move |x: &str, y: &str| {
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+
+        let similarity = _jaro_similarity::<U>(&x, &y);
+        if similarity > boost_threshold {
+            let prefix = x
+                .iter()
+                .zip(y.iter())
+                .take(MAX_PREFIX)
+                .take_while(|(a, b)| a == b)
+                .count();
+            let boost = U::from(prefix) * prefix_scale;
+            (U::one() - similarity) * (U::one() - boost)
+        } else {
+            U::one() - similarity
+        }
+    }
+}
+
+/// Computes the Jaro similarity between two sequences of characters.
+fn _jaro_similarity<U: Float>(x: &[char], y: &[char]) -> U {
+    if x.is_empty() && y.is_empty() {
+        return U::one();
+    } else if x.is_empty() || y.is_empty() {
+        return U::zero();
+    }
+
+    // Characters match if they are equal and no farther apart than this.
+    let window = (x.len().max(y.len()) / 2).saturating_sub(1);
+
+    let mut x_matched = vec![false; x.len()];
+    let mut y_matched = vec![false; y.len()];
+    let mut matches = 0;
+    for (i, &a) in x.iter().enumerate() {
+        let lo = i.saturating_sub(window);
+        let hi = (i + window + 1).min(y.len());
+        for j in lo..hi {
+            if !y_matched[j] && y[j] == a {
+                x_matched[i] = true;
+                y_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+
+    if matches == 0 {
+        return U::zero();
+    }
+
+    // Each transposition puts two matched characters out of order.
+    let x_common = x.iter().zip(x_matched).filter(|&(_, m)| m).map(|(c, _)| c);
+    let y_common = y.iter().zip(y_matched).filter(|&(_, m)| m).map(|(c, _)| c);
+    let out_of_order = x_common.zip(y_common).filter(|(a, b)| a != b).count();
+
+    let m = U::from(matches);
+    let t = U::from(out_of_order) / U::from(2);
+    (m / U::from(x.len()) + m / U::from(y.len()) + (m - t) / m) / U::from(3)
+}
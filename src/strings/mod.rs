@@ -1,19 +1,37 @@
 //! String distance metrics.
 
-// use alloc::vec::Vec;  // no-std
+use alloc::vec::Vec;
 
-use crate::number::UInt;
+use crate::{number::UInt, DistanceError};
 
+mod bit_parallel;
+mod bounded;
+mod damerau;
+mod jaro;
 pub mod needleman_wunsch;
+mod normalized;
+pub mod sequences;
+pub mod smith_waterman;
+mod substitution;
 
-pub use needleman_wunsch::nw_distance;
+pub use bit_parallel::levenshtein_bit_parallel;
+pub use bounded::{levenshtein_bounded, levenshtein_bounded_custom};
+pub use damerau::{
+    damerau_levenshtein, damerau_levenshtein_custom, osa, osa_custom, DamerauPenalties,
+};
+pub use jaro::{jaro, jaro_winkler, jaro_winkler_custom};
+pub use needleman_wunsch::{nw_distance, try_nw_distance};
+pub use normalized::{
+    hamming_normalized, levenshtein_normalized, levenshtein_yujian_bo, nw_distance_normalized,
+};
+pub use substitution::{MatrixParseError, MatrixPenalties, SubstitutionMatrix};
 
 /// Penalties to use in the Needleman-Wunsch distance calculation.
 ///
 /// Since we provide a distance implementation that is intended to be used as a
 /// metric that obeys the triangle inequality, the penalties should all be
 /// non-negative, thus the genericity over unsigned integers.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Penalties<U: UInt> {
     /// Penalty for a match.
     pub(crate) match_: U,
@@ -42,12 +60,78 @@ impl<U: UInt> Penalties<U> {
             gap,
         }
     }
+
+    /// Returns the penalty for a gap.
+    ///
+    /// This is the same as [`PenaltyScheme::gap`], which `Penalties`
+    /// implement for any type of element.
+    pub const fn gap(&self) -> U {
+        self.gap
+    }
+}
+
+/// A scheme of penalties for aligning two sequences, as used by the
+/// Levenshtein and Needleman-Wunsch distances.
+///
+/// The elements of the sequences are `char`s for strings, and any other type
+/// for the [`sequences`] of tokens. This is implemented by [`Penalties`],
+/// which charge the same penalty for every mismatch of any type of element,
+/// and by [`MatrixPenalties`], which look the penalty for aligning two
+/// characters up in a [`SubstitutionMatrix`].
+pub trait PenaltyScheme<U: UInt, T = char> {
+    /// Returns the penalty for aligning `a` with `b`.
+    fn substitution(&self, a: &T, b: &T) -> U;
+
+    /// Returns the penalty for a gap.
+    fn gap(&self) -> U;
+
+    /// Returns whether these are the unit penalties of the Levenshtein
+    /// distance, for which a faster algorithm can be used.
+    fn is_unit(&self) -> bool {
+        false
+    }
+}
+
+impl<U: UInt, T: Eq> PenaltyScheme<U, T> for Penalties<U> {
+    fn substitution(&self, a: &T, b: &T) -> U {
+        if a == b {
+            self.match_
+        } else {
+            self.mismatch
+        }
+    }
+
+    fn gap(&self) -> U {
+        self.gap
+    }
+
+    fn is_unit(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl<U: UInt, T, P: PenaltyScheme<U, T>> PenaltyScheme<U, T> for &P {
+    fn substitution(&self, a: &T, b: &T) -> U {
+        (**self).substitution(a, b)
+    }
+
+    fn gap(&self) -> U {
+        (**self).gap()
+    }
+
+    fn is_unit(&self) -> bool {
+        (**self).is_unit()
+    }
 }
 
 /// Creates a function to compute the Levenshtein distance between two strings
 /// using a custom set of penalties. The generated function will have the same
 /// signature as `levenshtein`.
 ///
+/// The penalties may be a set of [`Penalties`], or [`MatrixPenalties`] derived
+/// from a substitution matrix. With the default penalties, this uses the same
+/// bit-parallel algorithm as [`levenshtein`].
+///
 /// # Arguments
 ///
 /// * `penalties`: the set of penalties to use
@@ -70,19 +154,14 @@ impl<U: UInt> Penalties<U> {
 /// let distance: u16 = metric(x, y);
 /// assert_eq!(distance, 6);
 /// ```
-pub fn levenshtein_custom<U: UInt>(penalties: Penalties<U>) -> impl Fn(&str, &str) -> U {
+pub fn levenshtein_custom<U: UInt>(penalties: impl PenaltyScheme<U>) -> impl Fn(&str, &str) -> U {
     move |x: &str, y: &str| {
-        if x.is_empty() {
-            // handle special case of 0 length
-            U::from(y.len())
-        } else if y.is_empty() {
-            // handle special case of 0 length
-            U::from(x.len())
-        } else if x.len() < y.len() {
-            // require tat a is no shorter than b
-            _levenshtein(y, x, penalties)
+        if penalties.is_unit() {
+            levenshtein_bit_parallel(x, y)
         } else {
-            _levenshtein(x, y, penalties)
+            let x = x.chars().collect::<Vec<_>>();
+            let y = y.chars().collect::<Vec<_>>();
+            _levenshtein(&x, &y, &penalties)
         }
     }
 }
@@ -95,10 +174,11 @@ pub fn levenshtein_custom<U: UInt>(penalties: Penalties<U>) -> impl Fn(&str, &st
 /// single character. It is named after Vladimir Levenshtein, who
 /// considered this distance in 1965.
 ///
-/// We use the Wagner-Fischer algorithm to compute the Levenshtein
-/// distance. The Wagner-Fischer algorithm is a dynamic programming
-/// algorithm that computes the edit distance between two strings of
-/// characters.
+/// We use Myers' bit-parallel algorithm, with Hyyrö's blocking for strings
+/// longer than 64 characters, to compute the Levenshtein distance. See
+/// [`levenshtein_bit_parallel`] for details. This gives the same result as
+/// the Wagner-Fischer algorithm used by [`levenshtein_custom`] for other
+/// penalties.
 ///
 /// We use penalty values of `1` for all edit operations and we minimize the
 /// total penalty for aligning the two strings.
@@ -135,48 +215,43 @@ pub fn levenshtein_custom<U: UInt>(penalties: Penalties<U>) -> impl Fn(&str, &st
 /// * [Levenshtein distance](https://en.wikipedia.org/wiki/Levenshtein_distance)
 #[must_use]
 pub fn levenshtein<U: UInt>(x: &str, y: &str) -> U {
-    if x.is_empty() {
-        // handle special case of 0 length
-        U::from(y.len())
-    } else if y.is_empty() {
-        // handle special case of 0 length
-        U::from(x.len())
-    } else if x.len() < y.len() {
-        // require tat a is no shorter than b
-        _levenshtein(y, x, Penalties::default())
-    } else {
-        _levenshtein(x, y, Penalties::default())
-    }
+    levenshtein_bit_parallel(x, y)
 }
 
 /// Helper for Levenshtein distance.
 /// This function actually performs the dynamic programming for the
 /// Levenshtein edit distance, using the `penalties` struct.
-#[allow(unused_variables)]
-fn _levenshtein<U: UInt>(x: &str, y: &str, penalties: Penalties<U>) -> U {
-    // initialize DP table for string y
-    // this is a bit ugly with the U casts
-    let mut cur = (0..=y.len()).map(U::from).collect::<Vec<_>>();
+///
+/// This works on the characters of strings as well as on other
+/// [`sequences`], keeping one row of the table for the shorter sequence.
+pub(crate) fn _levenshtein<T, U: UInt>(
+    x: &[T],
+    y: &[T],
+    penalties: &impl PenaltyScheme<U, T>,
+) -> U {
+    // require that `x` is no shorter than `y`
+    let (x, y) = if x.len() < y.len() { (y, x) } else { (x, y) };
+
+    // initialize DP table for `y`
+    let mut cur = (0..=y.len())
+        .map(|j| penalties.gap() * U::from(j))
+        .collect::<Vec<_>>();
 
     // calculate edit distance
-    for (i, c_x) in x.chars().enumerate().map(|(i, c)| (U::from(i + 1), c)) {
+    for (i, a) in x.iter().enumerate() {
         // get first column for this row
         let mut pre = cur[0];
-        cur[0] = i;
-        for (j, c_y) in y.chars().enumerate() {
+        cur[0] = penalties.gap() * U::from(i + 1);
+        for (j, b) in y.iter().enumerate() {
             let tmp = cur[j + 1];
             cur[j + 1] = core::cmp::min(
                 // deletion
-                tmp + penalties.gap,
+                tmp + penalties.gap(),
                 core::cmp::min(
                     // insertion
-                    cur[j] + penalties.gap,
+                    cur[j] + penalties.gap(),
                     // match or substitution
-                    pre + if c_x == c_y {
-                        penalties.match_
-                    } else {
-                        penalties.mismatch
-                    },
+                    pre + penalties.substitution(a, b),
                 ),
             );
             pre = tmp;
@@ -229,8 +304,73 @@ pub fn hamming<U: UInt>(x: &str, y: &str) -> U {
     U::from(x.chars().zip(y.chars()).filter(|(a, b)| a != b).count())
 }
 
+/// Checks that neither of two strings is empty.
+pub(crate) const fn check_strings(x: &str, y: &str) -> Result<(), DistanceError> {
+    if x.is_empty() || y.is_empty() {
+        Err(DistanceError::EmptyInput)
+    } else {
+        Ok(())
+    }
+}
+
+/// Checked version of [`levenshtein`].
+///
+/// # Errors
+///
+/// * [`DistanceError::EmptyInput`] if either string is empty.
+pub fn try_levenshtein<U: UInt>(x: &str, y: &str) -> Result<U, DistanceError> {
+    check_strings(x, y)?;
+    Ok(levenshtein(x, y))
+}
+
+/// Checked version of [`levenshtein_custom`].
+///
+/// The generated function returns [`DistanceError::EmptyInput`] if either
+/// string is empty.
+pub fn try_levenshtein_custom<U: UInt>(
+    penalties: impl PenaltyScheme<U>,
+) -> impl Fn(&str, &str) -> Result<U, DistanceError> {
+    let metric = levenshtein_custom(penalties);
+    move |x: &str, y: &str| {
+        check_strings(x, y)?;
+        Ok(metric(x, y))
+    }
+}
+
+/// Checked version of [`hamming`].
+///
+/// Unlike `hamming`, this does not silently ignore the tail of the longer
+/// string.
+///
+/// # Errors
+///
+/// * [`DistanceError::EmptyInput`] if either string is empty.
+/// * [`DistanceError::LengthMismatch`] if the strings have different numbers
+///   of characters.
+///
+/// # Examples
+///
+/// ```
+/// use distances::{strings::try_hamming, DistanceError};
+///
+/// let distance: Result<u16, _> = try_hamming("NAJIB", "NAJIBE");
+///
+/// assert_eq!(distance, Err(DistanceError::LengthMismatch(5, 6)));
+/// ```
+pub fn try_hamming<U: UInt>(x: &str, y: &str) -> Result<U, DistanceError> {
+    check_strings(x, y)?;
+    let (x_len, y_len) = (x.chars().count(), y.chars().count());
+    if x_len == y_len {
+        Ok(hamming(x, y))
+    } else {
+        Err(DistanceError::LengthMismatch(x_len, y_len))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use alloc::string::ToString;
+
     use crate::strings::levenshtein;
 
     #[test]
@@ -0,0 +1,548 @@
+//! CIGAR strings and MD tags for the alignment of two strings.
+//!
+//! In the terms of the SAM format, the first string `x` is the reference and
+//! the second string `y` is the query.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::strings::sequences::AlignedPair;
+
+use super::{
+    helpers::{aligned_strings, compute_edits},
+    Edit,
+};
+
+/// An operation in a CIGAR string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CigarOp {
+    /// `=`: A character of the reference aligned with an equal character of
+    /// the query.
+    Equal,
+    /// `X`: A character of the reference aligned with a different character
+    /// of the query.
+    Mismatch,
+    /// `I`: A character of the query aligned with a gap in the reference.
+    Insertion,
+    /// `D`: A character of the reference aligned with a gap in the query.
+    Deletion,
+    /// `M`: A character of the reference aligned with a character of the
+    /// query, which may or may not be equal. This is only read, and never
+    /// produced, by [`Cigar`].
+    AlignmentMatch,
+}
+
+impl CigarOp {
+    /// Returns the character for the operation in a CIGAR string.
+    #[must_use]
+    pub const fn as_char(self) -> char {
+        match self {
+            Self::Equal => '=',
+            Self::Mismatch => 'X',
+            Self::Insertion => 'I',
+            Self::Deletion => 'D',
+            Self::AlignmentMatch => 'M',
+        }
+    }
+
+    /// Returns the operation for a character of a CIGAR string, if any.
+    #[must_use]
+    pub const fn from_char(c: char) -> Option<Self> {
+        match c {
+            '=' => Some(Self::Equal),
+            'X' => Some(Self::Mismatch),
+            'I' => Some(Self::Insertion),
+            'D' => Some(Self::Deletion),
+            'M' => Some(Self::AlignmentMatch),
+            _ => None,
+        }
+    }
+
+    /// Whether the operation consumes a character of the reference.
+    const fn consumes_reference(self) -> bool {
+        !matches!(self, Self::Insertion)
+    }
+
+    /// Whether the operation consumes a character of the query.
+    const fn consumes_query(self) -> bool {
+        !matches!(self, Self::Deletion)
+    }
+}
+
+/// The reasons a CIGAR string could not be parsed or applied to a pair of
+/// strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CigarError {
+    /// The operation at the given byte offset has no length.
+    MissingLength(usize),
+    /// The length ending at the given byte offset is zero or too large.
+    InvalidLength(usize),
+    /// The given character is not a CIGAR operation.
+    InvalidOp(char),
+    /// The text ends with a length that has no operation.
+    TrailingLength,
+    /// The number of characters of the reference consumed by the CIGAR, given
+    /// first, differs from the length of the reference, given second.
+    ReferenceLength(usize, usize),
+    /// The number of characters of the query consumed by the CIGAR, given
+    /// first, differs from the length of the query, given second.
+    QueryLength(usize, usize),
+    /// The `=` or `X` operation at the given column of the alignment does not
+    /// agree with the characters aligned there.
+    WrongOp(usize),
+}
+
+impl core::fmt::Display for CigarError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingLength(offset) => write!(f, "operation at offset {offset} has no length"),
+            Self::InvalidLength(offset) => write!(f, "invalid length at offset {offset}"),
+            Self::InvalidOp(c) => write!(f, "{c:?} is not a CIGAR operation"),
+            Self::TrailingLength => write!(f, "the CIGAR ends with a length"),
+            Self::ReferenceLength(c, x) => {
+                write!(
+                    f,
+                    "the CIGAR consumes {c} characters of a reference of length {x}"
+                )
+            }
+            Self::QueryLength(c, y) => {
+                write!(
+                    f,
+                    "the CIGAR consumes {c} characters of a query of length {y}"
+                )
+            }
+            Self::WrongOp(column) => write!(f, "wrong operation at column {column}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CigarError {}
+
+/// An alignment of two strings as a run-length encoded sequence of
+/// [`CigarOp`]s, e.g. `5=1X1I3=2D`.
+///
+/// The first string `x` is the reference and the second string `y` is the
+/// query, so that a gap in `x` is an insertion and a gap in `y` is a
+/// deletion. Consecutive runs of the same operation are always merged, and
+/// the empty alignment is written as `*`.
+///
+/// A `Cigar` is converted to and from aligned strings, as computed by the
+/// Needleman-Wunsch trace back, and scripts of [`Edit`]s. Given the two
+/// strings, it also gives the MD tag of the SAM format.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Cigar {
+    /// The runs of operations, as `(length, operation)`.
+    ops: Vec<(usize, CigarOp)>,
+}
+
+impl Cigar {
+    /// Returns the runs of operations, as `(length, operation)`.
+    #[must_use]
+    pub fn ops(&self) -> &[(usize, CigarOp)] {
+        &self.ops
+    }
+
+    /// Returns the number of characters of the reference in the alignment.
+    #[must_use]
+    pub fn reference_len(&self) -> usize {
+        self.ops
+            .iter()
+            .filter(|(_, op)| op.consumes_reference())
+            .map(|&(n, _)| n)
+            .sum()
+    }
+
+    /// Returns the number of characters of the query in the alignment.
+    #[must_use]
+    pub fn query_len(&self) -> usize {
+        self.ops
+            .iter()
+            .filter(|(_, op)| op.consumes_query())
+            .map(|&(n, _)| n)
+            .sum()
+    }
+
+    /// Appends `n` operations to the alignment.
+    fn extend(&mut self, n: usize, op: CigarOp) {
+        match self.ops.last_mut() {
+            Some((m, last)) if *last == op => *m += n,
+            _ => self.ops.push((n, op)),
+        }
+    }
+
+    /// Computes the CIGAR of an alignment of two sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use distances::strings::{needleman_wunsch::Cigar, sequences::nw_alignment};
+    ///
+    /// let x = ["the", "quick", "brown", "fox"];
+    /// let y = ["the", "brown", "fox", "jumps"];
+    /// let (alignment, _) = nw_alignment::<_, u8>(&x, &y);
+    ///
+    /// assert_eq!(Cigar::from_alignment(&alignment).to_string(), "1=1D2=1I");
+    /// ```
+    #[must_use]
+    pub fn from_alignment<T>(alignment: &[AlignedPair<T>]) -> Self {
+        let mut cigar = Self::default();
+        for pair in alignment {
+            let op = match pair {
+                AlignedPair::Match(..) => CigarOp::Equal,
+                AlignedPair::Mismatch(..) => CigarOp::Mismatch,
+                AlignedPair::GapInX(_) => CigarOp::Insertion,
+                AlignedPair::GapInY(_) => CigarOp::Deletion,
+            };
+            cigar.extend(1, op);
+        }
+        cigar
+    }
+
+    /// Computes the CIGAR of two aligned strings, with `-` for gaps.
+    ///
+    /// Since `-` is read as a gap, this cannot be used for strings that
+    /// contain it. Use [`Cigar::from_alignment`] for those.
+    ///
+    /// Columns in which both strings have a gap are ignored, as are the
+    /// trailing characters of the longer string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use distances::strings::needleman_wunsch::Cigar;
+    ///
+    /// let cigar = Cigar::from_aligned("NAJIB-PEPPERSEATS", "NAJIBEATSPEPPE-RS");
+    /// assert_eq!(cigar.to_string(), "5=1I3X2=2X1=1D1X1=");
+    /// ```
+    #[must_use]
+    pub fn from_aligned(aligned_x: &str, aligned_y: &str) -> Self {
+        let mut cigar = Self::default();
+        for (a, b) in aligned_x.chars().zip(aligned_y.chars()) {
+            let op = match (a, b) {
+                ('-', '-') => continue,
+                ('-', _) => CigarOp::Insertion,
+                (_, '-') => CigarOp::Deletion,
+                _ if a == b => CigarOp::Equal,
+                _ => CigarOp::Mismatch,
+            };
+            cigar.extend(1, op);
+        }
+        cigar
+    }
+
+    /// Computes the CIGAR of the alignment given by a script of edits from
+    /// the reference `x` to the query.
+    ///
+    /// The characters of `x` which are never deleted are aligned with the
+    /// characters they became, and all others are gaps. This works for any
+    /// script of edits, not only those computed from an alignment, but for
+    /// the latter it gives the same CIGAR as the alignment.
+    ///
+    /// # Panics
+    ///
+    /// * If the index of an edit is out of bounds for the string as edited by
+    ///   the previous edits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use distances::strings::needleman_wunsch::{edits_iterative, Cigar};
+    ///
+    /// let ([x_to_y, _], _) = edits_iterative::<u16>("ACGTTA", "ACTTAG");
+    /// assert_eq!(Cigar::from_edits("ACGTTA", &x_to_y).to_string(), "2=1D3=1I");
+    /// ```
+    #[must_use]
+    pub fn from_edits(x: &str, edits: &[Edit]) -> Self {
+        let x = x.chars().collect::<Vec<_>>();
+
+        // Each character of the edited string, along with the index of the
+        // character of `x` it came from, unless it was inserted.
+        let mut edited = x
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| (Some(i), c))
+            .collect::<Vec<_>>();
+        for edit in edits {
+            match edit {
+                Edit::Del(i) => {
+                    edited.remove(*i);
+                }
+                Edit::Ins(i, c) => edited.insert(*i, (None, *c)),
+                Edit::Sub(i, c) => edited[*i].1 = *c,
+            }
+        }
+
+        // The characters of `x` that remain are in order, and those between
+        // them were deleted.
+        let mut cigar = Self::default();
+        let mut next = 0;
+        for (origin, c) in edited {
+            match origin {
+                Some(i) => {
+                    if i > next {
+                        cigar.extend(i - next, CigarOp::Deletion);
+                    }
+                    let op = if x[i] == c {
+                        CigarOp::Equal
+                    } else {
+                        CigarOp::Mismatch
+                    };
+                    cigar.extend(1, op);
+                    next = i + 1;
+                }
+                None => cigar.extend(1, CigarOp::Insertion),
+            }
+        }
+        if x.len() > next {
+            cigar.extend(x.len() - next, CigarOp::Deletion);
+        }
+        cigar
+    }
+
+    /// Aligns the reference `x` with the query `y` along the CIGAR.
+    ///
+    /// # Errors
+    ///
+    /// * [`CigarError::ReferenceLength`] or [`CigarError::QueryLength`] if
+    ///   the CIGAR does not consume exactly the characters of `x` or `y`.
+    /// * [`CigarError::WrongOp`] if a `=` aligns different characters or an
+    ///   `X` aligns equal characters.
+    fn pairs(&self, x: &str, y: &str) -> Result<Vec<AlignedPair<char>>, CigarError> {
+        let (x_len, y_len) = (x.chars().count(), y.chars().count());
+        if self.reference_len() != x_len {
+            return Err(CigarError::ReferenceLength(self.reference_len(), x_len));
+        }
+        if self.query_len() != y_len {
+            return Err(CigarError::QueryLength(self.query_len(), y_len));
+        }
+
+        let (mut x, mut y) = (x.chars(), y.chars());
+        let mut next_x = || {
+            x.next()
+                .unwrap_or_else(|| unreachable!("We checked the length of x."))
+        };
+        let mut next_y = || {
+            y.next()
+                .unwrap_or_else(|| unreachable!("We checked the length of y."))
+        };
+
+        let mut pairs = Vec::with_capacity(self.ops.iter().map(|&(n, _)| n).sum());
+        for &(n, op) in &self.ops {
+            for _ in 0..n {
+                let pair = match op {
+                    CigarOp::Insertion => AlignedPair::GapInX(next_y()),
+                    CigarOp::Deletion => AlignedPair::GapInY(next_x()),
+                    CigarOp::Equal | CigarOp::Mismatch | CigarOp::AlignmentMatch => {
+                        let (a, b) = (next_x(), next_y());
+                        match (op, a == b) {
+                            (CigarOp::Mismatch, true) | (CigarOp::Equal, false) => {
+                                return Err(CigarError::WrongOp(pairs.len()));
+                            }
+                            (_, true) => AlignedPair::Match(a, b),
+                            (_, false) => AlignedPair::Mismatch(a, b),
+                        }
+                    }
+                };
+                pairs.push(pair);
+            }
+        }
+        Ok(pairs)
+    }
+
+    /// Aligns the reference `x` with the query `y` along the CIGAR, giving
+    /// the aligned strings with `-` for gaps.
+    ///
+    /// # Errors
+    ///
+    /// * [`CigarError::ReferenceLength`] or [`CigarError::QueryLength`] if
+    ///   the CIGAR does not consume exactly the characters of `x` or `y`.
+    /// * [`CigarError::WrongOp`] if a `=` aligns different characters or an
+    ///   `X` aligns equal characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use distances::strings::needleman_wunsch::Cigar;
+    ///
+    /// let cigar: Cigar = "2M1D3M1I".parse().unwrap();
+    /// let (aligned_x, aligned_y) = cigar.to_aligned("ACGTTA", "ACTTAG").unwrap();
+    ///
+    /// assert_eq!(aligned_x, "ACGTTA-");
+    /// assert_eq!(aligned_y, "AC-TTAG");
+    /// ```
+    pub fn to_aligned(&self, x: &str, y: &str) -> Result<(String, String), CigarError> {
+        self.pairs(x, y).map(|pairs| aligned_strings(&pairs))
+    }
+
+    /// Computes the edits needed to turn the reference `x` into the query `y`
+    /// and vice versa, along the CIGAR.
+    ///
+    /// These are the edits that `edits_iterative` would give for the same
+    /// alignment.
+    ///
+    /// # Errors
+    ///
+    /// * [`CigarError::ReferenceLength`] or [`CigarError::QueryLength`] if
+    ///   the CIGAR does not consume exactly the characters of `x` or `y`.
+    /// * [`CigarError::WrongOp`] if a `=` aligns different characters or an
+    ///   `X` aligns equal characters.
+    pub fn to_edits(&self, x: &str, y: &str) -> Result<[Vec<Edit>; 2], CigarError> {
+        self.pairs(x, y).map(|pairs| compute_edits(&pairs))
+    }
+
+    /// Computes the MD tag of the SAM format for the alignment of the
+    /// reference `x` with the query `y`.
+    ///
+    /// The MD tag lists the numbers of matching characters, separated by the
+    /// characters of the reference at mismatches, and by `^` followed by the
+    /// characters of the reference at deletions. Insertions are not shown. The
+    /// tag starts and ends with a number, which may be `0`.
+    ///
+    /// # Errors
+    ///
+    /// * [`CigarError::ReferenceLength`] or [`CigarError::QueryLength`] if
+    ///   the CIGAR does not consume exactly the characters of `x` or `y`.
+    /// * [`CigarError::WrongOp`] if a `=` aligns different characters or an
+    ///   `X` aligns equal characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use distances::strings::needleman_wunsch::Cigar;
+    ///
+    /// let cigar: Cigar = "3M2D1M1I2M".parse().unwrap();
+    /// let md = cigar.md_tag("ACGTAGCA", "ACGTCCA").unwrap();
+    ///
+    /// assert_eq!(md, "3^TA0G2");
+    /// ```
+    pub fn md_tag(&self, x: &str, y: &str) -> Result<String, CigarError> {
+        let mut md = String::new();
+        let mut matches = 0_usize;
+        let mut in_deletion = false;
+
+        for pair in self.pairs(x, y)? {
+            match pair {
+                AlignedPair::Match(..) => {
+                    matches += 1;
+                    in_deletion = false;
+                }
+                AlignedPair::Mismatch(a, _) => {
+                    md.push_str(&matches.to_string());
+                    md.push(a);
+                    matches = 0;
+                    in_deletion = false;
+                }
+                AlignedPair::GapInY(a) => {
+                    if !in_deletion {
+                        md.push_str(&matches.to_string());
+                        md.push('^');
+                        matches = 0;
+                        in_deletion = true;
+                    }
+                    md.push(a);
+                }
+                AlignedPair::GapInX(_) => in_deletion = false,
+            }
+        }
+        md.push_str(&matches.to_string());
+
+        Ok(md)
+    }
+}
+
+impl core::fmt::Display for Cigar {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.ops.is_empty() {
+            return write!(f, "*");
+        }
+        for &(n, op) in &self.ops {
+            write!(f, "{n}{}", op.as_char())?;
+        }
+        Ok(())
+    }
+}
+
+impl core::str::FromStr for Cigar {
+    type Err = CigarError;
+
+    /// Parses a CIGAR string with the `=`, `X`, `I`, `D` and `M` operations,
+    /// or `*` for the empty alignment.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cigar = Self::default();
+        if s == "*" {
+            return Ok(cigar);
+        }
+
+        let mut length: Option<usize> = None;
+        for (offset, c) in s.char_indices() {
+            if let Some(digit) = c.to_digit(10) {
+                let n = length
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .zip(usize::try_from(digit).ok())
+                    .and_then(|(n, d)| n.checked_add(d))
+                    .ok_or(CigarError::InvalidLength(offset))?;
+                length = Some(n);
+            } else {
+                let op = CigarOp::from_char(c).ok_or(CigarError::InvalidOp(c))?;
+                match length.take() {
+                    None => return Err(CigarError::MissingLength(offset)),
+                    Some(0) => return Err(CigarError::InvalidLength(offset)),
+                    Some(n) => cigar.extend(n, op),
+                }
+            }
+        }
+
+        if length.is_some() {
+            Err(CigarError::TrailingLength)
+        } else {
+            Ok(cigar)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let cigar = "5M1I3M2D".parse::<Cigar>().unwrap_or_default();
+        assert_eq!(cigar.reference_len(), 10);
+        assert_eq!(cigar.query_len(), 9);
+        assert_eq!(cigar.to_string(), "5M1I3M2D");
+
+        assert_eq!("*".parse(), Ok(Cigar::default()));
+        assert_eq!(
+            "2=3=".parse::<Cigar>().map(|c| c.to_string()),
+            Ok("5=".to_string())
+        );
+        assert_eq!("5M1".parse::<Cigar>(), Err(CigarError::TrailingLength));
+        assert_eq!("M".parse::<Cigar>(), Err(CigarError::MissingLength(0)));
+        assert_eq!("0M".parse::<Cigar>(), Err(CigarError::InvalidLength(1)));
+        assert_eq!("5S".parse::<Cigar>(), Err(CigarError::InvalidOp('S')));
+    }
+
+    #[test]
+    fn md_tag() {
+        let cigar = Cigar::from_aligned("ACGTTA-", "AC-TTAG");
+        assert_eq!(cigar.md_tag("ACGTTA", "ACTTAG"), Ok("2^G3".to_string()));
+
+        let cigar = Cigar::from_aligned("ACGT", "TCGA");
+        assert_eq!(cigar.md_tag("ACGT", "TCGA"), Ok("0A2T0".to_string()));
+
+        assert_eq!(
+            cigar.md_tag("ACGT", "TCG"),
+            Err(CigarError::QueryLength(4, 3))
+        );
+        assert_eq!(
+            "4=".parse::<Cigar>().map(|c| c.md_tag("ACGT", "TCGA")),
+            Ok(Err(CigarError::WrongOp(0)))
+        );
+    }
+}
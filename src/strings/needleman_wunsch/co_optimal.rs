@@ -0,0 +1,362 @@
+//! All the co-optimal Needleman-Wunsch alignments of two sequences.
+
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering;
+
+use crate::{
+    number::UInt,
+    strings::{sequences::AlignedPair, PenaltyScheme},
+};
+
+use super::{
+    helpers::{step, Direction},
+    AlignmentMode,
+};
+
+/// The set of directions of all the best alignments at a given position in
+/// the DP table, as a bitmask.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Directions(u8);
+
+impl Directions {
+    /// All the directions, in the order in which they are followed.
+    const ALL: [Direction; 3] = [Direction::Diagonal, Direction::Up, Direction::Left];
+
+    /// Returns the bit for a direction.
+    const fn bit(direction: Direction) -> u8 {
+        match direction {
+            Direction::Diagonal => 1,
+            Direction::Up => 2,
+            Direction::Left => 4,
+        }
+    }
+
+    /// Returns the set with only the given direction.
+    const fn only(direction: Direction) -> Self {
+        Self(Self::bit(direction))
+    }
+
+    /// Whether the set contains the given direction.
+    pub const fn contains(self, direction: Direction) -> bool {
+        self.0 & Self::bit(direction) != 0
+    }
+
+    /// Removes and returns the first direction in the set, in the order
+    /// `Diagonal`, `Up`, `Left`.
+    fn pop_first(&mut self) -> Option<Direction> {
+        let first = Self::ALL.into_iter().find(|&d| self.contains(d))?;
+        self.0 &= !Self::bit(first);
+        Some(first)
+    }
+
+    /// Returns the directions in the set, in the order `Diagonal`, `Up`,
+    /// `Left`.
+    pub fn iter(self) -> impl Iterator<Item = Direction> {
+        Self::ALL.into_iter().filter(move |&d| self.contains(d))
+    }
+}
+
+/// Returns the cell from which the given direction leads to `[row, col]`.
+const fn predecessor([row, col]: [usize; 2], direction: Direction) -> [usize; 2] {
+    match direction {
+        Direction::Diagonal => [row - 1, col - 1],
+        Direction::Up => [row - 1, col],
+        Direction::Left => [row, col - 1],
+    }
+}
+
+/// Computes the Needleman-Wunsch table for two sequences, keeping every
+/// direction that leads to the best total penalty of each cell.
+///
+/// The total penalties are the same as those of `compute_table`, which keeps
+/// only the first of these directions in the order `Diagonal`, `Up`, `Left`.
+/// The free ends of the `mode` are handled in the same way, and a cell of the
+/// last row or column may keep both its own directions and the free gap from
+/// the previous cell.
+///
+/// # Arguments
+///
+/// * `x`: The first sequence.
+/// * `y`: The second sequence.
+/// * `penalties`: The penalties to use.
+/// * `mode`: Which gaps at the ends of the sequences are free.
+///
+/// # Returns
+///
+/// A nested vector of tuples of total-penalty and `Directions`, with the rows
+/// indexed by `y` and the columns by `x`. The top-left cell has no
+/// directions.
+pub fn compute_table_all<T, U: UInt>(
+    x: &[T],
+    y: &[T],
+    penalties: &impl PenaltyScheme<U, T>,
+    mode: AlignmentMode,
+) -> Vec<Vec<(U, Directions)>> {
+    let (n, m) = (y.len(), x.len());
+    let [[prefix_x, suffix_x], [prefix_y, suffix_y]] = mode.free_ends([m, n]);
+    let gap = penalties.gap();
+    let mut table = vec![vec![(U::zero(), Directions::default()); m + 1]; n + 1];
+
+    for i in 1..=n {
+        let lead = if i <= prefix_y { U::zero() } else { gap };
+        table[i][0] = (table[i - 1][0].0 + lead, Directions::only(Direction::Up));
+    }
+    for j in 1..=m {
+        let lead = if j <= prefix_x { U::zero() } else { gap };
+        table[0][j] = (table[0][j - 1].0 + lead, Directions::only(Direction::Left));
+    }
+
+    for i in 1..=y.len() {
+        for j in 1..=x.len() {
+            let candidates = [
+                table[i - 1][j - 1].0 + penalties.substitution(&x[j - 1], &y[i - 1]),
+                table[i - 1][j].0 + gap,
+                table[i][j - 1].0 + gap,
+            ];
+            let best = candidates
+                .into_iter()
+                .min()
+                .unwrap_or_else(|| unreachable!("There are three candidates."));
+            let directions = candidates
+                .into_iter()
+                .zip(Directions::ALL)
+                .filter(|&(penalty, _)| penalty == best)
+                .fold(0, |bits, (_, d)| bits | Directions::bit(d));
+            table[i][j] = (best, Directions(directions));
+        }
+    }
+
+    // Let the alignments skip the free suffixes of the sequences.
+    for j in (m + 1 - suffix_x).max(1)..m {
+        table[n][j] = merge(table[n][j], table[n][j - 1].0, Direction::Left);
+    }
+    for i in (n + 1 - suffix_y).max(1)..n {
+        table[i][m] = merge(table[i][m], table[i - 1][m].0, Direction::Up);
+    }
+    if suffix_y > 0 {
+        table[n][m] = merge(table[n][m], table[n - 1][m].0, Direction::Up);
+    }
+    if suffix_x > 0 {
+        table[n][m] = merge(table[n][m], table[n][m - 1].0, Direction::Left);
+    }
+
+    table
+}
+
+/// Merges a free gap in the given direction, from a cell with the given total
+/// penalty, into a cell of the table.
+fn merge<U: UInt>(
+    (penalty, directions): (U, Directions),
+    free: U,
+    direction: Direction,
+) -> (U, Directions) {
+    match free.cmp(&penalty) {
+        Ordering::Less => (free, Directions::only(direction)),
+        Ordering::Equal => (
+            penalty,
+            Directions(directions.0 | Directions::bit(direction)),
+        ),
+        Ordering::Greater => (penalty, directions),
+    }
+}
+
+/// Counts the optimal paths from the top-left cell of the table to every
+/// cell, saturating at `u128::MAX`.
+pub fn count_paths<U: UInt>(table: &[Vec<(U, Directions)>]) -> Vec<Vec<u128>> {
+    let mut counts = vec![vec![0_u128; table[0].len()]; table.len()];
+    counts[0][0] = 1;
+    for (i, row) in table.iter().enumerate() {
+        for (j, &(_, directions)) in row.iter().enumerate() {
+            if i > 0 || j > 0 {
+                counts[i][j] = directions
+                    .iter()
+                    .map(|d| predecessor([i, j], d))
+                    .fold(0, |count, [r, c]| count.saturating_add(counts[r][c]));
+            }
+        }
+    }
+    counts
+}
+
+/// Finds the number of columns of the longest optimal alignment to every
+/// cell of the table.
+pub fn longest_paths<U: UInt>(table: &[Vec<(U, Directions)>]) -> Vec<Vec<usize>> {
+    let mut lengths = vec![vec![0; table[0].len()]; table.len()];
+    for (i, row) in table.iter().enumerate() {
+        for (j, &(_, directions)) in row.iter().enumerate() {
+            if i > 0 || j > 0 {
+                lengths[i][j] = directions
+                    .iter()
+                    .map(|d| predecessor([i, j], d))
+                    .map(|[r, c]| lengths[r][c] + 1)
+                    .max()
+                    .unwrap_or_default();
+            }
+        }
+    }
+    lengths
+}
+
+/// Builds the alignment of two sequences along a path of directions, ordered
+/// from the bottom-right cell of the table to the top-left cell.
+pub fn align<T: Eq + Clone>(path: &[Direction], [x, y]: [&[T]; 2]) -> Vec<AlignedPair<T>> {
+    let mut cell = [y.len(), x.len()];
+    let mut alignment = path
+        .iter()
+        .map(|&direction| {
+            let (pair, prev) = step(direction, cell, [x, y]);
+            cell = prev;
+            pair
+        })
+        .collect::<Vec<_>>();
+    alignment.reverse();
+    alignment
+}
+
+/// Samples an optimal path of directions uniformly at random, using the
+/// counts of optimal paths to each cell.
+///
+/// Going back from the bottom-right cell, each direction is taken with
+/// probability proportional to the number of optimal paths through it.
+pub fn random_path<U: UInt, R: rand::Rng>(
+    table: &[Vec<(U, Directions)>],
+    counts: &[Vec<u128>],
+    rng: &mut R,
+) -> Vec<Direction> {
+    let mut cell = [table.len() - 1, table[0].len() - 1];
+    let mut path = Vec::new();
+    while cell != [0, 0] {
+        let [row, col] = cell;
+        let mut choice = rng.gen_range(0..counts[row][col]);
+        let direction = table[row][col]
+            .1
+            .iter()
+            .find(|&d| {
+                let [r, c] = predecessor(cell, d);
+                if choice < counts[r][c] {
+                    true
+                } else {
+                    choice -= counts[r][c];
+                    false
+                }
+            })
+            .unwrap_or_else(|| unreachable!("The counts add up to that of the cell."));
+        path.push(direction);
+        cell = predecessor(cell, direction);
+    }
+    path
+}
+
+/// A lazy iterator over all the co-optimal Needleman-Wunsch alignments of two
+/// sequences.
+///
+/// The alignments are found by a depth-first search through the directions of
+/// the table, from the bottom-right cell to the top-left cell, following the
+/// directions in the order `Diagonal`, `Up`, `Left`. The first alignment is
+/// thus the one given by `edits_iterative`. Each alignment is yielded as a
+/// vector of [`AlignedPair`]s, so that the sequences may contain any element,
+/// e.g. `-`, and takes time proportional to its length.
+#[derive(Clone, Debug)]
+pub struct CoOptimalAlignments<T> {
+    /// The first sequence.
+    x: Vec<T>,
+    /// The second sequence.
+    y: Vec<T>,
+    /// The directions of the best alignments at each cell of the table.
+    table: Vec<Vec<Directions>>,
+    /// The cells on the current path, along with the directions from them
+    /// that have not yet been followed.
+    stack: Vec<([usize; 2], Directions)>,
+    /// The directions followed along the current path.
+    path: Vec<Direction>,
+}
+
+impl<T> CoOptimalAlignments<T> {
+    /// Creates an iterator over the alignments given by the table.
+    pub(crate) fn new<U: UInt>(x: Vec<T>, y: Vec<T>, table: &[Vec<(U, Directions)>]) -> Self {
+        let table = table
+            .iter()
+            .map(|row| row.iter().map(|&(_, d)| d).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let end = [y.len(), x.len()];
+        let stack = vec![(end, table[y.len()][x.len()])];
+        Self {
+            x,
+            y,
+            table,
+            stack,
+            path: Vec::new(),
+        }
+    }
+}
+
+impl<T: Eq + Clone> Iterator for CoOptimalAlignments<T> {
+    type Item = Vec<AlignedPair<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (cell, remaining) = self.stack.last_mut()?;
+            if *cell == [0, 0] {
+                let alignment = align(&self.path, [&self.x, &self.y]);
+                self.stack.pop();
+                self.path.pop();
+                return Some(alignment);
+            }
+
+            let cell = *cell;
+            if let Some(direction) = remaining.pop_first() {
+                let [row, col] = predecessor(cell, direction);
+                self.path.push(direction);
+                self.stack.push(([row, col], self.table[row][col]));
+            } else {
+                self.stack.pop();
+                self.path.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::*;
+    use crate::strings::Penalties;
+
+    #[test]
+    fn test_co_optimal() {
+        let x = "AB".chars().collect::<Vec<_>>();
+        let y = "BA".chars().collect::<Vec<_>>();
+        let table =
+            compute_table_all::<_, u8>(&x, &y, &Penalties::default(), AlignmentMode::Global);
+        assert_eq!(table[2][2].0, 2);
+        assert_eq!(count_paths(&table)[2][2], 3);
+
+        let alignments = CoOptimalAlignments::new(x, y, &table).collect::<Vec<_>>();
+        assert_eq!(
+            alignments,
+            [
+                vec![
+                    AlignedPair::Mismatch('A', 'B'),
+                    AlignedPair::Mismatch('B', 'A'),
+                ],
+                vec![
+                    AlignedPair::GapInY('A'),
+                    AlignedPair::Match('B', 'B'),
+                    AlignedPair::GapInX('A'),
+                ],
+                vec![
+                    AlignedPair::GapInX('B'),
+                    AlignedPair::Match('A', 'A'),
+                    AlignedPair::GapInY('B'),
+                ],
+            ]
+        );
+
+        let table =
+            compute_table_all::<char, u8>(&[], &[], &Penalties::default(), AlignmentMode::Global);
+        assert_eq!(count_paths(&table)[0][0], 1);
+        let alignments = CoOptimalAlignments::new(Vec::<char>::new(), Vec::new(), &table);
+        assert_eq!(alignments.collect::<Vec<_>>(), [Vec::new()]);
+    }
+}
@@ -1,9 +1,24 @@
 //! Helper functions for the Needleman-Wunsch algorithm.
+//!
+//! These work on slices of any type of element, e.g. the `char`s of strings
+//! or the tokens of [`sequences`](crate::strings::sequences), and give the
+//! alignments as sequences of [`AlignedPair`]s, so that the gaps are never
+//! confused with the elements.
 
-use crate::{number::UInt, strings::Penalties};
+use alloc::{string::String, vec, vec::Vec};
+use core::cmp::Ordering;
+
+use crate::{
+    number::UInt,
+    strings::{sequences::AlignedPair, PenaltyScheme},
+};
+
+use super::{AffinePenalties, AlignmentMode};
 
 /// The direction of best alignment at a given position in the DP table
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///
+/// The directions are ordered as they are preferred when breaking ties.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Direction {
     /// Diagonal (Up and Left) for a match.
     Diagonal,
@@ -14,74 +29,119 @@ pub enum Direction {
 }
 
 /// The type of edit needed to turn one sequence into another.
-pub enum Edit {
-    /// Delete a character at the given index.
+///
+/// The edits of strings hold characters, and those of other sequences, as
+/// computed in [`sequences`](crate::strings::sequences), hold their elements.
+///
+/// A script of edits is applied in order, and the index of each edit is that
+/// of an element in the sequence as edited by all the previous edits. For the
+/// scripts computed from an alignment, the edits are in increasing order of
+/// position, so that the index of an insertion or a substitution is that of
+/// the new element in the target sequence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Edit<T = char> {
+    /// Delete an element at the given index.
     Del(usize),
-    /// Insert a character at the given index.
-    Ins(usize, char),
-    /// Substitute a character at the given index.
-    Sub(usize, char),
+    /// Insert an element at the given index.
+    Ins(usize, T),
+    /// Substitute an element at the given index.
+    Sub(usize, T),
 }
 
 /// Computes the Needleman-Wunsch dynamic programming table for two sequences.
 ///
-/// The penalty for a mismatch is 1, and the penalty for a gap is 1. There is no
-/// penalty for a match. Our implementation minimizes the total penalty.
+/// For strings, the sequences are slices of `char`s rather than `&str`s, so
+/// that the table has one row or column per Unicode scalar value, and not per
+/// byte. Our implementation minimizes the total penalty.
+///
+/// With a `mode` other than `Global`, the first row or column starts at zero
+/// for as many elements of the sequences as their unaligned prefix may skip
+/// for free. For the free suffixes, the last cells of the last row or column
+/// take the total penalty of the previous cell when it is smaller, with a
+/// `Left` or `Up` direction, breaking ties in the order of [`Direction`], so
+/// that the bottom-right cell holds the total penalty of the best alignment in
+/// that mode and the usual trace back follows it.
 ///
 /// # Arguments
 ///
 /// * `x`: The first sequence.
 /// * `y`: The second sequence.
+/// * `penalties`: The penalties to use.
+/// * `mode`: Which gaps at the ends of the sequences are free.
 ///
 /// # Returns
 ///
 /// A nested vector of tuples of total-penalty and Direction, representing the
-/// best alignment at each position.
-pub fn compute_table<U: UInt>(
-    x: &str,
-    y: &str,
-    penalties: Penalties<U>,
+/// best alignment at each position. The rows are indexed by `y` and the
+/// columns by `x`.
+pub fn compute_table<T, U: UInt>(
+    x: &[T],
+    y: &[T],
+    penalties: impl PenaltyScheme<U, T>,
+    mode: AlignmentMode,
 ) -> Vec<Vec<(U, Direction)>> {
+    let (n, m) = (y.len(), x.len());
+    let [[prefix_x, suffix_x], [prefix_y, suffix_y]] = mode.free_ends([m, n]);
+
     // Initializing table; the inner vectors represent rows in the table.
-    let mut table = vec![vec![(U::zero(), Direction::Diagonal); x.len() + 1]; y.len() + 1];
+    let mut table = vec![vec![(U::zero(), Direction::Diagonal); m + 1]; n + 1];
 
     // The top-left cell starts with a total penalty of zero and no direction.
     table[0][0] = (U::zero(), Direction::Diagonal);
 
     // Initialize left-most column of distance values.
     for (i, row) in table.iter_mut().enumerate().skip(1) {
-        row[0] = (penalties.gap * U::from(i), Direction::Up);
+        row[0] = (
+            penalties.gap() * U::from(i.saturating_sub(prefix_y)),
+            Direction::Up,
+        );
     }
 
     // Initialize top row of distance values.
     for (j, cell) in table[0].iter_mut().enumerate().skip(1) {
-        *cell = (penalties.gap * U::from(j), Direction::Left);
+        *cell = (
+            penalties.gap() * U::from(j.saturating_sub(prefix_x)),
+            Direction::Left,
+        );
     }
 
     // Set values for the body of the table
-    for (i, y_c) in y.chars().enumerate() {
-        for (j, x_c) in x.chars().enumerate() {
+    for (i, y_c) in y.iter().enumerate() {
+        for (j, x_c) in x.iter().enumerate() {
             // Check if sequences match at position `i` in `x` and `j` in `y`.
-            let mismatch_penalty = if x_c == y_c {
-                penalties.match_
-            } else {
-                penalties.mismatch
-            };
+            let mismatch_penalty = penalties.substitution(x_c, y_c);
 
             // Compute the three possible penalties and use the minimum to set
             // the value for the next entry in the table.
             let d00 = (table[i][j].0 + mismatch_penalty, Direction::Diagonal);
-            let d01 = (table[i][j + 1].0 + penalties.gap, Direction::Up);
-            let d10 = (table[i + 1][j].0 + penalties.gap, Direction::Left);
+            let d01 = (table[i][j + 1].0 + penalties.gap(), Direction::Up);
+            let d10 = (table[i + 1][j].0 + penalties.gap(), Direction::Left);
 
             table[i + 1][j + 1] = min2(d00, min2(d01, d10));
         }
     }
 
+    // Let the alignments skip the free suffixes of the sequences.
+    for j in (m + 1 - suffix_x).max(1)..m {
+        table[n][j] = table[n][j].min((table[n][j - 1].0, Direction::Left));
+    }
+    for i in (n + 1 - suffix_y).max(1)..n {
+        table[i][m] = table[i][m].min((table[i - 1][m].0, Direction::Up));
+    }
+    if suffix_y > 0 {
+        table[n][m] = table[n][m].min((table[n - 1][m].0, Direction::Up));
+    }
+    if suffix_x > 0 {
+        table[n][m] = table[n][m].min((table[n][m - 1].0, Direction::Left));
+    }
+
     table
 }
 
 /// Returns the minimum of two penalties, defaulting to the first input.
+///
+/// Nested as `min2(d00, min2(d01, d10))`, ties are broken in the order
+/// `Diagonal`, `Up`, `Left`.
 fn min2<U: UInt>(a: (U, Direction), b: (U, Direction)) -> (U, Direction) {
     if a.0 <= b.0 {
         a
@@ -90,45 +150,310 @@ fn min2<U: UInt>(a: (U, Direction), b: (U, Direction)) -> (U, Direction) {
     }
 }
 
-/// Converts two aligned sequences into vectors of edits.
+/// A cell of the Gotoh dynamic programming table for affine gap penalties.
+///
+/// The entries hold the best total penalty of an alignment ending with a
+/// substitution, a gap in the first sequence and a gap in the second
+/// sequence, in the order of [`AFFINE_STATES`]. The direction stored with each
+/// penalty is the state from which that alignment was extended. An entry is
+/// `None` if no alignment can end in that state.
+pub type AffineCell<U> = [Option<(U, Direction)>; 3];
+
+/// The states of the Gotoh table, in the order of the entries of an
+/// [`AffineCell`].
+pub const AFFINE_STATES: [Direction; 3] = [Direction::Diagonal, Direction::Up, Direction::Left];
+
+/// Returns the index of a state in an [`AffineCell`].
+const fn state_index(state: Direction) -> usize {
+    match state {
+        Direction::Diagonal => 0,
+        Direction::Up => 1,
+        Direction::Left => 2,
+    }
+}
+
+/// Computes the Gotoh dynamic programming table for two sequences with
+/// affine gap penalties.
+///
+/// A gap of length `k` costs `gap_open + k * gap_extend`. Instead of the
+/// single table of `compute_table`, we keep three values per cell, for
+/// alignments ending with a substitution (`Diagonal`), a gap in `x` (`Up`)
+/// and a gap in `y` (`Left`), so that opening a gap is only charged once.
+///
+/// The free ends of the `mode` are gaps at no cost. A gap that runs on past
+/// the free elements of a prefix or suffix is charged as a gap of the rest of
+/// its length, opening penalty included.
+///
+/// # Arguments
+///
+/// * `x`: The first sequence.
+/// * `y`: The second sequence.
+/// * `penalties`: The affine penalties to use.
+/// * `mode`: Which gaps at the ends of the sequences are free.
+///
+/// # Returns
+///
+/// A nested vector of `AffineCell`s, with the rows indexed by `y` and the
+/// columns by `x`.
+pub fn compute_affine_table<T: Eq, U: UInt>(
+    x: &[T],
+    y: &[T],
+    penalties: AffinePenalties<U>,
+    mode: AlignmentMode,
+) -> Vec<Vec<AffineCell<U>>> {
+    let (n, m) = (y.len(), x.len());
+    let [[prefix_x, suffix_x], [prefix_y, suffix_y]] = mode.free_ends([m, n]);
+    let mut table = vec![vec![[None; 3]; m + 1]; n + 1];
+
+    // The empty alignment ends in the substitution state, so that the first
+    // gap in either sequence is charged the opening penalty.
+    table[0][0][0] = Some((U::zero(), Direction::Diagonal));
+
+    let open = penalties.gap_open + penalties.gap_extend;
+    let extend = penalties.gap_extend;
+    let free = [U::zero(); 3];
+
+    for i in 0..=n {
+        for j in 0..=m {
+            if i > 0 && j > 0 {
+                let substitution = if x[j - 1] == y[i - 1] {
+                    penalties.match_
+                } else {
+                    penalties.mismatch
+                };
+                table[i][j][0] = best(table[i - 1][j - 1], [substitution; 3]);
+            }
+            if i > 0 {
+                let gap = match (j, i.cmp(&(prefix_y + 1))) {
+                    (0, Ordering::Less) => free,
+                    (0, Ordering::Equal) => [open; 3],
+                    _ => [open, extend, open],
+                };
+                table[i][j][1] = best(table[i - 1][j], gap);
+            }
+            if j > 0 {
+                let gap = match (i, j.cmp(&(prefix_x + 1))) {
+                    (0, Ordering::Less) => free,
+                    (0, Ordering::Equal) => [open; 3],
+                    _ => [open, open, extend],
+                };
+                table[i][j][2] = best(table[i][j - 1], gap);
+            }
+        }
+    }
+
+    // Let the alignments skip the free suffixes of the sequences.
+    for j in (m + 1 - suffix_x).max(1)..=m {
+        table[n][j][2] = table[n][j][2]
+            .into_iter()
+            .chain(best(table[n][j - 1], free))
+            .reduce(min2);
+    }
+    for i in (n + 1 - suffix_y).max(1)..=n {
+        table[i][m][1] = table[i][m][1]
+            .into_iter()
+            .chain(best(table[i - 1][m], free))
+            .reduce(min2);
+    }
+
+    table
+}
+
+/// Extends the alignments ending in each state of a cell by the given
+/// penalties, and returns the best one along with the state it came from.
+///
+/// Ties are broken in the order of [`AFFINE_STATES`].
+fn best<U: UInt>(cell: AffineCell<U>, penalties: [U; 3]) -> Option<(U, Direction)> {
+    cell.into_iter()
+        .zip(penalties)
+        .zip(AFFINE_STATES)
+        .filter_map(|((value, penalty), state)| value.map(|(v, _)| (v + penalty, state)))
+        .reduce(min2)
+}
+
+/// Returns the best total penalty in a cell of the Gotoh table along with the
+/// state in which it ends.
+pub fn affine_cell_min<U: UInt>(cell: AffineCell<U>) -> (U, Direction) {
+    best(cell, [U::zero(); 3])
+        .unwrap_or_else(|| unreachable!("Every cell has at least one reachable state."))
+}
+
+/// Takes one step back through a table in the given direction.
+///
+/// # Arguments
+///
+/// * `direction`: The direction in which to step.
+/// * `[row_i, col_i]`: The cell from which to step.
+/// * `[x, y]`: The two sequences being aligned.
+///
+/// # Returns
+///
+/// The column of the alignment for the step, and the cell it leads to.
+pub fn step<T: Eq + Clone>(
+    direction: Direction,
+    [row_i, col_i]: [usize; 2],
+    [x, y]: [&[T]; 2],
+) -> (AlignedPair<T>, [usize; 2]) {
+    match direction {
+        Direction::Diagonal => {
+            let (a, b) = (x[col_i - 1].clone(), y[row_i - 1].clone());
+            let pair = if a == b {
+                AlignedPair::Match(a, b)
+            } else {
+                AlignedPair::Mismatch(a, b)
+            };
+            (pair, [row_i - 1, col_i - 1])
+        }
+        Direction::Up => (
+            AlignedPair::GapInX(y[row_i - 1].clone()),
+            [row_i - 1, col_i],
+        ),
+        Direction::Left => (
+            AlignedPair::GapInY(x[col_i - 1].clone()),
+            [row_i, col_i - 1],
+        ),
+    }
+}
+
+/// Converts an alignment of two strings into the two aligned strings, with
+/// `-` for gaps.
+pub fn aligned_strings(alignment: &[AlignedPair<char>]) -> (String, String) {
+    alignment
+        .iter()
+        .map(|pair| match *pair {
+            AlignedPair::Match(a, b) | AlignedPair::Mismatch(a, b) => (a, b),
+            AlignedPair::GapInX(b) => ('-', b),
+            AlignedPair::GapInY(a) => (a, '-'),
+        })
+        .unzip()
+}
+
+/// Converts an alignment into the edits needed to turn `x` into `y` and `y`
+/// into `x`.
+///
+/// The gaps are the columns of the alignment rather than some reserved
+/// element, so that the sequences may contain any element, e.g. `-`.
 ///
 /// # Arguments
 ///
-/// * `x`: The first aligned sequence.
-/// * `y`: The second aligned sequence.
+/// * `alignment`: The alignment of the two sequences.
 ///
 /// # Returns
 ///
 /// A 2-slice of Vec<Edit>, each containing the edits needed to convert one
 /// sequence into the other
-pub fn compute_edits(x: &str, y: &str) -> [Vec<Edit>; 2] {
-    [_x_to_y(x, y), _x_to_y(y, x)]
+pub fn compute_edits<T: Clone>(alignment: &[AlignedPair<T>]) -> [Vec<Edit<T>>; 2] {
+    // The edits for `y` into `x` are those for the mirrored alignment.
+    let mirrored = alignment
+        .iter()
+        .cloned()
+        .map(|pair| match pair {
+            AlignedPair::Match(a, b) => AlignedPair::Match(b, a),
+            AlignedPair::Mismatch(a, b) => AlignedPair::Mismatch(b, a),
+            AlignedPair::GapInX(b) => AlignedPair::GapInY(b),
+            AlignedPair::GapInY(a) => AlignedPair::GapInX(a),
+        })
+        .collect::<Vec<_>>();
+    [_x_to_y(alignment), _x_to_y(&mirrored)]
 }
 
 /// Helper for `compute_edits` to compute the edits for `x` into `y`.
-fn _x_to_y(x: &str, y: &str) -> Vec<Edit> {
-    // TODO: Revisit this for correctness when we start working on the MSA.
-    x.chars()
-        .zip(y.chars())
-        .filter(|(x, y)| x != y)
-        .enumerate()
-        .map(|(i, (x, y))| {
-            if y == '-' {
-                Edit::Del(i)
-            } else if x == '-' {
-                Edit::Ins(i, y)
-            } else {
-                Edit::Sub(i, y)
+fn _x_to_y<T: Clone>(alignment: &[AlignedPair<T>]) -> Vec<Edit<T>> {
+    // The index in the sequence being edited, of which the first `i`
+    // elements are already those of `y`.
+    let mut i = 0;
+    alignment
+        .iter()
+        .filter_map(|pair| {
+            let edit = match pair {
+                AlignedPair::Match(..) => None,
+                AlignedPair::GapInY(_) => Some(Edit::Del(i)),
+                AlignedPair::GapInX(b) => Some(Edit::Ins(i, b.clone())),
+                AlignedPair::Mismatch(_, b) => Some(Edit::Sub(i, b.clone())),
+            };
+            if !matches!(pair, AlignedPair::GapInY(_)) {
+                i += 1;
             }
+            edit
         })
         .collect()
 }
 
+/// Applies a script of edits to a sequence, in order.
+///
+/// # Panics
+///
+/// * If the index of an edit is out of bounds for the sequence as edited by
+///   the previous edits.
+pub fn apply<T: Clone>(x: &mut Vec<T>, edits: &[Edit<T>]) {
+    for edit in edits {
+        match edit {
+            Edit::Del(i) => {
+                x.remove(*i);
+            }
+            Edit::Ins(i, v) => x.insert(*i, v.clone()),
+            Edit::Sub(i, v) => x[*i] = v.clone(),
+        }
+    }
+}
+
+/// Computes the script of edits that undoes the given script on `x`.
+///
+/// We apply the edits to a copy of `x` to recover the elements which they
+/// delete or substitute, and the inverse of each edit is then applied in the
+/// reverse order.
+///
+/// # Panics
+///
+/// * If the index of an edit is out of bounds for the sequence as edited by
+///   the previous edits.
+pub fn invert<T: Clone>(x: &[T], edits: &[Edit<T>]) -> Vec<Edit<T>> {
+    let mut x = x.to_vec();
+    let mut inverse = edits
+        .iter()
+        .map(|edit| match edit {
+            Edit::Del(i) => Edit::Ins(*i, x.remove(*i)),
+            Edit::Ins(i, v) => {
+                x.insert(*i, v.clone());
+                Edit::Del(*i)
+            }
+            Edit::Sub(i, v) => Edit::Sub(*i, core::mem::replace(&mut x[*i], v.clone())),
+        })
+        .collect::<Vec<_>>();
+    inverse.reverse();
+    inverse
+}
+
+/// Computes the script of edits that applies `first` and then `second`.
+///
+/// Consecutive edits at the same index are merged, e.g. an insertion followed
+/// by a substitution becomes a single insertion, and an insertion followed by
+/// a deletion cancels out.
+pub fn compose<T: Clone>(first: &[Edit<T>], second: &[Edit<T>]) -> Vec<Edit<T>> {
+    let mut composed: Vec<Edit<T>> = Vec::with_capacity(first.len() + second.len());
+    for edit in first.iter().chain(second).cloned() {
+        let merged = match (composed.last(), edit) {
+            (Some(Edit::Ins(i, _)), Edit::Del(j)) if *i == j => None,
+            (Some(Edit::Ins(i, _)), Edit::Sub(j, v)) if *i == j => Some(Edit::Ins(j, v)),
+            (Some(Edit::Sub(i, _)), Edit::Sub(j, v)) if *i == j => Some(Edit::Sub(j, v)),
+            (Some(Edit::Sub(i, _)), Edit::Del(j)) if *i == j => Some(Edit::Del(j)),
+            (_, edit) => {
+                composed.push(edit);
+                continue;
+            }
+        };
+        composed.pop();
+        composed.extend(merged);
+    }
+    composed
+}
+
 /// Iteratively traces back through the Needleman-Wunsch table to get the alignment of two sequences.
 ///
-/// For now, we ignore ties in the paths that can be followed to get the best alignments.
-/// We break ties by always choosing the `Diagonal` path first, then the `Left`
-/// path, then the `Up` path.
+/// The table stores only one direction per cell, so this follows a single one
+/// of the best alignments. `compute_table` breaks ties by choosing the
+/// `Diagonal` path first, then the `Up` path, then the `Left` path. Use
+/// `compute_table_all` and `CoOptimalAlignments` to get all of them.
 ///
 /// # Arguments
 ///
@@ -137,53 +462,30 @@ fn _x_to_y(x: &str, y: &str) -> Vec<Edit> {
 ///
 /// # Returns
 ///
-/// A tuple of the two aligned sequences.
-pub fn trace_back_iterative<U: UInt>(
+/// The alignment of the two sequences.
+pub fn trace_back_iterative<T: Eq + Clone, U: UInt>(
     table: &[Vec<(U, Direction)>],
-    [x, y]: [&str; 2],
-) -> (String, String) {
-    let (x, y) = (x.as_bytes(), y.as_bytes());
-
-    let (mut row_i, mut col_i) = (y.len(), x.len());
-    let (mut aligned_x, mut aligned_y) = (Vec::new(), Vec::new());
-
-    while row_i > 0 && col_i > 0 {
-        match table[row_i][col_i].1 {
-            Direction::Diagonal => {
-                aligned_x.push(x[col_i - 1]);
-                aligned_y.push(y[row_i - 1]);
-                row_i -= 1;
-                col_i -= 1;
-            }
-            Direction::Left => {
-                aligned_x.push(x[col_i - 1]);
-                aligned_y.push(b'-');
-                col_i -= 1;
-            }
-            Direction::Up => {
-                aligned_x.push(b'-');
-                aligned_y.push(y[row_i - 1]);
-                row_i -= 1;
-            }
-        }
+    [x, y]: [&[T]; 2],
+) -> Vec<AlignedPair<T>> {
+    let mut cell = [y.len(), x.len()];
+    let mut alignment = Vec::new();
+
+    while cell != [0, 0] {
+        let (pair, prev) = step(table[cell[0]][cell[1]].1, cell, [x, y]);
+        alignment.push(pair);
+        cell = prev;
     }
 
-    aligned_x.reverse();
-    aligned_y.reverse();
-
-    let aligned_x = String::from_utf8(aligned_x)
-        .unwrap_or_else(|_| unreachable!("We know we added valid characters."));
-    let aligned_y = String::from_utf8(aligned_y)
-        .unwrap_or_else(|_| unreachable!("We know we added valid characters."));
-
-    (aligned_x, aligned_y)
+    alignment.reverse();
+    alignment
 }
 
 /// Recursively traces back through the Needleman-Wunsch table to get the alignment of two sequences.
 ///
-/// For now, we ignore ties in the paths that can be followed to get the best alignments.
-/// We break ties by always choosing the `Diagonal` path first, then the `Left`
-/// path, then the `Up` path.
+/// The table stores only one direction per cell, so this follows a single one
+/// of the best alignments. `compute_table` breaks ties by choosing the
+/// `Diagonal` path first, then the `Up` path, then the `Left` path. Use
+/// `compute_table_all` and `CoOptimalAlignments` to get all of them.
 ///
 /// # Arguments
 ///
@@ -192,29 +494,15 @@ pub fn trace_back_iterative<U: UInt>(
 ///
 /// # Returns
 ///
-/// A tuple of the two aligned sequences.
-pub fn trace_back_recursive<U: UInt>(
+/// The alignment of the two sequences.
+pub fn trace_back_recursive<T: Eq + Clone, U: UInt>(
     table: &[Vec<(U, Direction)>],
-    [x, y]: [&str; 2],
-) -> (String, String) {
-    let (mut aligned_x, mut aligned_y) = (Vec::new(), Vec::new());
-
-    _trace_back_recursive(
-        table,
-        [y.len(), x.len()],
-        [x.as_bytes(), y.as_bytes()],
-        [&mut aligned_x, &mut aligned_y],
-    );
-
-    aligned_x.reverse();
-    aligned_y.reverse();
-
-    let aligned_x = String::from_utf8(aligned_x)
-        .unwrap_or_else(|_| unreachable!("We know we added valid characters."));
-    let aligned_y = String::from_utf8(aligned_y)
-        .unwrap_or_else(|_| unreachable!("We know we added valid characters."));
-
-    (aligned_x, aligned_y)
+    [x, y]: [&[T]; 2],
+) -> Vec<AlignedPair<T>> {
+    let mut alignment = Vec::new();
+    _trace_back_recursive(table, [y.len(), x.len()], [x, y], &mut alignment);
+    alignment.reverse();
+    alignment
 }
 
 /// Helper function for `trace_back_recursive`.
@@ -222,48 +510,118 @@ pub fn trace_back_recursive<U: UInt>(
 /// # Arguments
 ///
 /// * `table`: The Needleman-Wunsch table.
-/// * `[row_i, col_i]`: mutable indices into the table.
-/// * `[x, y]`: The two sequences to align, passed as slices of bytes.
-/// * `[aligned_x, aligned_y]`: mutable aligned sequences that will be built
-/// up from initially empty vectors.
-fn _trace_back_recursive<U: UInt>(
+/// * `cell`: The indices into the table of the current cell.
+/// * `[x, y]`: The two sequences to align.
+/// * `alignment`: The reversed alignment that will be built up from an
+///   initially empty vector.
+fn _trace_back_recursive<T: Eq + Clone, U: UInt>(
     table: &[Vec<(U, Direction)>],
-    [mut row_i, mut col_i]: [usize; 2],
-    [x, y]: [&[u8]; 2],
-    [aligned_x, aligned_y]: [&mut Vec<u8>; 2],
+    cell: [usize; 2],
+    [x, y]: [&[T]; 2],
+    alignment: &mut Vec<AlignedPair<T>>,
 ) {
-    if row_i > 0 || col_i > 0 {
-        match table[row_i][col_i].1 {
-            Direction::Diagonal => {
-                aligned_x.push(x[col_i - 1]);
-                aligned_y.push(y[row_i - 1]);
-                row_i -= 1;
-                col_i -= 1;
-            }
-            Direction::Left => {
-                aligned_x.push(x[col_i - 1]);
-                aligned_y.push(b'-');
-                col_i -= 1;
-            }
-            Direction::Up => {
-                aligned_x.push(b'-');
-                aligned_y.push(y[row_i - 1]);
-                row_i -= 1;
-            }
-        };
-        _trace_back_recursive(table, [row_i, col_i], [x, y], [aligned_x, aligned_y]);
+    if cell != [0, 0] {
+        let (pair, prev) = step(table[cell[0]][cell[1]].1, cell, [x, y]);
+        alignment.push(pair);
+        _trace_back_recursive(table, prev, [x, y], alignment);
+    }
+}
+
+/// Iteratively traces back through the Gotoh table to get the alignment of two sequences.
+///
+/// We start from the state with the best total penalty in the last cell and
+/// follow the states from which each alignment was extended. Ties are broken
+/// in the same order as in `trace_back_iterative`.
+///
+/// # Arguments
+///
+/// * `table`: The Gotoh table.
+/// * `[x, y]`: The two sequences to align.
+///
+/// # Returns
+///
+/// The alignment of the two sequences.
+pub fn trace_back_affine_iterative<T: Eq + Clone, U: UInt>(
+    table: &[Vec<AffineCell<U>>],
+    [x, y]: [&[T]; 2],
+) -> Vec<AlignedPair<T>> {
+    let mut cell = [y.len(), x.len()];
+    let mut state = affine_cell_min(table[cell[0]][cell[1]]).1;
+    let mut alignment = Vec::new();
+
+    while cell != [0, 0] {
+        let (_, prev_state) = table[cell[0]][cell[1]][state_index(state)]
+            .unwrap_or_else(|| unreachable!("We only follow reachable states."));
+        let (pair, prev) = step(state, cell, [x, y]);
+        alignment.push(pair);
+        (cell, state) = (prev, prev_state);
+    }
+
+    alignment.reverse();
+    alignment
+}
+
+/// Recursively traces back through the Gotoh table to get the alignment of two sequences.
+///
+/// This follows the same path as `trace_back_affine_iterative`.
+///
+/// # Arguments
+///
+/// * `table`: The Gotoh table.
+/// * `[x, y]`: The two sequences to align.
+///
+/// # Returns
+///
+/// The alignment of the two sequences.
+pub fn trace_back_affine_recursive<T: Eq + Clone, U: UInt>(
+    table: &[Vec<AffineCell<U>>],
+    [x, y]: [&[T]; 2],
+) -> Vec<AlignedPair<T>> {
+    let mut alignment = Vec::new();
+    let state = affine_cell_min(table[y.len()][x.len()]).1;
+    _trace_back_affine_recursive(table, [y.len(), x.len()], state, [x, y], &mut alignment);
+    alignment.reverse();
+    alignment
+}
+
+/// Helper function for `trace_back_affine_recursive`.
+///
+/// # Arguments
+///
+/// * `table`: The Gotoh table.
+/// * `cell`: The indices into the table of the current cell.
+/// * `state`: the state in which the alignment up to the current cell ends.
+/// * `[x, y]`: The two sequences to align.
+/// * `alignment`: The reversed alignment that will be built up from an
+///   initially empty vector.
+fn _trace_back_affine_recursive<T: Eq + Clone, U: UInt>(
+    table: &[Vec<AffineCell<U>>],
+    cell: [usize; 2],
+    state: Direction,
+    [x, y]: [&[T]; 2],
+    alignment: &mut Vec<AlignedPair<T>>,
+) {
+    if cell != [0, 0] {
+        let (_, prev_state) = table[cell[0]][cell[1]][state_index(state)]
+            .unwrap_or_else(|| unreachable!("We only follow reachable states."));
+        let (pair, prev) = step(state, cell, [x, y]);
+        alignment.push(pair);
+        _trace_back_affine_recursive(table, prev, prev_state, [x, y], alignment);
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::string::ToString;
+
     use super::*;
+    use crate::strings::Penalties;
 
     #[test]
     fn test_compute_table() {
-        let x = "NAJIBPEPPERSEATS";
-        let y = "NAJIBEATSPEPPERS";
-        let table = compute_table::<u16>(x, y, Penalties::default());
+        let x = "NAJIBPEPPERSEATS".chars().collect::<Vec<_>>();
+        let y = "NAJIBEATSPEPPERS".chars().collect::<Vec<_>>();
+        let table = compute_table::<_, u16>(&x, &y, Penalties::default(), AlignmentMode::Global);
 
         #[rustfmt::skip]
         let true_table: [[(u16, Direction); 17]; 17] = [
@@ -291,28 +649,219 @@ mod tests {
 
     #[test]
     fn test_trace_back() {
-        let peppers_x = "NAJIBPEPPERSEATS";
-        let peppers_y = "NAJIBEATSPEPPERS";
-        let peppers_table = compute_table::<u16>(peppers_x, peppers_y, Penalties::default());
-
-        let (aligned_x, aligned_y) = trace_back_recursive(&peppers_table, [peppers_x, peppers_y]);
+        let peppers_x = "NAJIBPEPPERSEATS".chars().collect::<Vec<_>>();
+        let peppers_y = "NAJIBEATSPEPPERS".chars().collect::<Vec<_>>();
+        let peppers_table = compute_table::<_, u16>(
+            &peppers_x,
+            &peppers_y,
+            Penalties::default(),
+            AlignmentMode::Global,
+        );
+
+        let (aligned_x, aligned_y) = aligned_strings(&trace_back_recursive(
+            &peppers_table,
+            [&peppers_x, &peppers_y],
+        ));
         assert_eq!(aligned_x, "NAJIB-PEPPERSEATS");
         assert_eq!(aligned_y, "NAJIBEATSPEPPE-RS");
 
-        let (aligned_x, aligned_y) = trace_back_iterative(&peppers_table, [peppers_x, peppers_y]);
+        let (aligned_x, aligned_y) = aligned_strings(&trace_back_iterative(
+            &peppers_table,
+            [&peppers_x, &peppers_y],
+        ));
         assert_eq!(aligned_x, "NAJIB-PEPPERSEATS");
         assert_eq!(aligned_y, "NAJIBEATSPEPPE-RS");
 
-        let guilty_x = "NOTGUILTY";
-        let guilty_y = "NOTGUILTY";
-        let guilty_table = compute_table::<u16>(guilty_x, guilty_y, Penalties::default());
-
-        let (aligned_x, aligned_y) = trace_back_recursive(&guilty_table, [guilty_x, guilty_y]);
+        let guilty_x = "NOTGUILTY".chars().collect::<Vec<_>>();
+        let guilty_y = "NOTGUILTY".chars().collect::<Vec<_>>();
+        let guilty_table = compute_table::<_, u16>(
+            &guilty_x,
+            &guilty_y,
+            Penalties::default(),
+            AlignmentMode::Global,
+        );
+
+        let (aligned_x, aligned_y) =
+            aligned_strings(&trace_back_recursive(&guilty_table, [&guilty_x, &guilty_y]));
         assert_eq!(aligned_x, "NOTGUILTY");
         assert_eq!(aligned_y, "NOTGUILTY");
 
-        let (aligned_x, aligned_y) = trace_back_iterative(&guilty_table, [guilty_x, guilty_y]);
+        let (aligned_x, aligned_y) =
+            aligned_strings(&trace_back_iterative(&guilty_table, [&guilty_x, &guilty_y]));
         assert_eq!(aligned_x, "NOTGUILTY");
         assert_eq!(aligned_y, "NOTGUILTY");
+
+        // Gaps at the start of the alignment.
+        let table = compute_table::<_, u16>(
+            &['X', 'A'],
+            &['A'],
+            Penalties::default(),
+            AlignmentMode::Global,
+        );
+        let (aligned_x, aligned_y) =
+            aligned_strings(&trace_back_iterative(&table, [&['X', 'A'], &['A']]));
+        assert_eq!(aligned_x, "XA");
+        assert_eq!(aligned_y, "-A");
+
+        // Multi-byte characters take up a single column.
+        let x = "café".chars().collect::<Vec<_>>();
+        let y = "cafe".chars().collect::<Vec<_>>();
+        let table = compute_table::<_, u16>(&x, &y, Penalties::default(), AlignmentMode::Global);
+        assert_eq!(table.len(), 5);
+        assert_eq!(table[0].len(), 5);
+        assert_eq!(table[4][4].0, 1);
+
+        let (aligned_x, aligned_y) = aligned_strings(&trace_back_recursive(&table, [&x, &y]));
+        assert_eq!(aligned_x, "café");
+        assert_eq!(aligned_y, "cafe");
+    }
+
+    #[test]
+    fn test_alignment_modes() {
+        let x = "GGGACGTCCC".chars().collect::<Vec<_>>();
+        let y = "ACGT".chars().collect::<Vec<_>>();
+        let penalties = Penalties::<u16>::default();
+        let overlap = |min_overlap| AlignmentMode::Overlap { min_overlap };
+
+        for (mode, distance, aligned_x, aligned_y) in [
+            (AlignmentMode::Global, 6, "GGGACGTCCC", "---ACGT---"),
+            (AlignmentMode::SemiGlobal, 0, "GGGACGTCCC", "---ACGT---"),
+            (AlignmentMode::Glocal, 3, "GGGACGTCCC", "---ACGT---"),
+            // Without a minimum overlap, the sequences need not overlap at all.
+            (overlap(0), 0, "GGGACGTCCC----", "----------ACGT"),
+            (overlap(8), 2, "GGGACGTCCC", "---ACGT---"),
+        ] {
+            let table = compute_table(&x, &y, penalties, mode);
+            assert_eq!(table[y.len()][x.len()].0, distance, "{mode:?}");
+            let alignment = (aligned_x.to_string(), aligned_y.to_string());
+            assert_eq!(
+                aligned_strings(&trace_back_iterative(&table, [&x, &y])),
+                alignment
+            );
+            assert_eq!(
+                aligned_strings(&trace_back_recursive(&table, [&x, &y])),
+                alignment
+            );
+        }
+
+        // Only two elements at either end of `y` are free in the first and
+        // last columns.
+        let table = compute_table(&y, &x, penalties, overlap(8));
+        assert_eq!(table[x.len()][y.len()].0, 2);
+        let (aligned_y, aligned_x) = aligned_strings(&trace_back_iterative(&table, [&y, &x]));
+        assert_eq!(aligned_y.replace('-', ""), "ACGT");
+        assert_eq!(aligned_x.replace('-', ""), "GGGACGTCCC");
+    }
+
+    #[test]
+    fn test_edit_scripts() {
+        let x = "NAJIBPEPPERSEATS".chars().collect::<Vec<_>>();
+        let y = "NAJIBEATSPEPPERS".chars().collect::<Vec<_>>();
+        let table = compute_table::<_, u16>(&x, &y, Penalties::default(), AlignmentMode::Global);
+        let [x_to_y, y_to_x] = compute_edits(&trace_back_iterative(&table, [&x, &y]));
+        assert_eq!(
+            x_to_y,
+            [
+                Edit::Ins(5, 'E'),
+                Edit::Sub(6, 'A'),
+                Edit::Sub(7, 'T'),
+                Edit::Sub(8, 'S'),
+                Edit::Sub(11, 'P'),
+                Edit::Sub(12, 'P'),
+                Edit::Del(14),
+                Edit::Sub(14, 'R'),
+            ]
+        );
+        assert_eq!(y_to_x[0], Edit::Del(5));
+
+        let mut z = x.clone();
+        apply(&mut z, &x_to_y);
+        assert_eq!(z, y);
+        apply(&mut z, &invert(&x, &x_to_y));
+        assert_eq!(z, x);
+
+        let first = [Edit::Ins(1, 'a'), Edit::Sub(2, 'b')];
+        let second = [
+            Edit::Sub(2, 'c'),
+            Edit::Sub(1, 'd'),
+            Edit::Del(1),
+            Edit::Del(1),
+        ];
+        assert_eq!(
+            compose(&first, &second),
+            [
+                Edit::Ins(1, 'a'),
+                Edit::Sub(2, 'c'),
+                Edit::Del(1),
+                Edit::Del(1)
+            ]
+        );
+        assert_eq!(
+            compose(&[Edit::Ins(1, 'a')], &[Edit::Sub(1, 'b'), Edit::Del(1)]),
+            []
+        );
+        assert_eq!(
+            compose(&[Edit::Sub(0, 'a')], &[Edit::Sub(0, 'b'), Edit::Del(0)]),
+            [Edit::Del(0)]
+        );
+    }
+
+    #[test]
+    fn test_affine_trace_back() {
+        let x = "NAJIBEATSPEPPERS".chars().collect::<Vec<_>>();
+        let y = "NAJIBPEPPERS".chars().collect::<Vec<_>>();
+
+        // With linear gaps, the deletion of "EATS" may be split up.
+        let table = compute_affine_table::<_, u16>(
+            &x,
+            &y,
+            AffinePenalties::new(0, 1, 0, 1),
+            AlignmentMode::Global,
+        );
+        assert_eq!(affine_cell_min(table[y.len()][x.len()]).0, 4);
+
+        // With affine gaps, it is a single gap.
+        let penalties = AffinePenalties::new(0, 3, 3, 1);
+        let table = compute_affine_table::<_, u16>(&x, &y, penalties, AlignmentMode::Global);
+        assert_eq!(affine_cell_min(table[y.len()][x.len()]).0, 7);
+
+        let (aligned_x, aligned_y) =
+            aligned_strings(&trace_back_affine_iterative(&table, [&x, &y]));
+        assert_eq!(aligned_x, "NAJIBEATSPEPPERS");
+        assert_eq!(aligned_y, "NAJIB----PEPPERS");
+
+        let (aligned_x, aligned_y) =
+            aligned_strings(&trace_back_affine_recursive(&table, [&x, &y]));
+        assert_eq!(aligned_x, "NAJIBEATSPEPPERS");
+        assert_eq!(aligned_y, "NAJIB----PEPPERS");
+
+        let table = compute_affine_table::<_, u16>(&x, &[], penalties, AlignmentMode::Global);
+        assert_eq!(affine_cell_min(table[0][x.len()]).0, 19);
+
+        let (aligned_x, aligned_y) =
+            aligned_strings(&trace_back_affine_iterative(&table, [&x, &[]]));
+        assert_eq!(aligned_x, "NAJIBEATSPEPPERS");
+        assert_eq!(aligned_y, "----------------");
+
+        // Gaps at the free ends cost nothing, and those running past them are
+        // charged as gaps of the remaining length.
+        let x = "GGGACGTCCC".chars().collect::<Vec<_>>();
+        let y = "ACGT".chars().collect::<Vec<_>>();
+        for (mode, distance) in [
+            (AlignmentMode::SemiGlobal, 0),
+            (AlignmentMode::Glocal, 6),
+            (AlignmentMode::Overlap { min_overlap: 8 }, 8),
+        ] {
+            let table = compute_affine_table::<_, u16>(&x, &y, penalties, mode);
+            assert_eq!(
+                affine_cell_min(table[y.len()][x.len()]).0,
+                distance,
+                "{mode:?}"
+            );
+            assert_eq!(
+                aligned_strings(&trace_back_affine_iterative(&table, [&x, &y])),
+                ("GGGACGTCCC".to_string(), "---ACGT---".to_string())
+            );
+        }
     }
 }
@@ -0,0 +1,364 @@
+//! Hirschberg's linear-memory algorithm for the Needleman-Wunsch alignment.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    number::UInt,
+    strings::{sequences::AlignedPair, PenaltyScheme},
+};
+
+use super::{
+    helpers::{step, Direction},
+    AlignmentMode,
+};
+
+/// Computes the Needleman-Wunsch alignment of two sequences with Hirschberg's
+/// divide-and-conquer algorithm.
+///
+/// Instead of keeping the whole table, we compute the last row of the table
+/// for the first half of `y` against `x`, and that of the table for the
+/// reversed second half of `y` against the reversed `x`. The column at which
+/// the sum of these rows is smallest is where an optimal alignment crosses the
+/// middle of `y`, and we recurse on the two halves. This takes `O(n * m)` time
+/// and `O(n + m)` memory.
+///
+/// Among the columns with the smallest sum, we split at the last one. When
+/// one of the sequences has at most one element, we fall back to the full
+/// table, breaking ties in the same way as `compute_table`, i.e. preferring
+/// `Diagonal`, then `Up`, then `Left`. The alignment is optimal, and it is the
+/// same as that of `trace_back_iterative` when the optimal alignment is
+/// unique. When there are several optimal alignments, the two may differ.
+///
+/// # Arguments
+///
+/// * `[x, y]`: The two sequences to align.
+/// * `penalties`: The penalties to use.
+///
+/// # Returns
+///
+/// The alignment of the two sequences.
+pub fn trace_back_hirschberg<T: Eq + Clone, U: UInt>(
+    [x, y]: [&[T]; 2],
+    penalties: &impl PenaltyScheme<U, T>,
+) -> Vec<AlignedPair<T>> {
+    let mut alignment = Vec::new();
+    _hirschberg([x, y], penalties, &mut alignment);
+    alignment
+}
+
+/// Computes the Needleman-Wunsch alignment of two sequences in the given mode
+/// with Hirschberg's algorithm, as well as its total penalty.
+///
+/// We first find the cell of the table at which the best alignment ends, with
+/// one pass over the rows, and then the cell at which it starts, with a pass
+/// over the reversed prefixes of the sequences that end there. The free
+/// elements before the start and after the end are aligned against gaps,
+/// and those in between with `trace_back_hirschberg`. This still takes
+/// `O(n * m)` time and `O(n + m)` memory.
+///
+/// In the `Global` mode, the alignment is that of `trace_back_hirschberg`.
+/// Otherwise, it is optimal but, when there are ties, it may differ from that
+/// of `trace_back_iterative`.
+///
+/// # Arguments
+///
+/// * `[x, y]`: The two sequences to align.
+/// * `penalties`: The penalties to use.
+/// * `mode`: Which gaps at the ends of the sequences are free.
+///
+/// # Returns
+///
+/// A tuple of the alignment of the two sequences and its total penalty.
+pub fn align_hirschberg<T: Eq + Clone, U: UInt>(
+    [x, y]: [&[T]; 2],
+    penalties: &impl PenaltyScheme<U, T>,
+    mode: AlignmentMode,
+) -> (Vec<AlignedPair<T>>, U) {
+    let [[prefix_x, suffix_x], [prefix_y, suffix_y]] = mode.free_ends([x.len(), y.len()]);
+    let (distance, [end_i, end_j]) = best_end(
+        [x, y],
+        penalties,
+        [prefix_x, prefix_y],
+        [suffix_x, suffix_y],
+    );
+
+    let [start_i, start_j] = if prefix_x > 0 || prefix_y > 0 {
+        let reversed_x = x[..end_j].iter().rev().cloned().collect::<Vec<_>>();
+        let reversed_y = y[..end_i].iter().rev().cloned().collect::<Vec<_>>();
+        let (_, [len_i, len_j]) = best_end(
+            [&reversed_x, &reversed_y],
+            penalties,
+            [0, 0],
+            [prefix_x, prefix_y],
+        );
+        [end_i - len_i, end_j - len_j]
+    } else {
+        [0, 0]
+    };
+
+    // At most one of the sequences has free elements at each end.
+    let mut alignment = x[..start_j]
+        .iter()
+        .cloned()
+        .map(AlignedPair::GapInY)
+        .chain(y[..start_i].iter().cloned().map(AlignedPair::GapInX))
+        .collect::<Vec<_>>();
+    alignment.extend(trace_back_hirschberg(
+        [&x[start_j..end_j], &y[start_i..end_i]],
+        penalties,
+    ));
+    alignment.extend(x[end_j..].iter().cloned().map(AlignedPair::GapInY));
+    alignment.extend(y[end_i..].iter().cloned().map(AlignedPair::GapInX));
+
+    (alignment, distance)
+}
+
+/// Finds the cell of the table at which the best alignment ends, and its total
+/// penalty, keeping only one row of the table.
+///
+/// The first row and the first column start at zero for the first `prefix_x`
+/// and `prefix_y` elements of `x` and of `y`, respectively. The alignment may
+/// end in the last `suffix_x` cells of the last row, or the last `suffix_y`
+/// cells of the last column, before the bottom-right cell. Ties are broken by
+/// preferring the bottom-right cell, then the last column, then the last row,
+/// and then the cells closest to the bottom-right cell.
+fn best_end<T, U: UInt>(
+    [x, y]: [&[T]; 2],
+    penalties: &impl PenaltyScheme<U, T>,
+    [prefix_x, prefix_y]: [usize; 2],
+    [suffix_x, suffix_y]: [usize; 2],
+) -> (U, [usize; 2]) {
+    let gap = penalties.gap();
+    let (n, m) = (y.len(), x.len());
+
+    let mut row = (0..=m)
+        .map(|j| gap * U::from(j.saturating_sub(prefix_x)))
+        .collect::<Vec<_>>();
+    let mut column: Option<(U, usize)> = None;
+
+    for (i, y_c) in y.iter().enumerate() {
+        if i + suffix_y >= n && column.is_none_or(|(penalty, _)| row[m] <= penalty) {
+            column = Some((row[m], i));
+        }
+        let mut diagonal = row[0];
+        row[0] = gap * U::from((i + 1).saturating_sub(prefix_y));
+        for (j, x_c) in x.iter().enumerate() {
+            let up = row[j + 1];
+            row[j + 1] = (diagonal + penalties.substitution(x_c, y_c))
+                .min(up + gap)
+                .min(row[j] + gap);
+            diagonal = up;
+        }
+    }
+
+    let mut best = (row[m], [n, m]);
+    if let Some((penalty, i)) = column.filter(|&(penalty, _)| penalty < best.0) {
+        best = (penalty, [i, m]);
+    }
+    for j in (m.saturating_sub(suffix_x)..m).rev() {
+        if row[j] < best.0 {
+            best = (row[j], [n, j]);
+        }
+    }
+    best
+}
+
+/// Helper function for `trace_back_hirschberg`.
+///
+/// # Arguments
+///
+/// * `[x, y]`: The two sequences to align.
+/// * `penalties`: The penalties to use.
+/// * `alignment`: The alignment to which that of `x` and `y` is appended.
+fn _hirschberg<T: Eq + Clone, U: UInt>(
+    [x, y]: [&[T]; 2],
+    penalties: &impl PenaltyScheme<U, T>,
+    alignment: &mut Vec<AlignedPair<T>>,
+) {
+    if x.len() <= 1 || y.len() <= 1 {
+        alignment.extend(align_small([x, y], penalties));
+        return;
+    }
+
+    let mid = y.len() / 2;
+    let forward = last_row(&x.iter(), y[..mid].iter(), penalties);
+    let backward = last_row(&x.iter().rev(), y[mid..].iter().rev(), penalties);
+
+    // `backward[k]` is the cost of aligning the last `k` elements of `x`
+    // with the second half of `y`.
+    let split = forward
+        .iter()
+        .zip(backward.iter().rev())
+        .map(|(&f, &b)| f + b)
+        .enumerate()
+        .reduce(|best, cur| if cur.1 <= best.1 { cur } else { best })
+        .map_or(0, |(j, _)| j);
+
+    _hirschberg([&x[..split], &y[..mid]], penalties, alignment);
+    _hirschberg([&x[split..], &y[mid..]], penalties, alignment);
+}
+
+/// Computes the total penalty of aligning every prefix of `x` with all of
+/// `y`, keeping only one row of the table.
+pub fn last_row<'a, T: 'a, U: UInt>(
+    x: &(impl Iterator<Item = &'a T> + Clone),
+    y: impl Iterator<Item = &'a T>,
+    penalties: &impl PenaltyScheme<U, T>,
+) -> Vec<U> {
+    let gap = penalties.gap();
+    let mut row = core::iter::once(U::zero())
+        .chain(x.clone().scan(U::zero(), |total, _| {
+            *total += gap;
+            Some(*total)
+        }))
+        .collect::<Vec<_>>();
+
+    for y_c in y {
+        let mut diagonal = row[0];
+        row[0] += gap;
+        for (j, x_c) in x.clone().enumerate() {
+            let up = row[j + 1];
+            row[j + 1] = (diagonal + penalties.substitution(x_c, y_c))
+                .min(up + gap)
+                .min(row[j] + gap);
+            diagonal = up;
+        }
+    }
+
+    row
+}
+
+/// Aligns two sequences with the full table, when one of them has at most
+/// one element.
+fn align_small<T: Eq + Clone, U: UInt>(
+    [x, y]: [&[T]; 2],
+    penalties: &impl PenaltyScheme<U, T>,
+) -> Vec<AlignedPair<T>> {
+    let gap = penalties.gap();
+    let mut table = vec![vec![(U::zero(), Direction::Diagonal); x.len() + 1]; y.len() + 1];
+    for i in 1..=y.len() {
+        table[i][0] = (table[i - 1][0].0 + gap, Direction::Up);
+    }
+    for j in 1..=x.len() {
+        table[0][j] = (table[0][j - 1].0 + gap, Direction::Left);
+    }
+    for i in 1..=y.len() {
+        for j in 1..=x.len() {
+            let candidates = [
+                (
+                    table[i - 1][j - 1].0 + penalties.substitution(&x[j - 1], &y[i - 1]),
+                    Direction::Diagonal,
+                ),
+                (table[i - 1][j].0 + gap, Direction::Up),
+                (table[i][j - 1].0 + gap, Direction::Left),
+            ];
+            // Ties are broken in the order of the candidates.
+            table[i][j] = candidates
+                .into_iter()
+                .reduce(|best, cur| if cur.0 < best.0 { cur } else { best })
+                .unwrap_or_else(|| unreachable!("There are three candidates."));
+        }
+    }
+
+    let mut cell = [y.len(), x.len()];
+    let mut alignment = Vec::new();
+    while cell != [0, 0] {
+        let (pair, prev) = step(table[cell[0]][cell[1]].1, cell, [x, y]);
+        alignment.push(pair);
+        cell = prev;
+    }
+    alignment.reverse();
+    alignment
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::strings::{
+        needleman_wunsch::helpers::{aligned_strings, compute_table, trace_back_iterative},
+        Penalties,
+    };
+
+    #[test]
+    fn test_trace_back_hirschberg() {
+        let penalties = Penalties::<u16>::default();
+
+        // With ties, the alignment may differ from that of the full table, but
+        // it is optimal.
+        let x = "NAJIBPEPPERSEATS";
+        let y = "NAJIBEATSPEPPERS";
+        let (x_chars, y_chars) = (x.chars().collect::<Vec<_>>(), y.chars().collect::<Vec<_>>());
+        let (aligned_x, aligned_y) =
+            aligned_strings(&trace_back_hirschberg([&x_chars, &y_chars], &penalties));
+        assert_eq!(aligned_x.replace('-', ""), x);
+        assert_eq!(aligned_y.replace('-', ""), y);
+        let cost = aligned_x
+            .chars()
+            .zip(aligned_y.chars())
+            .filter(|(a, b)| a != b)
+            .count();
+        assert_eq!(cost, 8);
+
+        // Unique optimal alignments are the same as with the full table.
+        for (x, y) in [
+            ("ACGTACGT", "ACGACGTT"),
+            ("XA", "A"),
+            ("", "AB"),
+            ("GATTACA", "GATACA"),
+        ] {
+            let (x, y) = (x.chars().collect::<Vec<_>>(), y.chars().collect::<Vec<_>>());
+            let table = compute_table(&x, &y, penalties, AlignmentMode::Global);
+            assert_eq!(
+                trace_back_hirschberg([&x, &y], &penalties),
+                trace_back_iterative(&table, [&x, &y])
+            );
+            assert_eq!(
+                last_row(&x.iter(), y.iter(), &penalties)[x.len()],
+                table[y.len()][x.len()].0
+            );
+        }
+    }
+
+    #[test]
+    fn test_align_hirschberg() {
+        let penalties = Penalties::<u16>::default();
+        let x = "GGGACGTCCC".chars().collect::<Vec<_>>();
+        let y = "ACGT".chars().collect::<Vec<_>>();
+
+        let (alignment, distance) = align_hirschberg([&x, &y], &penalties, AlignmentMode::Global);
+        assert_eq!(distance, 6);
+        assert_eq!(alignment, trace_back_hirschberg([&x, &y], &penalties));
+
+        let (alignment, distance) =
+            align_hirschberg([&x, &y], &penalties, AlignmentMode::SemiGlobal);
+        assert_eq!(distance, 0);
+        assert_eq!(
+            aligned_strings(&alignment),
+            ("GGGACGTCCC".into(), "---ACGT---".into())
+        );
+
+        let (alignment, distance) = align_hirschberg(
+            [&y, &x],
+            &penalties,
+            AlignmentMode::Overlap { min_overlap: 8 },
+        );
+        assert_eq!(distance, 2);
+        assert_eq!(
+            aligned_strings(&alignment),
+            ("---ACGT---".into(), "GGGACGTCCC".into())
+        );
+
+        for mode in [
+            AlignmentMode::Glocal,
+            AlignmentMode::Overlap { min_overlap: 0 },
+            AlignmentMode::Overlap { min_overlap: 8 },
+        ] {
+            let (alignment, distance) = align_hirschberg([&x, &y], &penalties, mode);
+            let table = compute_table(&x, &y, penalties, mode);
+            assert_eq!(distance, table[y.len()][x.len()].0);
+            assert_eq!(alignment, trace_back_iterative(&table, [&x, &y]));
+        }
+    }
+}
@@ -2,12 +2,186 @@
 //!
 //! This implementation should not be considered stable.
 
+mod cigar;
+mod co_optimal;
 mod helpers;
+mod hirschberg;
 
-use super::Penalties;
-use crate::number::UInt;
+#[cfg(feature = "graphemes")]
+use alloc::borrow::ToOwned;
+use alloc::{string::String, vec::Vec};
 
-use helpers::{compute_edits, compute_table, trace_back_iterative, trace_back_recursive, Edit};
+#[cfg(feature = "graphemes")]
+use unicode_segmentation::UnicodeSegmentation;
+
+#[cfg(feature = "graphemes")]
+use super::sequences;
+use super::{check_strings, sequences::AlignedPair, Penalties, PenaltyScheme};
+use crate::{number::UInt, DistanceError};
+
+use helpers::{compose, trace_back_affine_recursive, trace_back_recursive};
+
+pub use cigar::{Cigar, CigarError, CigarOp};
+pub use co_optimal::CoOptimalAlignments;
+pub(crate) use co_optimal::{align, compute_table_all, count_paths, longest_paths, random_path};
+pub use helpers::Edit;
+pub(crate) use helpers::{
+    affine_cell_min, apply, compute_affine_table, compute_edits, compute_table, invert,
+    trace_back_affine_iterative, trace_back_iterative,
+};
+pub(crate) use hirschberg::align_hirschberg;
+
+/// Affine gap penalties to use in the Needleman-Wunsch distance calculation.
+///
+/// With the linear gap penalty of [`Penalties`], a gap of length `k` costs
+/// `k * gap`, so that many short gaps cost as much as one long gap. With
+/// affine gap penalties, a gap of length `k` costs
+/// `gap_open + k * gap_extend`, which favors fewer, longer gaps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AffinePenalties<U: UInt> {
+    /// Penalty for a match.
+    pub(crate) match_: U,
+    /// Penalty for a mis-match.
+    pub(crate) mismatch: U,
+    /// Penalty for opening a gap.
+    pub(crate) gap_open: U,
+    /// Penalty for each position in a gap.
+    pub(crate) gap_extend: U,
+}
+
+impl<U: UInt> Default for AffinePenalties<U> {
+    fn default() -> Self {
+        Self {
+            match_: U::zero(),
+            mismatch: U::one(),
+            gap_open: U::one(),
+            gap_extend: U::one(),
+        }
+    }
+}
+
+impl<U: UInt> AffinePenalties<U> {
+    /// Create a set of affine penalties to use for the NW distance metric.
+    pub const fn new(match_: U, mismatch: U, gap_open: U, gap_extend: U) -> Self {
+        Self {
+            match_,
+            mismatch,
+            gap_open,
+            gap_extend,
+        }
+    }
+
+    /// Extend a set of `Penalties` with a gap-opening penalty, using their gap
+    /// penalty as the gap-extension penalty.
+    pub const fn from_penalties(penalties: Penalties<U>, gap_open: U) -> Self {
+        Self::new(
+            penalties.match_,
+            penalties.mismatch,
+            gap_open,
+            penalties.gap,
+        )
+    }
+}
+
+/// Which gaps at the ends of the sequences are free in a Needleman-Wunsch
+/// alignment.
+///
+/// A global alignment charges every gap, so that aligning a short read `y`
+/// against a long reference `x` is dominated by the gaps at its ends. The
+/// other modes do not charge for some elements of `x` or `y` before the
+/// first aligned pair or after the last one. The alignments and edits still
+/// cover both sequences in full, with these elements against gaps, but the
+/// distance does not include them.
+///
+/// Since all penalties are non-negative, freeing the prefix of one sequence
+/// and the suffix of the other would make the empty alignment free. The
+/// `Overlap` mode therefore keeps at least `min_overlap` elements of each
+/// sequence out of its free prefix and suffix. Except for `Global`, the
+/// distances are not metrics, e.g. two different strings may be at distance
+/// zero.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::{
+///     needleman_wunsch::{nw_distance_custom, AlignmentMode},
+///     Penalties,
+/// };
+///
+/// let (x, y) = ("GGGACGTCCC", "ACCT");
+/// let distance = |mode| nw_distance_custom::<u16>(Penalties::default(), mode)(x, y);
+///
+/// // Six gaps and a mismatch.
+/// assert_eq!(distance(AlignmentMode::Global), 7);
+/// // Only the mismatch of "ACGT" against "ACCT".
+/// assert_eq!(distance(AlignmentMode::SemiGlobal), 1);
+/// // "GGGACGT" against "---ACCT", and "CCC" is free.
+/// assert_eq!(distance(AlignmentMode::Glocal), 4);
+/// // "ACGT" within `x` against "ACCT", or the suffix "CCC" of `x` against
+/// // the prefix "ACC" of `y`, with one mismatch.
+/// assert_eq!(distance(AlignmentMode::Overlap { min_overlap: 3 }), 1);
+///
+/// // The suffix "TACA" of `x` is the prefix of `y`.
+/// let overlap = |min_overlap| {
+///     nw_distance_custom::<u16>(Penalties::default(), AlignmentMode::Overlap { min_overlap })
+/// };
+/// assert_eq!(overlap(4)("GATTACA", "TACAGGT"), 0);
+/// // A fifth element of each sequence in the overlap costs two gaps.
+/// assert_eq!(overlap(5)("GATTACA", "TACAGGT"), 2);
+/// // Without a minimum overlap, the empty alignment is free.
+/// assert_eq!(overlap(0)("GATTACA", "CCCCCCC"), 0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AlignmentMode {
+    /// Every gap is charged.
+    #[default]
+    Global,
+    /// The whole of `y` is aligned within `x`, i.e. the elements of `x`
+    /// before and after those aligned with `y` are free, as when aligning a
+    /// read against a reference.
+    SemiGlobal,
+    /// A suffix of one sequence is aligned with a prefix of the other, as
+    /// when assembling overlapping reads, or one sequence is aligned within
+    /// the other. The alignment starts at the start of either sequence and
+    /// ends at the end of either sequence, and the rest of the other sequence
+    /// is free at each end.
+    ///
+    /// At most `len - min_overlap` elements of a sequence of length `len` are
+    /// free at either end, and any more are charged as gaps. A suffix of one
+    /// sequence is thus aligned with a prefix of the other only if each holds
+    /// at least `min_overlap` elements. With a `min_overlap` of zero, the
+    /// distance is always zero.
+    Overlap {
+        /// The smallest number of elements of each sequence in the overlap.
+        min_overlap: usize,
+    },
+    /// The whole of `y` is aligned with a prefix of `x`, i.e. global at the
+    /// start and local at the end, so that the elements of `x` after those
+    /// aligned with `y` are free, as when extending an alignment from a seed.
+    Glocal,
+}
+
+impl AlignmentMode {
+    /// Returns how many elements of the prefix and of the suffix of `x`, and
+    /// of those of `y`, are free, as
+    /// `[[x_prefix, x_suffix], [y_prefix, y_suffix]]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `[m, n]`: The lengths of `x` and `y`.
+    pub(crate) const fn free_ends(self, [m, n]: [usize; 2]) -> [[usize; 2]; 2] {
+        match self {
+            Self::Global => [[0, 0], [0, 0]],
+            Self::SemiGlobal => [[m, m], [0, 0]],
+            Self::Overlap { min_overlap } => {
+                let (x_free, y_free) =
+                    (m.saturating_sub(min_overlap), n.saturating_sub(min_overlap));
+                [[x_free, x_free], [y_free, y_free]]
+            }
+            Self::Glocal => [[0, m], [0, 0]],
+        }
+    }
+}
 
 /// Use a custom set of penalties to create a function to that calculates the
 /// Needleman-Wunsch edit distance between two strings using the specified
@@ -18,19 +192,51 @@ use helpers::{compute_edits, compute_table, trace_back_iterative, trace_back_rec
 ///
 /// # Arguments:
 ///
-/// * `penalties`: The penalties to use in the generated function.
+/// * `penalties`: The penalties to use in the generated function. These may
+///   be a set of [`Penalties`], or [`MatrixPenalties`](super::MatrixPenalties)
+///   derived from a substitution matrix.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
 ///
 /// # Returns:
 ///
 /// A function with the same signature as `nw_distance`.
-pub fn nw_distance_custom<U: UInt>(penalties: Penalties<U>) -> impl Fn(&str, &str) -> U {
-    move |x: &str, y: &str| compute_table(x, y, penalties)[y.len()][x.len()].0
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::{
+///     needleman_wunsch::{nw_distance_custom, AlignmentMode},
+///     MatrixPenalties, SubstitutionMatrix,
+/// };
+///
+/// let matrix = SubstitutionMatrix::dna_transition_transversion();
+/// let metric = nw_distance_custom(MatrixPenalties::new(&matrix, 4), AlignmentMode::Global);
+///
+/// // A transition costs 2 and a transversion costs 3.
+/// let distance: u16 = metric("ACGT", "ACAT");
+/// assert_eq!(distance, 2);
+/// let distance: u16 = metric("ACGT", "ACCT");
+/// assert_eq!(distance, 3);
+/// ```
+pub fn nw_distance_custom<U: UInt>(
+    penalties: impl PenaltyScheme<U>,
+    mode: AlignmentMode,
+) -> impl Fn(&str, &str) -> U {
+    move |x: &str, y: &str| {
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+        compute_table(&x, &y, &penalties, mode)[y.len()][x.len()].0
+    }
 }
 
 /// Calculate the edit distance between two strings using Needleman-Wunsch table.
 /// This function is only accurate with a scoring scheme for which all penalties
 /// are non-negative.
 ///
+/// The distance is computed over Unicode scalar values, so that each
+/// character counts once regardless of the number of bytes it takes in UTF-8.
+///
 /// * [Demo](https://bioboot.github.io/bimm143_W20/class-material/nw/)
 /// * [Wikipedia](https://en.wikipedia.org/wiki/Needleman%E2%80%93Wunsch_algorithm)
 ///
@@ -38,9 +244,48 @@ pub fn nw_distance_custom<U: UInt>(penalties: Penalties<U>) -> impl Fn(&str, &st
 ///
 /// * `x`: unaligned sequence represented as a `String`
 /// * `y`: unaligned sequence represented as a `String`
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::needleman_wunsch::nw_distance;
+///
+/// let distance: u16 = nw_distance("naïve café", "naive cafe");
+/// assert_eq!(distance, 2);
+///
+/// let distance: u16 = nw_distance("東京都", "京都");
+/// assert_eq!(distance, 1);
+/// ```
 #[must_use]
 pub fn nw_distance<U: UInt>(x: &str, y: &str) -> U {
-    compute_table(x, y, Penalties::default())[y.len()][x.len()].0
+    let x = x.chars().collect::<Vec<_>>();
+    let y = y.chars().collect::<Vec<_>>();
+    compute_table(&x, &y, Penalties::default(), AlignmentMode::Global)[y.len()][x.len()].0
+}
+
+/// Checked version of [`nw_distance_custom`].
+///
+/// The generated function returns [`DistanceError::EmptyInput`] if either
+/// sequence is empty.
+pub fn try_nw_distance_custom<U: UInt>(
+    penalties: impl PenaltyScheme<U>,
+    mode: AlignmentMode,
+) -> impl Fn(&str, &str) -> Result<U, DistanceError> {
+    let metric = nw_distance_custom(penalties, mode);
+    move |x: &str, y: &str| {
+        check_strings(x, y)?;
+        Ok(metric(x, y))
+    }
+}
+
+/// Checked version of [`nw_distance`].
+///
+/// # Errors
+///
+/// * [`DistanceError::EmptyInput`] if either sequence is empty.
+pub fn try_nw_distance<U: UInt>(x: &str, y: &str) -> Result<U, DistanceError> {
+    check_strings(x, y)?;
+    Ok(nw_distance(x, y))
 }
 
 /// Use a custom set of penalties to create a function to that calculates the
@@ -50,18 +295,22 @@ pub fn nw_distance<U: UInt>(x: &str, y: &str) -> U {
 /// # Arguments:
 ///
 /// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
 ///
 /// # Returns:
 ///
 /// A function with the same signature as `edits_recursive`.
 pub fn edits_recursive_custom<U: UInt>(
-    penalties: Penalties<U>,
+    penalties: impl PenaltyScheme<U>,
+    mode: AlignmentMode,
 ) -> impl Fn(&str, &str) -> ([Vec<Edit>; 2], U) {
     move |x: &str, y: &str| {
-        let table = compute_table(x, y, penalties);
-        let (aligned_x, aligned_y) = trace_back_recursive(&table, [x, y]);
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+        let table = compute_table(&x, &y, &penalties, mode);
         (
-            compute_edits(&aligned_x, &aligned_y),
+            compute_edits(&trace_back_recursive(&table, [&x, &y])),
             table[y.len()][x.len()].0,
         )
     }
@@ -81,10 +330,11 @@ pub fn edits_recursive_custom<U: UInt>(
 /// * `y`: an unaligned sequence.
 #[must_use]
 pub fn edits_recursive<U: UInt>(x: &str, y: &str) -> ([Vec<Edit>; 2], U) {
-    let table = compute_table(x, y, Penalties::default());
-    let (aligned_x, aligned_y) = trace_back_recursive(&table, [x, y]);
+    let x = x.chars().collect::<Vec<_>>();
+    let y = y.chars().collect::<Vec<_>>();
+    let table = compute_table(&x, &y, Penalties::default(), AlignmentMode::Global);
     (
-        compute_edits(&aligned_x, &aligned_y),
+        compute_edits(&trace_back_recursive(&table, [&x, &y])),
         table[y.len()][x.len()].0,
     )
 }
@@ -96,18 +346,22 @@ pub fn edits_recursive<U: UInt>(x: &str, y: &str) -> ([Vec<Edit>; 2], U) {
 /// # Arguments:
 ///
 /// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
 ///
 /// # Returns:
 ///
 /// A function with the same signature as `edits_iterative`.
 pub fn edits_iterative_custom<U: UInt>(
-    penalties: Penalties<U>,
+    penalties: impl PenaltyScheme<U>,
+    mode: AlignmentMode,
 ) -> impl Fn(&str, &str) -> ([Vec<Edit>; 2], U) {
     move |x: &str, y: &str| {
-        let table = compute_table(x, y, penalties);
-        let (aligned_x, aligned_y) = trace_back_iterative(&table, [x, y]);
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+        let table = compute_table(&x, &y, &penalties, mode);
         (
-            compute_edits(&aligned_x, &aligned_y),
+            compute_edits(&trace_back_iterative(&table, [&x, &y])),
             table[y.len()][x.len()].0,
         )
     }
@@ -127,16 +381,678 @@ pub fn edits_iterative_custom<U: UInt>(
 /// * `y`: an unaligned sequence.
 #[must_use]
 pub fn edits_iterative<U: UInt>(x: &str, y: &str) -> ([Vec<Edit>; 2], U) {
-    let table = compute_table(x, y, Penalties::default());
-    let (aligned_x, aligned_y) = trace_back_iterative(&table, [x, y]);
+    let x = x.chars().collect::<Vec<_>>();
+    let y = y.chars().collect::<Vec<_>>();
+    let table = compute_table(&x, &y, Penalties::default(), AlignmentMode::Global);
     (
-        compute_edits(&aligned_x, &aligned_y),
+        compute_edits(&trace_back_iterative(&table, [&x, &y])),
         table[y.len()][x.len()].0,
     )
 }
 
+/// Use a custom set of penalties to create a function that calculates the
+/// edits and NW edit distance between two sequences in linear memory.
+///
+/// # Arguments:
+///
+/// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns:
+///
+/// A function with the same signature as `edits_hirschberg`.
+pub fn edits_hirschberg_custom<U: UInt>(
+    penalties: impl PenaltyScheme<U>,
+    mode: AlignmentMode,
+) -> impl Fn(&str, &str) -> ([Vec<Edit>; 2], U) {
+    move |x: &str, y: &str| {
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+        let (alignment, distance) = align_hirschberg([&x, &y], &penalties, mode);
+        (compute_edits(&alignment), distance)
+    }
+}
+
+/// Determine the set of edits needed to turn one unaligned sequence into
+/// another, as well as the edit distance between the two sequences, using
+/// Hirschberg's algorithm.
+///
+/// Contrast to `edits_iterative` and `edits_recursive`, which keep the whole
+/// Needleman-Wunsch table in memory, i.e. `O(n * m)` cells for sequences of
+/// lengths `n` and `m`. Hirschberg's algorithm recomputes parts of the table
+/// as needed to find the alignment, which takes about twice as long but only
+/// `O(n + m)` memory.
+///
+/// The distance is the same as that of `edits_iterative`. The alignment, and
+/// hence the edits, are the same when the optimal alignment is unique. When
+/// there are ties, we split each sub-problem at the last column at which an
+/// optimal alignment crosses the middle row of the table, and otherwise
+/// prefer aligning two characters, then a gap in `x`, then a gap in `y`, as in
+/// `edits_iterative`. Both alignments are then optimal but they may differ.
+///
+/// The alignment is computed over Unicode scalar values.
+///
+/// # Arguments:
+///
+/// * `x`: an unaligned sequence.
+/// * `y`: an unaligned sequence.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::needleman_wunsch::edits_hirschberg;
+///
+/// let ([x_to_y, y_to_x], distance) = edits_hirschberg::<u16>("NAJIBPEPPERS", "NAJIBEATSPEPPERS");
+///
+/// assert_eq!(distance, 4);
+/// assert_eq!(x_to_y.len(), 4);
+/// assert_eq!(y_to_x.len(), 4);
+/// ```
+///
+/// # References
+///
+/// * [A linear space algorithm for computing maximal common subsequences](https://doi.org/10.1145/360825.360861)
+#[must_use]
+pub fn edits_hirschberg<U: UInt>(x: &str, y: &str) -> ([Vec<Edit>; 2], U) {
+    edits_hirschberg_custom(Penalties::default(), AlignmentMode::Global)(x, y)
+}
+
+/// Use a custom set of penalties to create a function that lazily iterates
+/// over all the co-optimal Needleman-Wunsch alignments of two strings.
+///
+/// # Arguments:
+///
+/// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns:
+///
+/// A function with the same signature as `co_optimal_alignments`.
+pub fn co_optimal_alignments_custom<U: UInt>(
+    penalties: impl PenaltyScheme<U>,
+    mode: AlignmentMode,
+) -> impl Fn(&str, &str) -> CoOptimalAlignments<char> {
+    move |x: &str, y: &str| {
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+        let table = compute_table_all(&x, &y, &penalties, mode);
+        CoOptimalAlignments::new(x, y, &table)
+    }
+}
+
+/// Lazily iterate over all the alignments of two strings with the smallest
+/// Needleman-Wunsch edit distance.
+///
+/// Whereas `edits_iterative` and the other functions of this module break
+/// ties between the alignments by preferring aligning two characters, then a
+/// gap in `x`, then a gap in `y`, this keeps every direction that leads to
+/// the best penalty in each cell of the table. The first alignment is the one
+/// given by `edits_iterative`. See [`CoOptimalAlignments`] for details.
+///
+/// The number of co-optimal alignments may grow exponentially with the
+/// lengths of the strings. Use [`co_optimal_count`] to count them first, or
+/// [`random_co_optimal_alignment`] to sample one of them.
+///
+/// # Arguments:
+///
+/// * `x`: an unaligned sequence.
+/// * `y`: an unaligned sequence.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::{
+///     needleman_wunsch::{co_optimal_alignments, Cigar},
+///     sequences::AlignedPair,
+/// };
+///
+/// let alignments = co_optimal_alignments("AB", "BA").collect::<Vec<_>>();
+///
+/// assert_eq!(alignments.len(), 3);
+/// assert_eq!(
+///     alignments[0],
+///     [AlignedPair::Mismatch('A', 'B'), AlignedPair::Mismatch('B', 'A')]
+/// );
+/// assert_eq!(
+///     alignments[1],
+///     [AlignedPair::GapInY('A'), AlignedPair::Match('B', 'B'), AlignedPair::GapInX('A')]
+/// );
+///
+/// // The aligned strings, with `-` for gaps, are a CIGAR string away.
+/// let aligned = Cigar::from_alignment(&alignments[2]).to_aligned("AB", "BA");
+/// assert_eq!(aligned, Ok(("-AB".to_string(), "BA-".to_string())));
+/// ```
+#[must_use]
+pub fn co_optimal_alignments(x: &str, y: &str) -> CoOptimalAlignments<char> {
+    co_optimal_alignments_custom(Penalties::<usize>::default(), AlignmentMode::Global)(x, y)
+}
+
+/// Use a custom set of penalties to create a function that counts the
+/// co-optimal Needleman-Wunsch alignments of two strings.
+///
+/// # Arguments:
+///
+/// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns:
+///
+/// A function with the same signature as `co_optimal_count`.
+pub fn co_optimal_count_custom<U: UInt>(
+    penalties: impl PenaltyScheme<U>,
+    mode: AlignmentMode,
+) -> impl Fn(&str, &str) -> u128 {
+    move |x: &str, y: &str| {
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+        count_paths(&compute_table_all(&x, &y, &penalties, mode))[y.len()][x.len()]
+    }
+}
+
+/// Count the alignments of two strings with the smallest Needleman-Wunsch edit
+/// distance, i.e. those given by [`co_optimal_alignments`].
+///
+/// This takes `O(n * m)` time, however many alignments there are. The count
+/// saturates at `u128::MAX`.
+///
+/// # Arguments:
+///
+/// * `x`: an unaligned sequence.
+/// * `y`: an unaligned sequence.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::needleman_wunsch::co_optimal_count;
+///
+/// assert_eq!(co_optimal_count("AB", "BA"), 3);
+/// assert_eq!(co_optimal_count("NOTGUILTY", "NOTGUILTY"), 1);
+/// assert_eq!(co_optimal_count("NAJIBPEPPERSEATS", "NAJIBEATSPEPPERS"), 16);
+/// ```
+#[must_use]
+pub fn co_optimal_count(x: &str, y: &str) -> u128 {
+    co_optimal_count_custom(Penalties::<usize>::default(), AlignmentMode::Global)(x, y)
+}
+
+/// Use a custom set of penalties to create a function that samples one of the
+/// co-optimal Needleman-Wunsch alignments of two strings uniformly at random.
+///
+/// # Arguments:
+///
+/// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns:
+///
+/// A function with the same signature as `random_co_optimal_alignment`.
+pub fn random_co_optimal_alignment_custom<U: UInt, R: rand::Rng>(
+    penalties: impl PenaltyScheme<U>,
+    mode: AlignmentMode,
+) -> impl Fn(&str, &str, &mut R) -> Vec<AlignedPair<char>> {
+    move |x: &str, y: &str, rng: &mut R| {
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+        let table = compute_table_all(&x, &y, &penalties, mode);
+        let path = random_path(&table, &count_paths(&table), rng);
+        align(&path, [&x, &y])
+    }
+}
+
+/// Sample one of the alignments of two strings with the smallest
+/// Needleman-Wunsch edit distance uniformly at random.
+///
+/// We count the optimal paths to every cell of the table, as in
+/// [`co_optimal_count`], and go back from the last cell, choosing each
+/// direction with probability proportional to the number of optimal paths
+/// through it. The sample is uniform as long as the count does not saturate.
+///
+/// # Arguments:
+///
+/// * `x`: an unaligned sequence.
+/// * `y`: an unaligned sequence.
+/// * `rng`: the random number generator to use.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::needleman_wunsch::{co_optimal_alignments, random_co_optimal_alignment};
+/// use rand::SeedableRng;
+///
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+/// let alignment = random_co_optimal_alignment("AB", "BA", &mut rng);
+///
+/// assert!(co_optimal_alignments("AB", "BA").any(|a| a == alignment));
+/// ```
+pub fn random_co_optimal_alignment<R: rand::Rng>(
+    x: &str,
+    y: &str,
+    rng: &mut R,
+) -> Vec<AlignedPair<char>> {
+    random_co_optimal_alignment_custom(Penalties::<usize>::default(), AlignmentMode::Global)(
+        x, y, rng,
+    )
+}
+
+/// Use a custom set of penalties to create a function that calculates the
+/// Needleman-Wunsch alignment of two strings as a [`Cigar`], as well as the
+/// NW edit distance between them.
+///
+/// # Arguments:
+///
+/// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns:
+///
+/// A function with the same signature as `nw_cigar`.
+pub fn nw_cigar_custom<U: UInt>(
+    penalties: impl PenaltyScheme<U>,
+    mode: AlignmentMode,
+) -> impl Fn(&str, &str) -> (Cigar, U) {
+    move |x: &str, y: &str| {
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+        let table = compute_table(&x, &y, &penalties, mode);
+        (
+            Cigar::from_alignment(&trace_back_iterative(&table, [&x, &y])),
+            table[y.len()][x.len()].0,
+        )
+    }
+}
+
+/// Determine the Needleman-Wunsch alignment of two strings as an extended
+/// [`Cigar`], as well as the edit distance between them.
+///
+/// The alignment is the same as that of `edits_iterative`, with `x` as the
+/// reference and `y` as the query. The [`Cigar`] can be converted back into
+/// aligned strings or edits, and gives the MD tag of the SAM format.
+///
+/// # Arguments:
+///
+/// * `x`: the reference.
+/// * `y`: the query.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::needleman_wunsch::nw_cigar;
+///
+/// let (x, y) = ("NAJIBPEPPERSEATS", "NAJIBEATSPEPPERS");
+/// let (cigar, distance) = nw_cigar::<u16>(x, y);
+///
+/// assert_eq!(distance, 8);
+/// assert_eq!(cigar.to_string(), "5=1I3X2=2X1=1D1X1=");
+/// assert_eq!(cigar.md_tag(x, y).unwrap(), "5P0E0P2R0S1^A0T1");
+///
+/// let (aligned_x, aligned_y) = cigar.to_aligned(x, y).unwrap();
+/// assert_eq!(aligned_x, "NAJIB-PEPPERSEATS");
+/// assert_eq!(aligned_y, "NAJIBEATSPEPPE-RS");
+/// ```
+///
+/// # References
+///
+/// * [The SAM format specification](https://samtools.github.io/hts-specs/SAMv1.pdf)
+#[must_use]
+pub fn nw_cigar<U: UInt>(x: &str, y: &str) -> (Cigar, U) {
+    nw_cigar_custom(Penalties::default(), AlignmentMode::Global)(x, y)
+}
+
+/// Applies a script of edits to a string, e.g. to recover a string from its
+/// edits against a reference.
+///
+/// The edits are applied in order, and the index of each edit counts the
+/// characters of the string as edited by all the previous edits. The edits
+/// computed by `edits_iterative` and the other functions of this module,
+/// when applied to `x`, give `y`, and vice versa. This holds for strings of
+/// any characters, including `-`, which is only used for gaps in the aligned
+/// strings given by [`co_optimal_alignments`] and [`Cigar::to_aligned`].
+///
+/// # Arguments:
+///
+/// * `x`: The string to edit.
+/// * `edits`: The edits to apply.
+///
+/// # Panics
+///
+/// * If the index of an edit is out of bounds for the string as edited by the
+///   previous edits.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::needleman_wunsch::{apply_edits, edits_iterative, Edit};
+///
+/// let (x, y) = ("NAJIBPEPPERS", "NAJIBEATSPEPPERS");
+/// let ([x_to_y, y_to_x], _) = edits_iterative::<u16>(x, y);
+///
+/// assert_eq!(apply_edits(x, &x_to_y), y);
+/// assert_eq!(apply_edits(y, &y_to_x), x);
+///
+/// let ([x_to_y, _], _) = edits_iterative::<u16>("a-b", "ab");
+/// assert_eq!(x_to_y, [Edit::Del(1)]);
+/// assert_eq!(apply_edits("a-b", &x_to_y), "ab");
+///
+/// let edits = [Edit::Sub(0, 'c'), Edit::Del(1), Edit::Sub(3, 'é')];
+/// assert_eq!(apply_edits("naïve", &edits), "cïvé");
+/// ```
+#[must_use]
+pub fn apply_edits(x: &str, edits: &[Edit]) -> String {
+    let mut x = x.chars().collect::<Vec<_>>();
+    apply(&mut x, edits);
+    x.into_iter().collect()
+}
+
+/// Computes the script of edits that undoes a script of edits on a string.
+///
+/// If applying `edits` to `x` gives `y`, then applying the inverted edits to
+/// `y` gives `x`. The string `x` is needed to recover the characters that
+/// were deleted or substituted. The inverted edits undo the edits in the
+/// reverse order, so that they are in decreasing order of position when the
+/// edits were in increasing order.
+///
+/// # Arguments:
+///
+/// * `x`: The string to which `edits` apply.
+/// * `edits`: The edits to invert.
+///
+/// # Panics
+///
+/// * If the index of an edit is out of bounds for the string as edited by the
+///   previous edits.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::needleman_wunsch::{apply_edits, invert_edits, Edit};
+///
+/// let edits = [Edit::Sub(0, 'c'), Edit::Del(1), Edit::Sub(3, 'é')];
+/// let inverse = invert_edits("naïve", &edits);
+///
+/// assert_eq!(inverse, [Edit::Sub(3, 'e'), Edit::Ins(1, 'a'), Edit::Sub(0, 'n')]);
+/// assert_eq!(apply_edits("cïvé", &inverse), "naïve");
+/// ```
+#[must_use]
+pub fn invert_edits(x: &str, edits: &[Edit]) -> Vec<Edit> {
+    invert(&x.chars().collect::<Vec<_>>(), edits)
+}
+
+/// Composes two scripts of edits into one.
+///
+/// If applying `first` to `x` gives `y`, and applying `second` to `y` gives
+/// `z`, then applying the composed edits to `x` gives `z`. The composed script
+/// is that of `first` followed by that of `second`, in which consecutive edits
+/// at the same index are merged. For example, an insertion followed by a
+/// deletion at the same index cancels out, and two substitutions at the same
+/// index become one.
+///
+/// This works on the edits of both strings and other
+/// [`sequences`](super::sequences).
+///
+/// # Arguments:
+///
+/// * `first`: The edits to apply first.
+/// * `second`: The edits to apply second.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::needleman_wunsch::{apply_edits, compose_edits, edits_iterative};
+///
+/// let (x, y, z) = ("NAJIBPEPPERS", "NAJIBEATSPEPPERS", "NAJIBEATS");
+/// let ([x_to_y, _], _) = edits_iterative::<u16>(x, y);
+/// let ([y_to_z, _], _) = edits_iterative::<u16>(y, z);
+///
+/// let x_to_z = compose_edits(&x_to_y, &y_to_z);
+/// assert_eq!(apply_edits(x, &x_to_z), z);
+/// ```
+#[must_use]
+pub fn compose_edits<T: Clone>(first: &[Edit<T>], second: &[Edit<T>]) -> Vec<Edit<T>> {
+    compose(first, second)
+}
+
+/// Use a set of affine gap penalties to create a function that calculates
+/// the Needleman-Wunsch edit distance between two strings.
+///
+/// We use Gotoh's algorithm, which keeps three values per cell of the table
+/// and takes `O(n * m)` time and memory. With a gap-opening penalty of zero,
+/// this gives the same distance as [`nw_distance_custom`] with a gap penalty
+/// equal to the gap-extension penalty.
+///
+/// The distance is computed over Unicode scalar values.
+///
+/// # Arguments:
+///
+/// * `penalties`: The affine penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns:
+///
+/// A function with the same signature as `nw_distance`.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::needleman_wunsch::{nw_distance_affine, AffinePenalties, AlignmentMode};
+///
+/// let metric = nw_distance_affine(AffinePenalties::new(0, 1, 4, 1), AlignmentMode::Global);
+///
+/// // One gap of length 4 costs 8, while two gaps of length 2 would cost 12.
+/// let distance: u16 = metric("NAJIBEATSPEPPERS", "NAJIBPEPPERS");
+/// assert_eq!(distance, 8);
+/// ```
+///
+/// # References
+///
+/// * [An improved algorithm for matching biological sequences](https://doi.org/10.1016/0022-2836(82)90398-9)
+pub fn nw_distance_affine<U: UInt>(
+    penalties: AffinePenalties<U>,
+    mode: AlignmentMode,
+) -> impl Fn(&str, &str) -> U {
+    move |x: &str, y: &str| {
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+        let table = compute_affine_table(&x, &y, penalties, mode);
+        affine_cell_min(table[y.len()][x.len()]).0
+    }
+}
+
+/// Use a set of affine gap penalties to create a function that calculates the
+/// set of edits needed to turn one unaligned sequence into another, as well as
+/// the NW edit distance between the two sequences.
+///
+/// See [`nw_distance_affine`] for details on the penalties.
+///
+/// # Arguments:
+///
+/// * `penalties`: The affine penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns:
+///
+/// A function with the same signature as `edits_recursive`.
+pub fn edits_recursive_affine<U: UInt>(
+    penalties: AffinePenalties<U>,
+    mode: AlignmentMode,
+) -> impl Fn(&str, &str) -> ([Vec<Edit>; 2], U) {
+    move |x: &str, y: &str| {
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+        let table = compute_affine_table(&x, &y, penalties, mode);
+        (
+            compute_edits(&trace_back_affine_recursive(&table, [&x, &y])),
+            affine_cell_min(table[y.len()][x.len()]).0,
+        )
+    }
+}
+
+/// Use a set of affine gap penalties to create a function that calculates the
+/// set of edits needed to turn one unaligned sequence into another, as well as
+/// the NW edit distance between the two sequences.
+///
+/// See [`nw_distance_affine`] for details on the penalties.
+///
+/// # Arguments:
+///
+/// * `penalties`: The affine penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns:
+///
+/// A function with the same signature as `edits_iterative`.
+pub fn edits_iterative_affine<U: UInt>(
+    penalties: AffinePenalties<U>,
+    mode: AlignmentMode,
+) -> impl Fn(&str, &str) -> ([Vec<Edit>; 2], U) {
+    move |x: &str, y: &str| {
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+        let table = compute_affine_table(&x, &y, penalties, mode);
+        (
+            compute_edits(&trace_back_affine_iterative(&table, [&x, &y])),
+            affine_cell_min(table[y.len()][x.len()]).0,
+        )
+    }
+}
+
+/// Use a custom set of penalties to create a function that calculates the
+/// Needleman-Wunsch edit distance between two strings segmented into extended
+/// grapheme clusters.
+///
+/// # Arguments:
+///
+/// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns:
+///
+/// A function with the same signature as `nw_distance_graphemes`.
+#[cfg(feature = "graphemes")]
+pub fn nw_distance_graphemes_custom<U: UInt>(
+    penalties: Penalties<U>,
+    mode: AlignmentMode,
+) -> impl Fn(&str, &str) -> U {
+    move |x: &str, y: &str| {
+        let x = x.graphemes(true).collect::<Vec<_>>();
+        let y = y.graphemes(true).collect::<Vec<_>>();
+        sequences::nw_distance_custom(penalties, mode)(&x, &y)
+    }
+}
+
+/// Calculate the Needleman-Wunsch edit distance between two strings segmented
+/// into extended grapheme clusters.
+///
+/// Whereas [`nw_distance`] works on Unicode scalar values, this treats each
+/// user-perceived character as a single element of the sequence, e.g. a
+/// letter followed by combining accents, or an emoji built from several code
+/// points. This requires the `graphemes` feature.
+///
+/// # Arguments:
+///
+/// * `x`: unaligned sequence represented as a `String`
+/// * `y`: unaligned sequence represented as a `String`
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::needleman_wunsch::{nw_distance, nw_distance_graphemes};
+///
+/// // "é" written as an "e" followed by a combining acute accent, and as a
+/// // single precomposed character.
+/// let (x, y) = ("cafe\u{301}", "caf\u{e9}");
+///
+/// let distance: u16 = nw_distance(x, y);
+/// assert_eq!(distance, 2);
+/// let distance: u16 = nw_distance_graphemes(x, y);
+/// assert_eq!(distance, 1);
+///
+/// let distance: u16 = nw_distance_graphemes(x, "cafe\u{301}s");
+/// assert_eq!(distance, 1);
+/// ```
+#[cfg(feature = "graphemes")]
+#[must_use]
+pub fn nw_distance_graphemes<U: UInt>(x: &str, y: &str) -> U {
+    nw_distance_graphemes_custom(Penalties::default(), AlignmentMode::Global)(x, y)
+}
+
+/// Use a custom set of penalties to create a function that calculates the
+/// edits and NW edit distance between two sequences of grapheme clusters.
+///
+/// # Arguments:
+///
+/// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns:
+///
+/// A function with the same signature as `edits_graphemes`.
+#[cfg(feature = "graphemes")]
+pub fn edits_graphemes_custom<U: UInt>(
+    penalties: Penalties<U>,
+    mode: AlignmentMode,
+) -> impl Fn(&str, &str) -> ([Vec<Edit<String>>; 2], U) {
+    move |x: &str, y: &str| {
+        let x = x.graphemes(true).collect::<Vec<_>>();
+        let y = y.graphemes(true).collect::<Vec<_>>();
+        let ([x_to_y, y_to_x], distance) = sequences::edits_custom(penalties, mode)(&x, &y);
+        ([to_owned_edits(x_to_y), to_owned_edits(y_to_x)], distance)
+    }
+}
+
+/// Determine the set of edits needed to turn one unaligned sequence into
+/// another, as well as the edit distance between the two sequences, segmented
+/// into extended grapheme clusters.
+///
+/// Each edit holds a whole grapheme cluster, and its index is counted in
+/// grapheme clusters. This requires the `graphemes` feature.
+///
+/// # Arguments:
+///
+/// * `x`: an unaligned sequence.
+/// * `y`: an unaligned sequence.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::needleman_wunsch::{edits_graphemes, Edit};
+///
+/// let ([x_to_y, _], distance) = edits_graphemes::<u16>("cafe", "cafe\u{301}");
+///
+/// assert_eq!(distance, 1);
+/// assert_eq!(x_to_y, [Edit::Sub(3, "e\u{301}".to_string())]);
+/// ```
+#[cfg(feature = "graphemes")]
+#[must_use]
+pub fn edits_graphemes<U: UInt>(x: &str, y: &str) -> ([Vec<Edit<String>>; 2], U) {
+    edits_graphemes_custom(Penalties::default(), AlignmentMode::Global)(x, y)
+}
+
+/// Converts edits borrowing grapheme clusters from the input into owned ones.
+#[cfg(feature = "graphemes")]
+fn to_owned_edits(edits: Vec<Edit<&str>>) -> Vec<Edit<String>> {
+    edits
+        .into_iter()
+        .map(|edit| match edit {
+            Edit::Del(i) => Edit::Del(i),
+            Edit::Ins(i, g) => Edit::Ins(i, g.to_owned()),
+            Edit::Sub(i, g) => Edit::Sub(i, g.to_owned()),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
+    use alloc::string::ToString;
+
     use super::nw_distance;
 
     #[test]
@@ -0,0 +1,182 @@
+//! Normalized versions of the string distances, which lie in `[0, 1]`.
+
+use alloc::vec::Vec;
+
+use crate::number::Float;
+
+use super::{
+    hamming, levenshtein,
+    needleman_wunsch::{compute_table_all, longest_paths, AlignmentMode},
+    Penalties,
+};
+
+/// Computes the Levenshtein distance between two strings divided by the
+/// number of characters in the longer string.
+///
+/// This is the most common normalization, and it lies in `[0, 1]` since no
+/// more edits are needed than there are characters in the longer string. It
+/// is not a metric, since it does not obey the triangle inequality, e.g. with
+/// `"ab"`, `"aba"` and `"ba"` the distances are `1 / 3`, `1 / 3` and `1`. Use
+/// [`levenshtein_yujian_bo`] if a metric is needed.
+///
+/// Two empty strings are at distance zero. The distance is computed over
+/// Unicode scalar values.
+///
+/// # Arguments
+///
+/// * `x`: The first string.
+/// * `y`: The second string.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::levenshtein_normalized;
+///
+/// let distance: f64 = levenshtein_normalized("NAJIBEATSPEPPERS", "NAJIBPEPPERSEATS");
+/// assert!((distance - 0.5).abs() <= f64::EPSILON);
+///
+/// let distance: f32 = levenshtein_normalized("café", "cafés");
+/// assert!((distance - 0.2).abs() <= f32::EPSILON);
+/// ```
+#[must_use]
+pub fn levenshtein_normalized<U: Float>(x: &str, y: &str) -> U {
+    let max_len = x.chars().count().max(y.chars().count());
+    if max_len == 0 {
+        U::zero()
+    } else {
+        U::from(levenshtein::<usize>(x, y)) / U::from(max_len)
+    }
+}
+
+/// Computes the normalized Levenshtein distance of Yujian and Bo between two
+/// strings.
+///
+/// With a Levenshtein distance of `d` between strings of lengths `n` and `m`,
+/// this is `2 * d / (n + m + d)`. It lies in `[0, 1]`, and unlike
+/// [`levenshtein_normalized`] and [`nw_distance_normalized`], it is a metric,
+/// i.e. it obeys the triangle inequality.
+///
+/// Two empty strings are at distance zero. The distance is computed over
+/// Unicode scalar values.
+///
+/// # Arguments
+///
+/// * `x`: The first string.
+/// * `y`: The second string.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::levenshtein_yujian_bo;
+///
+/// // The Levenshtein distance is 8 between strings of length 16.
+/// let distance: f64 = levenshtein_yujian_bo("NAJIBEATSPEPPERS", "NAJIBPEPPERSEATS");
+/// assert!((distance - 0.4).abs() <= f64::EPSILON);
+///
+/// let distance: f64 = levenshtein_yujian_bo("", "東京");
+/// assert!((distance - 1.0).abs() <= f64::EPSILON);
+/// ```
+///
+/// # References
+///
+/// * [A Normalized Levenshtein Distance Metric](https://doi.org/10.1109/TPAMI.2007.1078)
+#[must_use]
+pub fn levenshtein_yujian_bo<U: Float>(x: &str, y: &str) -> U {
+    let d = levenshtein::<usize>(x, y);
+    if d == 0 {
+        U::zero()
+    } else {
+        let total = x.chars().count() + y.chars().count() + d;
+        U::from(2 * d) / U::from(total)
+    }
+}
+
+/// Computes the Needleman-Wunsch distance between two strings divided by the
+/// length of their longest optimal alignment.
+///
+/// The length of an alignment is the number of columns, i.e. of matches,
+/// mismatches and gaps. When there are several optimal alignments, they may
+/// have different lengths, e.g. `"ab"` and `"ba"` are aligned both as
+/// `"ab"`/`"ba"` and as `"ab-"`/`"-ba"`. We use the longest of them, as given
+/// by [`co_optimal_alignments`](super::needleman_wunsch::co_optimal_alignments),
+/// so that the distance does not depend on how ties are broken and is
+/// symmetric. It lies in `[0, 1]` since each column costs at most one. It is
+/// not a metric, since it does not obey the triangle inequality, e.g. with
+/// `"aab"`, `"aabc"` and `"bcc"` the distances are `1 / 4`, `3 / 5` and `1`.
+///
+/// Two empty strings are at distance zero. The distance is computed over
+/// Unicode scalar values.
+///
+/// # Arguments
+///
+/// * `x`: The first string.
+/// * `y`: The second string.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::nw_distance_normalized;
+///
+/// // The longest alignment is "NAJIB----PEPPERSEATS" against
+/// // "NAJIBEATSPEPPERS----".
+/// let distance: f64 = nw_distance_normalized("NAJIBPEPPERSEATS", "NAJIBEATSPEPPERS");
+/// assert!((distance - 8.0 / 20.0).abs() <= f64::EPSILON);
+///
+/// let distance: f64 = nw_distance_normalized("ab", "ba");
+/// assert!((distance - 2.0 / 3.0).abs() <= f64::EPSILON);
+/// ```
+#[must_use]
+pub fn nw_distance_normalized<U: Float>(x: &str, y: &str) -> U {
+    let x = x.chars().collect::<Vec<_>>();
+    let y = y.chars().collect::<Vec<_>>();
+    let table = compute_table_all(
+        &x,
+        &y,
+        &Penalties::<usize>::default(),
+        AlignmentMode::Global,
+    );
+    let (distance, length) = (
+        table[y.len()][x.len()].0,
+        longest_paths(&table)[y.len()][x.len()],
+    );
+    if length == 0 {
+        U::zero()
+    } else {
+        U::from(distance) / U::from(length)
+    }
+}
+
+/// Computes the Hamming distance between two strings divided by the number of
+/// positions compared.
+///
+/// As with [`hamming`], the distance is only computed up to the length of the
+/// shorter string, so that this is the fraction of those positions at which
+/// the strings differ. It lies in `[0, 1]`, and it is a metric over strings
+/// of the same length. Over strings of different lengths, it is not, since
+/// e.g. `"ab"` and `"abc"` are at distance zero.
+///
+/// If either string is empty, the distance is zero. The distance is computed
+/// over Unicode scalar values.
+///
+/// # Arguments
+///
+/// * `x`: The first string.
+/// * `y`: The second string.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::hamming_normalized;
+///
+/// let distance: f64 = hamming_normalized("NAJIBEATSPEPPERS", "NAJIBPEPPERSEATS");
+/// assert!((distance - 10.0 / 16.0).abs() <= f64::EPSILON);
+/// ```
+#[must_use]
+pub fn hamming_normalized<U: Float>(x: &str, y: &str) -> U {
+    let len = x.chars().count().min(y.chars().count());
+    if len == 0 {
+        U::zero()
+    } else {
+        U::from(hamming::<usize>(x, y)) / U::from(len)
+    }
+}
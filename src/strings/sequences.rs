@@ -0,0 +1,651 @@
+//! Distances and alignments between sequences of arbitrary tokens.
+//!
+//! The functions in the parent module work on the characters of strings.
+//! Those in this module work on slices of any type that can be compared for
+//! equality, e.g. words, k-mer ids, 2-bit encoded nucleotides or amino acid
+//! codes. Alignments are returned as a sequence of [`AlignedPair`]s rather
+//! than as strings padded with `-`, so that the sequences may contain any
+//! value.
+//!
+//! These share their implementation with the functions for strings, which
+//! align the characters of the strings in the same way.
+
+use alloc::vec::Vec;
+
+use crate::number::UInt;
+
+use super::{
+    _levenshtein,
+    needleman_wunsch::{
+        affine_cell_min, align, align_hirschberg, apply, compute_affine_table, compute_edits,
+        compute_table, compute_table_all, count_paths, invert, random_path,
+        trace_back_affine_iterative, trace_back_iterative, AffinePenalties, AlignmentMode,
+        CoOptimalAlignments, Edit,
+    },
+    Penalties, PenaltyScheme,
+};
+
+/// A column in the alignment of two sequences `x` and `y`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AlignedPair<T> {
+    /// An element of `x` aligned with an equal element of `y`.
+    Match(T, T),
+    /// An element of `x` aligned with a different element of `y`.
+    Mismatch(T, T),
+    /// An element of `y` aligned with a gap in `x`.
+    GapInX(T),
+    /// An element of `x` aligned with a gap in `y`.
+    GapInY(T),
+}
+
+/// Computes the Levenshtein distance between two sequences.
+///
+/// This is the same distance as [`levenshtein`](super::levenshtein) computes
+/// between the characters of two strings, i.e. the minimum number of
+/// insertions, deletions and substitutions of single elements needed to turn
+/// one sequence into the other.
+///
+/// # Arguments
+///
+/// * `x`: The first sequence.
+/// * `y`: The second sequence.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::sequences::levenshtein;
+///
+/// let x = ["the", "cat", "sat", "on", "the", "mat"];
+/// let y = ["the", "cat", "sat", "on", "a", "mat"];
+///
+/// let distance: u16 = levenshtein(&x, &y);
+/// assert_eq!(distance, 1);
+/// ```
+#[must_use]
+pub fn levenshtein<T: Eq, U: UInt>(x: &[T], y: &[T]) -> U {
+    levenshtein_custom(Penalties::default())(x, y)
+}
+
+/// Creates a function to compute the Levenshtein distance between two
+/// sequences using a custom set of penalties. The generated function will
+/// have the same signature as [`levenshtein`].
+///
+/// # Arguments
+///
+/// * `penalties`: the set of penalties to use
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::{sequences::levenshtein_custom, Penalties};
+///
+/// let metric = levenshtein_custom(Penalties::new(0, 3, 1));
+///
+/// // 2-bit encoded nucleotides.
+/// let distance: u16 = metric(&[0_u8, 1, 2, 3], &[0, 2, 2, 3]);
+/// assert_eq!(distance, 2);
+/// ```
+pub fn levenshtein_custom<T, U: UInt>(
+    penalties: impl PenaltyScheme<U, T>,
+) -> impl Fn(&[T], &[T]) -> U {
+    move |x: &[T], y: &[T]| _levenshtein(x, y, &penalties)
+}
+
+/// Computes the Hamming distance between two sequences, i.e. the number of
+/// positions at which they hold different elements.
+///
+/// As with [`hamming`](super::hamming), the distance is only computed up to
+/// the length of the shorter sequence.
+///
+/// # Arguments
+///
+/// * `x`: The first sequence.
+/// * `y`: The second sequence.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::sequences::hamming;
+///
+/// let distance: u16 = hamming(&[1_u32, 2, 3, 4], &[1, 5, 3, 6]);
+/// assert_eq!(distance, 2);
+/// ```
+#[must_use]
+pub fn hamming<T: Eq, U: UInt>(x: &[T], y: &[T]) -> U {
+    U::from(x.iter().zip(y.iter()).filter(|(a, b)| a != b).count())
+}
+
+/// Calculate the edit distance between two sequences using the
+/// Needleman-Wunsch table.
+///
+/// This is the sequence version of
+/// [`nw_distance`](super::needleman_wunsch::nw_distance).
+///
+/// # Arguments
+///
+/// * `x`: The first sequence.
+/// * `y`: The second sequence.
+#[must_use]
+pub fn nw_distance<T: Eq, U: UInt>(x: &[T], y: &[T]) -> U {
+    nw_distance_custom(Penalties::default(), AlignmentMode::Global)(x, y)
+}
+
+/// Use a custom set of penalties to create a function that calculates the
+/// Needleman-Wunsch edit distance between two sequences.
+///
+/// # Arguments
+///
+/// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns
+///
+/// A function with the same signature as `nw_distance`.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::{
+///     needleman_wunsch::AlignmentMode,
+///     sequences::nw_distance_custom,
+///     Penalties,
+/// };
+///
+/// let x = ["the", "cat", "sat", "on", "the", "mat"];
+/// let y = ["sat", "on", "a", "mat"];
+///
+/// // The words of `x` around the alignment are free in semi-global mode.
+/// let metric = nw_distance_custom(Penalties::default(), AlignmentMode::SemiGlobal);
+/// let distance: u16 = metric(&x, &y);
+/// assert_eq!(distance, 1);
+///
+/// let metric = nw_distance_custom(Penalties::default(), AlignmentMode::Global);
+/// let distance: u16 = metric(&x, &y);
+/// assert_eq!(distance, 3);
+/// ```
+pub fn nw_distance_custom<T, U: UInt>(
+    penalties: impl PenaltyScheme<U, T>,
+    mode: AlignmentMode,
+) -> impl Fn(&[T], &[T]) -> U {
+    move |x: &[T], y: &[T]| compute_table(x, y, &penalties, mode)[y.len()][x.len()].0
+}
+
+/// Determine the alignment of two sequences with the Needleman-Wunsch table,
+/// as well as the edit distance between them.
+///
+/// In cases where there exist ties for the shortest edit distance, we only
+/// return one alignment, breaking ties in the same way as
+/// [`edits_iterative`](super::needleman_wunsch::edits_iterative).
+///
+/// # Arguments
+///
+/// * `x`: The first sequence.
+/// * `y`: The second sequence.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::sequences::{nw_alignment, AlignedPair};
+///
+/// // Gaps in the sequences are ordinary elements.
+/// let x = ['A', '-', 'C'];
+/// let y = ['A', 'C'];
+///
+/// let (alignment, distance) = nw_alignment::<_, u16>(&x, &y);
+///
+/// assert_eq!(distance, 1);
+/// assert_eq!(
+///     alignment,
+///     vec![
+///         AlignedPair::Match('A', 'A'),
+///         AlignedPair::GapInY('-'),
+///         AlignedPair::Match('C', 'C'),
+///     ]
+/// );
+/// ```
+#[must_use]
+pub fn nw_alignment<T: Eq + Clone, U: UInt>(x: &[T], y: &[T]) -> (Vec<AlignedPair<T>>, U) {
+    nw_alignment_custom(Penalties::default(), AlignmentMode::Global)(x, y)
+}
+
+/// Use a custom set of penalties to create a function that calculates the
+/// Needleman-Wunsch alignment of two sequences, as well as the edit distance
+/// between them.
+///
+/// # Arguments
+///
+/// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns
+///
+/// A function with the same signature as `nw_alignment`.
+pub fn nw_alignment_custom<T: Eq + Clone, U: UInt>(
+    penalties: impl PenaltyScheme<U, T>,
+    mode: AlignmentMode,
+) -> impl Fn(&[T], &[T]) -> (Vec<AlignedPair<T>>, U) {
+    move |x: &[T], y: &[T]| {
+        let table = compute_table(x, y, &penalties, mode);
+        (
+            trace_back_iterative(&table, [x, y]),
+            table[y.len()][x.len()].0,
+        )
+    }
+}
+
+/// Determine the alignment of two sequences, as well as the edit distance
+/// between them, with Hirschberg's linear-memory algorithm.
+///
+/// This is the sequence version of
+/// [`edits_hirschberg`](super::needleman_wunsch::edits_hirschberg), which
+/// describes how ties are broken.
+///
+/// # Arguments
+///
+/// * `x`: The first sequence.
+/// * `y`: The second sequence.
+#[must_use]
+pub fn nw_alignment_hirschberg<T: Eq + Clone, U: UInt>(
+    x: &[T],
+    y: &[T],
+) -> (Vec<AlignedPair<T>>, U) {
+    nw_alignment_hirschberg_custom(Penalties::default(), AlignmentMode::Global)(x, y)
+}
+
+/// Use a custom set of penalties to create a function that calculates the
+/// Hirschberg alignment of two sequences and the edit distance between them.
+///
+/// # Arguments
+///
+/// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns
+///
+/// A function with the same signature as `nw_alignment_hirschberg`.
+pub fn nw_alignment_hirschberg_custom<T: Eq + Clone, U: UInt>(
+    penalties: impl PenaltyScheme<U, T>,
+    mode: AlignmentMode,
+) -> impl Fn(&[T], &[T]) -> (Vec<AlignedPair<T>>, U) {
+    move |x: &[T], y: &[T]| align_hirschberg([x, y], &penalties, mode)
+}
+
+/// Use a set of affine gap penalties to create a function that calculates
+/// the Needleman-Wunsch edit distance between two sequences.
+///
+/// This is the sequence version of
+/// [`nw_distance_affine`](super::needleman_wunsch::nw_distance_affine).
+///
+/// # Arguments
+///
+/// * `penalties`: The affine penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns
+///
+/// A function with the same signature as `nw_distance`.
+pub fn nw_distance_affine<T: Eq, U: UInt>(
+    penalties: AffinePenalties<U>,
+    mode: AlignmentMode,
+) -> impl Fn(&[T], &[T]) -> U {
+    move |x: &[T], y: &[T]| {
+        let table = compute_affine_table(x, y, penalties, mode);
+        affine_cell_min(table[y.len()][x.len()]).0
+    }
+}
+
+/// Use a set of affine gap penalties to create a function that calculates the
+/// Needleman-Wunsch alignment of two sequences, as well as the edit distance
+/// between them.
+///
+/// # Arguments
+///
+/// * `penalties`: The affine penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns
+///
+/// A function with the same signature as `nw_alignment`.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::{
+///     needleman_wunsch::{AffinePenalties, AlignmentMode},
+///     sequences::{nw_alignment_affine, AlignedPair},
+/// };
+///
+/// let x = [1_u8, 2, 3, 4, 5];
+/// let y = [1_u8, 5];
+///
+/// // One gap of length 3 costs 5.
+/// let metric = nw_alignment_affine(AffinePenalties::new(0, 3, 2, 1), AlignmentMode::Global);
+/// let (alignment, distance): (_, u16) = metric(&x, &y);
+/// assert_eq!(distance, 5);
+/// assert_eq!(alignment[1..4], [2, 3, 4].map(AlignedPair::GapInY));
+/// ```
+pub fn nw_alignment_affine<T: Eq + Clone, U: UInt>(
+    penalties: AffinePenalties<U>,
+    mode: AlignmentMode,
+) -> impl Fn(&[T], &[T]) -> (Vec<AlignedPair<T>>, U) {
+    move |x: &[T], y: &[T]| {
+        let table = compute_affine_table(x, y, penalties, mode);
+        (
+            trace_back_affine_iterative(&table, [x, y]),
+            affine_cell_min(table[y.len()][x.len()]).0,
+        )
+    }
+}
+
+/// Lazily iterate over all the alignments of two sequences with the smallest
+/// Needleman-Wunsch edit distance.
+///
+/// This is the sequence version of
+/// [`co_optimal_alignments`](super::needleman_wunsch::co_optimal_alignments).
+///
+/// # Arguments
+///
+/// * `x`: The first sequence.
+/// * `y`: The second sequence.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::sequences::{co_optimal_alignments, AlignedPair};
+///
+/// let x = ["the", "cat"];
+/// let y = ["cat", "the"];
+///
+/// let alignments = co_optimal_alignments(&x, &y).collect::<Vec<_>>();
+///
+/// assert_eq!(alignments.len(), 3);
+/// assert_eq!(
+///     alignments[1],
+///     [
+///         AlignedPair::GapInY("the"),
+///         AlignedPair::Match("cat", "cat"),
+///         AlignedPair::GapInX("the"),
+///     ]
+/// );
+/// ```
+#[must_use]
+pub fn co_optimal_alignments<T: Eq + Clone>(x: &[T], y: &[T]) -> CoOptimalAlignments<T> {
+    co_optimal_alignments_custom(Penalties::<usize>::default(), AlignmentMode::Global)(x, y)
+}
+
+/// Use a custom set of penalties to create a function that lazily iterates
+/// over all the co-optimal Needleman-Wunsch alignments of two sequences.
+///
+/// # Arguments
+///
+/// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns
+///
+/// A function with the same signature as `co_optimal_alignments`.
+pub fn co_optimal_alignments_custom<T: Eq + Clone, U: UInt>(
+    penalties: impl PenaltyScheme<U, T>,
+    mode: AlignmentMode,
+) -> impl Fn(&[T], &[T]) -> CoOptimalAlignments<T> {
+    move |x: &[T], y: &[T]| {
+        let table = compute_table_all(x, y, &penalties, mode);
+        CoOptimalAlignments::new(x.to_vec(), y.to_vec(), &table)
+    }
+}
+
+/// Count the alignments of two sequences with the smallest Needleman-Wunsch
+/// edit distance, i.e. those given by [`co_optimal_alignments`].
+///
+/// This is the sequence version of
+/// [`co_optimal_count`](super::needleman_wunsch::co_optimal_count).
+///
+/// # Arguments
+///
+/// * `x`: The first sequence.
+/// * `y`: The second sequence.
+#[must_use]
+pub fn co_optimal_count<T: Eq>(x: &[T], y: &[T]) -> u128 {
+    co_optimal_count_custom(Penalties::<usize>::default(), AlignmentMode::Global)(x, y)
+}
+
+/// Use a custom set of penalties to create a function that counts the
+/// co-optimal Needleman-Wunsch alignments of two sequences.
+///
+/// # Arguments
+///
+/// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns
+///
+/// A function with the same signature as `co_optimal_count`.
+pub fn co_optimal_count_custom<T, U: UInt>(
+    penalties: impl PenaltyScheme<U, T>,
+    mode: AlignmentMode,
+) -> impl Fn(&[T], &[T]) -> u128 {
+    move |x: &[T], y: &[T]| {
+        count_paths(&compute_table_all(x, y, &penalties, mode))[y.len()][x.len()]
+    }
+}
+
+/// Sample one of the alignments of two sequences with the smallest
+/// Needleman-Wunsch edit distance uniformly at random.
+///
+/// This is the sequence version of
+/// [`random_co_optimal_alignment`](super::needleman_wunsch::random_co_optimal_alignment).
+///
+/// # Arguments
+///
+/// * `x`: The first sequence.
+/// * `y`: The second sequence.
+/// * `rng`: The random number generator to use.
+pub fn random_co_optimal_alignment<T: Eq + Clone, R: rand::Rng>(
+    x: &[T],
+    y: &[T],
+    rng: &mut R,
+) -> Vec<AlignedPair<T>> {
+    random_co_optimal_alignment_custom(Penalties::<usize>::default(), AlignmentMode::Global)(
+        x, y, rng,
+    )
+}
+
+/// Use a custom set of penalties to create a function that samples one of the
+/// co-optimal Needleman-Wunsch alignments of two sequences uniformly at
+/// random.
+///
+/// # Arguments
+///
+/// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns
+///
+/// A function with the same signature as `random_co_optimal_alignment`.
+pub fn random_co_optimal_alignment_custom<T: Eq + Clone, U: UInt, R: rand::Rng>(
+    penalties: impl PenaltyScheme<U, T>,
+    mode: AlignmentMode,
+) -> impl Fn(&[T], &[T], &mut R) -> Vec<AlignedPair<T>> {
+    move |x: &[T], y: &[T], rng: &mut R| {
+        let table = compute_table_all(x, y, &penalties, mode);
+        align(&random_path(&table, &count_paths(&table), rng), [x, y])
+    }
+}
+
+/// Determine the set of edits needed to turn one sequence into another, as
+/// well as the edit distance between the two sequences.
+///
+/// This is the sequence version of
+/// [`edits_iterative`](super::needleman_wunsch::edits_iterative).
+///
+/// # Arguments
+///
+/// * `x`: The first sequence.
+/// * `y`: The second sequence.
+#[must_use]
+pub fn edits<T: Eq + Clone, U: UInt>(x: &[T], y: &[T]) -> ([Vec<Edit<T>>; 2], U) {
+    edits_custom(Penalties::default(), AlignmentMode::Global)(x, y)
+}
+
+/// Use a custom set of penalties to create a function that calculates the set
+/// of edits needed to turn one sequence into another, as well as the NW edit
+/// distance between the two sequences.
+///
+/// # Arguments
+///
+/// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns
+///
+/// A function with the same signature as `edits`.
+#[allow(clippy::type_complexity)]
+pub fn edits_custom<T: Eq + Clone, U: UInt>(
+    penalties: impl PenaltyScheme<U, T>,
+    mode: AlignmentMode,
+) -> impl Fn(&[T], &[T]) -> ([Vec<Edit<T>>; 2], U) {
+    let alignment = nw_alignment_custom(penalties, mode);
+    move |x: &[T], y: &[T]| {
+        let (alignment, distance) = alignment(x, y);
+        (compute_edits(&alignment), distance)
+    }
+}
+
+/// Determine the set of edits needed to turn one sequence into another, as
+/// well as the edit distance between the two sequences, with Hirschberg's
+/// linear-memory algorithm.
+///
+/// # Arguments
+///
+/// * `x`: The first sequence.
+/// * `y`: The second sequence.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::sequences::{apply_edits, edits_hirschberg};
+///
+/// let x = ["a", "-", "b"];
+/// let y = ["a", "b", "-"];
+/// let ([x_to_y, _], distance) = edits_hirschberg::<_, u8>(&x, &y);
+///
+/// assert_eq!(distance, 2);
+/// assert_eq!(apply_edits(&x, &x_to_y), y);
+/// ```
+#[must_use]
+pub fn edits_hirschberg<T: Eq + Clone, U: UInt>(x: &[T], y: &[T]) -> ([Vec<Edit<T>>; 2], U) {
+    edits_hirschberg_custom(Penalties::default(), AlignmentMode::Global)(x, y)
+}
+
+/// Use a custom set of penalties to create a function that calculates the
+/// edits and NW edit distance between two sequences in linear memory.
+///
+/// # Arguments
+///
+/// * `penalties`: The penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns
+///
+/// A function with the same signature as `edits_hirschberg`.
+#[allow(clippy::type_complexity)]
+pub fn edits_hirschberg_custom<T: Eq + Clone, U: UInt>(
+    penalties: impl PenaltyScheme<U, T>,
+    mode: AlignmentMode,
+) -> impl Fn(&[T], &[T]) -> ([Vec<Edit<T>>; 2], U) {
+    let alignment = nw_alignment_hirschberg_custom(penalties, mode);
+    move |x: &[T], y: &[T]| {
+        let (alignment, distance) = alignment(x, y);
+        (compute_edits(&alignment), distance)
+    }
+}
+
+/// Use a set of affine gap penalties to create a function that calculates the
+/// set of edits needed to turn one sequence into another, as well as the NW
+/// edit distance between the two sequences.
+///
+/// # Arguments
+///
+/// * `penalties`: The affine penalties to use in the generated function.
+/// * `mode`: Which gaps at the ends of the sequences are free. See
+///   [`AlignmentMode`].
+///
+/// # Returns
+///
+/// A function with the same signature as `edits`.
+#[allow(clippy::type_complexity)]
+pub fn edits_affine<T: Eq + Clone, U: UInt>(
+    penalties: AffinePenalties<U>,
+    mode: AlignmentMode,
+) -> impl Fn(&[T], &[T]) -> ([Vec<Edit<T>>; 2], U) {
+    let alignment = nw_alignment_affine(penalties, mode);
+    move |x: &[T], y: &[T]| {
+        let (alignment, distance) = alignment(x, y);
+        (compute_edits(&alignment), distance)
+    }
+}
+
+/// Applies a script of edits to a sequence.
+///
+/// This is the sequence version of
+/// [`apply_edits`](super::needleman_wunsch::apply_edits).
+///
+/// # Arguments
+///
+/// * `x`: The sequence to edit.
+/// * `edits`: The edits to apply.
+///
+/// # Panics
+///
+/// * If the index of an edit is out of bounds for the sequence as edited by
+///   the previous edits.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::sequences::{apply_edits, edits};
+///
+/// let x = ["the", "quick", "brown", "fox"];
+/// let y = ["the", "red", "fox", "jumps"];
+/// let ([x_to_y, y_to_x], _) = edits::<_, u8>(&x, &y);
+///
+/// assert_eq!(apply_edits(&x, &x_to_y), y);
+/// assert_eq!(apply_edits(&y, &y_to_x), x);
+/// ```
+#[must_use]
+pub fn apply_edits<T: Clone>(x: &[T], edits: &[Edit<T>]) -> Vec<T> {
+    let mut x = x.to_vec();
+    apply(&mut x, edits);
+    x
+}
+
+/// Computes the script of edits that undoes a script of edits on a sequence.
+///
+/// This is the sequence version of
+/// [`invert_edits`](super::needleman_wunsch::invert_edits). Scripts of edits
+/// of sequences may be composed with
+/// [`compose_edits`](super::needleman_wunsch::compose_edits).
+///
+/// # Arguments
+///
+/// * `x`: The sequence to which `edits` apply.
+/// * `edits`: The edits to invert.
+///
+/// # Panics
+///
+/// * If the index of an edit is out of bounds for the sequence as edited by
+///   the previous edits.
+#[must_use]
+pub fn invert_edits<T: Clone>(x: &[T], edits: &[Edit<T>]) -> Vec<Edit<T>> {
+    invert(x, edits)
+}
@@ -0,0 +1,169 @@
+//! Helper functions for the Smith-Waterman algorithm.
+
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::number::IInt;
+
+use super::Scores;
+
+/// The direction of best alignment at a given position in the DP table
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// The local alignment starts at this position.
+    Stop,
+    /// Diagonal (Up and Left) for a match or mismatch.
+    Diagonal,
+    /// Up for a gap in the first sequence.
+    Up,
+    /// Left for a gap in the second sequence.
+    Left,
+}
+
+/// Computes the Smith-Waterman dynamic programming table for two sequences.
+///
+/// Each cell holds the best score of a local alignment ending at that
+/// position, which is never negative. Our implementation maximizes the total
+/// score.
+///
+/// # Arguments
+///
+/// * `x`: The first sequence.
+/// * `y`: The second sequence.
+/// * `scores`: The scores to use.
+///
+/// # Returns
+///
+/// A nested vector of tuples of total-score and Direction, representing the
+/// best local alignment ending at each position. The rows are indexed by `y`
+/// and the columns by `x`.
+pub fn compute_table<I: IInt>(
+    x: &[char],
+    y: &[char],
+    scores: Scores<I>,
+) -> Vec<Vec<(I, Direction)>> {
+    // The first row and column are all zeros, since every local alignment
+    // may start anywhere.
+    let mut table = vec![vec![(I::zero(), Direction::Stop); x.len() + 1]; y.len() + 1];
+
+    for (i, &y_c) in y.iter().enumerate() {
+        for (j, &x_c) in x.iter().enumerate() {
+            let substitution = if x_c == y_c {
+                scores.match_
+            } else {
+                scores.mismatch
+            };
+
+            // Compute the three possible scores and use the maximum to set the
+            // value for the next entry in the table, starting a new alignment
+            // if all of them are negative.
+            let d00 = (table[i][j].0 + substitution, Direction::Diagonal);
+            let d01 = (table[i][j + 1].0 + scores.gap, Direction::Up);
+            let d10 = (table[i + 1][j].0 + scores.gap, Direction::Left);
+
+            table[i + 1][j + 1] = max2((I::zero(), Direction::Stop), max2(d00, max2(d01, d10)));
+        }
+    }
+
+    table
+}
+
+/// Returns the maximum of two scores, defaulting to the first input.
+fn max2<I: IInt>(a: (I, Direction), b: (I, Direction)) -> (I, Direction) {
+    if a.0 >= b.0 {
+        a
+    } else {
+        b
+    }
+}
+
+/// Finds the cell with the highest score in the table.
+///
+/// Ties are broken in favor of the first such cell in row-major order.
+///
+/// # Returns
+///
+/// The highest score and the `[row, column]` of its cell.
+pub fn best_cell<I: IInt>(table: &[Vec<(I, Direction)>]) -> (I, [usize; 2]) {
+    let mut best = (I::zero(), [0, 0]);
+    for (i, row) in table.iter().enumerate() {
+        for (j, &(score, _)) in row.iter().enumerate() {
+            if score > best.0 {
+                best = (score, [i, j]);
+            }
+        }
+    }
+    best
+}
+
+/// Traces back through the Smith-Waterman table from the given cell to get
+/// the best local alignment of two sequences ending there.
+///
+/// The trace back stops at the first cell whose direction is `Stop`, so that
+/// the alignment never starts with a zero-scoring prefix.
+///
+/// # Arguments
+///
+/// * `table`: The Smith-Waterman table.
+/// * `[x, y]`: The two sequences to align.
+/// * `[row_i, col_i]`: The cell at which the alignment ends.
+///
+/// # Returns
+///
+/// A tuple of the two aligned sequences and the `[row, column]` of the cell
+/// at which the alignment starts.
+pub fn trace_back<I: IInt>(
+    table: &[Vec<(I, Direction)>],
+    [x, y]: [&[char]; 2],
+    [mut row_i, mut col_i]: [usize; 2],
+) -> (String, String, [usize; 2]) {
+    let (mut aligned_x, mut aligned_y) = (Vec::new(), Vec::new());
+
+    loop {
+        match table[row_i][col_i].1 {
+            Direction::Stop => break,
+            Direction::Diagonal => {
+                aligned_x.push(x[col_i - 1]);
+                aligned_y.push(y[row_i - 1]);
+                row_i -= 1;
+                col_i -= 1;
+            }
+            Direction::Left => {
+                aligned_x.push(x[col_i - 1]);
+                aligned_y.push('-');
+                col_i -= 1;
+            }
+            Direction::Up => {
+                aligned_x.push('-');
+                aligned_y.push(y[row_i - 1]);
+                row_i -= 1;
+            }
+        }
+    }
+
+    let aligned_x = aligned_x.into_iter().rev().collect();
+    let aligned_y = aligned_y.into_iter().rev().collect();
+
+    (aligned_x, aligned_y, [row_i, col_i])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_back() {
+        // The example from Wikipedia.
+        let x = "TGTTACGG".chars().collect::<Vec<_>>();
+        let y = "GGTTGACTA".chars().collect::<Vec<_>>();
+        let table = compute_table::<i16>(&x, &y, Scores::new(3, -3, -2));
+
+        let (score, end) = best_cell(&table);
+        assert_eq!(score, 13);
+        assert_eq!(end, [7, 6]);
+
+        let (aligned_x, aligned_y, start) = trace_back(&table, [&x, &y], end);
+        assert_eq!(aligned_x, "GTT-AC");
+        assert_eq!(aligned_y, "GTTGAC");
+        assert_eq!(start, [1, 1]);
+    }
+}
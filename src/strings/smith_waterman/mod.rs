@@ -0,0 +1,230 @@
+//! Smith-Waterman local alignment between two strings.
+//!
+//! This implementation should not be considered stable.
+
+mod helpers;
+
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+
+use crate::number::IInt;
+
+use helpers::{best_cell, compute_table, trace_back};
+
+/// Scores to use in the Smith-Waterman local alignment.
+///
+/// Unlike the [`Penalties`](super::Penalties) used for the distances, these
+/// are rewards which are maximized, thus the genericity over signed integers.
+/// For a meaningful local alignment, the score for a match should be positive
+/// while those for a mismatch and a gap should be negative.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Scores<I: IInt> {
+    /// Score for a match.
+    pub(crate) match_: I,
+    /// Score for a mis-match.
+    pub(crate) mismatch: I,
+    /// Score for a gap.
+    pub(crate) gap: I,
+}
+
+impl<I: IInt> Default for Scores<I> {
+    fn default() -> Self {
+        Self {
+            match_: I::one() + I::one(),
+            mismatch: I::zero() - I::one(),
+            gap: I::zero() - I::one(),
+        }
+    }
+}
+
+impl<I: IInt> Scores<I> {
+    /// Create a set of scores to use for the Smith-Waterman alignment.
+    pub const fn new(match_: I, mismatch: I, gap: I) -> Self {
+        Self {
+            match_,
+            mismatch,
+            gap,
+        }
+    }
+}
+
+/// The best local alignment of two strings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalAlignment<I: IInt> {
+    /// The score of the alignment.
+    pub score: I,
+    /// The aligned substring of the first string, with `-` for gaps.
+    pub aligned_x: String,
+    /// The aligned substring of the second string, with `-` for gaps.
+    pub aligned_y: String,
+    /// The byte offsets of the aligned substring in the first string.
+    pub x_range: Range<usize>,
+    /// The byte offsets of the aligned substring in the second string.
+    pub y_range: Range<usize>,
+}
+
+/// Use a custom set of scores to create a function that calculates the
+/// Smith-Waterman local alignment score between two strings.
+///
+/// * [Wikipedia](https://en.wikipedia.org/wiki/Smith%E2%80%93Waterman_algorithm)
+///
+/// # Arguments:
+///
+/// * `scores`: The scores to use in the generated function.
+///
+/// # Returns:
+///
+/// A function with the same signature as `sw_score`.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::smith_waterman::{sw_score_custom, Scores};
+///
+/// let metric = sw_score_custom(Scores::new(3, -3, -2));
+///
+/// let score: i16 = metric("TGTTACGG", "GGTTGACTA");
+/// assert_eq!(score, 13);
+/// ```
+pub fn sw_score_custom<I: IInt>(scores: Scores<I>) -> impl Fn(&str, &str) -> I {
+    move |x: &str, y: &str| {
+        let x = x.chars().collect::<Vec<_>>();
+        let y = y.chars().collect::<Vec<_>>();
+        best_cell(&compute_table(&x, &y, scores)).0
+    }
+}
+
+/// Calculate the score of the best local alignment between two strings using
+/// the Smith-Waterman table.
+///
+/// We use the default [`Scores`] of `2` for a match and `-1` for a mismatch
+/// or a gap. The score is never negative, since the empty alignment scores
+/// zero. This is a similarity rather than a distance, and it is computed over
+/// Unicode scalar values.
+///
+/// * [Wikipedia](https://en.wikipedia.org/wiki/Smith%E2%80%93Waterman_algorithm)
+///
+/// # Arguments:
+///
+/// * `x`: unaligned sequence represented as a `String`
+/// * `y`: unaligned sequence represented as a `String`
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::smith_waterman::sw_score;
+///
+/// let score: i16 = sw_score("NAJIBEATSPEPPERS", "NAJIBPEPPERSEATS");
+/// assert_eq!(score, 20);
+/// ```
+#[must_use]
+pub fn sw_score<I: IInt>(x: &str, y: &str) -> I {
+    sw_score_custom(Scores::default())(x, y)
+}
+
+/// Use a custom set of scores to create a function that calculates the best
+/// local alignment between two strings.
+///
+/// # Arguments:
+///
+/// * `scores`: The scores to use in the generated function.
+///
+/// # Returns:
+///
+/// A function with the same signature as `sw_alignment`.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::smith_waterman::{sw_alignment_custom, Scores};
+///
+/// let x = "TGTTACGG";
+/// let y = "GGTTGACTA";
+/// let alignment = sw_alignment_custom(Scores::<i16>::new(3, -3, -2))(x, y);
+///
+/// assert_eq!(alignment.score, 13);
+/// assert_eq!(alignment.aligned_x, "GTT-AC");
+/// assert_eq!(alignment.aligned_y, "GTTGAC");
+/// assert_eq!(&x[alignment.x_range], "GTTAC");
+/// assert_eq!(&y[alignment.y_range], "GTTGAC");
+/// ```
+pub fn sw_alignment_custom<I: IInt>(scores: Scores<I>) -> impl Fn(&str, &str) -> LocalAlignment<I> {
+    move |x: &str, y: &str| {
+        let x_chars = x.chars().collect::<Vec<_>>();
+        let y_chars = y.chars().collect::<Vec<_>>();
+
+        let table = compute_table(&x_chars, &y_chars, scores);
+        let (score, [row_end, col_end]) = best_cell(&table);
+        let (aligned_x, aligned_y, [row_start, col_start]) =
+            trace_back(&table, [&x_chars, &y_chars], [row_end, col_end]);
+
+        LocalAlignment {
+            score,
+            aligned_x,
+            aligned_y,
+            x_range: byte_offset(x, col_start)..byte_offset(x, col_end),
+            y_range: byte_offset(y, row_start)..byte_offset(y, row_end),
+        }
+    }
+}
+
+/// Determine the best local alignment between two strings, i.e. the pair of
+/// substrings with the highest Smith-Waterman score, along with their aligned
+/// forms and their byte offsets in the inputs.
+///
+/// The alignment is found by tracing back from the first cell with the
+/// highest score in row-major order, preferring a diagonal step, then a gap
+/// in `x`, then a gap in `y`, and stopping as soon as the score drops to
+/// zero. For now, in cases where there exist several best local alignments, we
+/// only return this one. If no pair of characters matches, the alignment is
+/// empty and both ranges are `0..0`.
+///
+/// # Arguments:
+///
+/// * `x`: an unaligned sequence.
+/// * `y`: an unaligned sequence.
+///
+/// # Examples
+///
+/// ```
+/// use distances::strings::smith_waterman::sw_alignment;
+///
+/// let x = "NAJIBEATSPEPPERS";
+/// let y = "EATSPEPPER";
+/// let alignment = sw_alignment::<i16>(x, y);
+///
+/// assert_eq!(alignment.score, 20);
+/// assert_eq!(alignment.aligned_x, "EATSPEPPER");
+/// assert_eq!(alignment.x_range, 5..15);
+/// assert_eq!(alignment.y_range, 0..10);
+/// ```
+#[must_use]
+pub fn sw_alignment<I: IInt>(x: &str, y: &str) -> LocalAlignment<I> {
+    sw_alignment_custom(Scores::default())(x, y)
+}
+
+/// Returns the byte offset of the character at index `i` in `s`, or the
+/// length of `s` if `i` is past its last character.
+fn byte_offset(s: &str, i: usize) -> usize {
+    s.char_indices()
+        .nth(i)
+        .map_or(s.len(), |(offset, _)| offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sw_alignment, sw_score};
+
+    #[test]
+    fn score() {
+        let s: i8 = sw_score("NOTGUILTY", "NOTGUILTY");
+        assert_eq!(s, 18);
+
+        let s: i8 = sw_score("ABC", "XYZ");
+        assert_eq!(s, 0);
+
+        let alignment = sw_alignment::<i8>("ABC", "XYZ");
+        assert!(alignment.aligned_x.is_empty() && alignment.aligned_y.is_empty());
+        assert_eq!(alignment.x_range, 0..0);
+    }
+}
@@ -0,0 +1,369 @@
+//! Substitution matrices for scoring the alignment of two characters.
+
+mod matrices;
+
+use alloc::{vec, vec::Vec};
+
+use crate::number::UInt;
+
+use super::PenaltyScheme;
+
+/// The reasons a substitution matrix could not be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatrixParseError {
+    /// The text has no header line with the alphabet.
+    MissingHeader,
+    /// The label of a row or column, on the given line, is not a single ASCII
+    /// character or is repeated.
+    InvalidLabel(usize),
+    /// The row on the given line does not have one score per column.
+    RowLength(usize),
+    /// A score on the given line is not an integer.
+    InvalidScore(usize),
+    /// The matrix has no row for the given character of the alphabet.
+    MissingRow(char),
+    /// The scores for aligning the two characters differ in both orders.
+    Asymmetric(char, char),
+}
+
+impl core::fmt::Display for MatrixParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingHeader => write!(f, "the matrix has no header"),
+            Self::InvalidLabel(line) => write!(f, "invalid label on line {line}"),
+            Self::RowLength(line) => write!(f, "wrong number of scores on line {line}"),
+            Self::InvalidScore(line) => write!(f, "invalid score on line {line}"),
+            Self::MissingRow(c) => write!(f, "the matrix has no row for {c:?}"),
+            Self::Asymmetric(a, b) => write!(f, "the scores of {a:?} and {b:?} are asymmetric"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MatrixParseError {}
+
+/// A symmetric matrix of scores for aligning the characters of an alphabet,
+/// where higher scores are given to more similar characters.
+///
+/// Matrices can be parsed from the standard NCBI text format with
+/// [`SubstitutionMatrix::from_ncbi`], and the BLOSUM62, BLOSUM45 and PAM250
+/// matrices for proteins, and a matrix for DNA that distinguishes transitions
+/// from transversions, are built in. To use a matrix with the Levenshtein and
+/// Needleman-Wunsch distances, convert it into [`MatrixPenalties`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubstitutionMatrix {
+    /// The characters of the alphabet, in the order of the rows and columns.
+    alphabet: Vec<char>,
+    /// The scores, stored row by row.
+    scores: Vec<i32>,
+}
+
+impl SubstitutionMatrix {
+    /// Parses a substitution matrix in the NCBI text format.
+    ///
+    /// Lines starting with `#` and blank lines are ignored. The first
+    /// remaining line lists the alphabet as whitespace-separated characters,
+    /// and each following line holds a character of the alphabet followed by
+    /// its scores against every character of the alphabet, in the order of the
+    /// header.
+    ///
+    /// # Errors
+    ///
+    /// * [`MatrixParseError`] if the text is not a well-formed, symmetric
+    ///   matrix over an alphabet of ASCII characters. Line numbers start at 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use distances::strings::SubstitutionMatrix;
+    ///
+    /// let text = "
+    /// ## A tiny matrix.
+    ///    A  B
+    /// A  2 -1
+    /// B -1  3
+    /// ";
+    /// let matrix = SubstitutionMatrix::from_ncbi(text).unwrap();
+    ///
+    /// assert_eq!(matrix.score('A', 'B'), Some(-1));
+    /// assert_eq!(matrix.score('B', 'B'), Some(3));
+    /// assert_eq!(matrix.score('A', 'C'), None);
+    /// ```
+    pub fn from_ncbi(text: &str) -> Result<Self, MatrixParseError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let (header_line, header) = lines.next().ok_or(MatrixParseError::MissingHeader)?;
+        let mut alphabet = Vec::new();
+        for label in header.split_whitespace() {
+            let c = parse_label(label, header_line)?;
+            if alphabet.contains(&c) {
+                return Err(MatrixParseError::InvalidLabel(header_line));
+            }
+            alphabet.push(c);
+        }
+
+        let n = alphabet.len();
+        let mut rows = vec![None; n];
+        for (line_number, line) in lines {
+            let mut fields = line.split_whitespace();
+            let label = fields.next().unwrap_or_default();
+            let c = parse_label(label, line_number)?;
+            let i = alphabet
+                .iter()
+                .position(|&a| a == c)
+                .filter(|&i| rows[i].is_none())
+                .ok_or(MatrixParseError::InvalidLabel(line_number))?;
+
+            let row = fields
+                .map(|field| {
+                    field
+                        .parse::<i32>()
+                        .map_err(|_| MatrixParseError::InvalidScore(line_number))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if row.len() != n {
+                return Err(MatrixParseError::RowLength(line_number));
+            }
+            rows[i] = Some(row);
+        }
+
+        let mut scores = Vec::with_capacity(n * n);
+        for (row, &c) in rows.into_iter().zip(alphabet.iter()) {
+            scores.extend(row.ok_or(MatrixParseError::MissingRow(c))?);
+        }
+
+        for i in 0..n {
+            for j in (i + 1)..n {
+                if scores[i * n + j] != scores[j * n + i] {
+                    return Err(MatrixParseError::Asymmetric(alphabet[i], alphabet[j]));
+                }
+            }
+        }
+
+        Ok(Self { alphabet, scores })
+    }
+
+    /// The BLOSUM62 matrix for proteins, with the IUPAC amino acid codes,
+    /// the ambiguity codes `B`, `Z` and `X`, and `*` for a stop codon.
+    ///
+    /// # References
+    ///
+    /// * [Amino acid substitution matrices from protein blocks](https://doi.org/10.1073/pnas.89.22.10915)
+    #[must_use]
+    pub fn blosum62() -> Self {
+        Self::built_in(matrices::BLOSUM62)
+    }
+
+    /// The BLOSUM45 matrix for proteins, over the same alphabet as
+    /// [`SubstitutionMatrix::blosum62`]. It is suited to more distantly
+    /// related sequences.
+    ///
+    /// # References
+    ///
+    /// * [Amino acid substitution matrices from protein blocks](https://doi.org/10.1073/pnas.89.22.10915)
+    #[must_use]
+    pub fn blosum45() -> Self {
+        Self::built_in(matrices::BLOSUM45)
+    }
+
+    /// The PAM250 matrix for proteins, over the same alphabet as
+    /// [`SubstitutionMatrix::blosum62`].
+    ///
+    /// # References
+    ///
+    /// * [A model of evolutionary change in proteins](https://en.wikipedia.org/wiki/Point_accepted_mutation)
+    #[must_use]
+    pub fn pam250() -> Self {
+        Self::built_in(matrices::PAM250)
+    }
+
+    /// A matrix for DNA over `A`, `C`, `G` and `T`, which scores a match as
+    /// `1`, a transition (`A` and `G`, or `C` and `T`) as `-1` and a
+    /// transversion as `-2`.
+    #[must_use]
+    pub fn dna_transition_transversion() -> Self {
+        Self::built_in(matrices::DNA_TRANSITION_TRANSVERSION)
+    }
+
+    /// Parses one of the built-in matrices.
+    fn built_in(text: &str) -> Self {
+        Self::from_ncbi(text).unwrap_or_else(|_| unreachable!("The built-in matrices are valid."))
+    }
+
+    /// Returns the characters of the alphabet, in the order of the rows.
+    #[must_use]
+    pub fn alphabet(&self) -> &[char] {
+        &self.alphabet
+    }
+
+    /// Returns the score for aligning `a` with `b`, or `None` if either is not
+    /// in the alphabet.
+    #[must_use]
+    pub fn score(&self, a: char, b: char) -> Option<i32> {
+        let i = self.alphabet.iter().position(|&c| c == a)?;
+        let j = self.alphabet.iter().position(|&c| c == b)?;
+        Some(self.scores[i * self.alphabet.len() + j])
+    }
+}
+
+/// Parses the label of a row or column of a matrix.
+fn parse_label(label: &str, line: usize) -> Result<char, MatrixParseError> {
+    let mut chars = label.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii() => Ok(c),
+        _ => Err(MatrixParseError::InvalidLabel(line)),
+    }
+}
+
+/// Penalties derived from a [`SubstitutionMatrix`], to use in the
+/// Levenshtein and Needleman-Wunsch distance calculations.
+///
+/// The scores of a substitution matrix are similarities, so we convert the
+/// score `s(a, b)` of aligning `a` with `b` into the penalty
+/// `max(s(a, a), s(b, b)) - s(a, b)`, which is zero when `a` and `b` are equal
+/// and is never negative for the built-in matrices. Negative penalties, which
+/// a custom matrix might give, are raised to zero. Aligning two equal
+/// characters outside the alphabet costs nothing, and aligning any other pair
+/// involving such a character costs the largest penalty in the matrix.
+///
+/// Even with these penalties, the resulting distances need not obey the
+/// triangle inequality.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatrixPenalties<U: UInt> {
+    /// The index of each ASCII character in the alphabet, or `None`.
+    index: [Option<u8>; 128],
+    /// The number of characters in the alphabet.
+    size: usize,
+    /// The penalties for aligning two characters, stored row by row.
+    penalties: Vec<U>,
+    /// The penalty for aligning a character outside the alphabet.
+    unknown: U,
+    /// Penalty for a gap.
+    gap: U,
+}
+
+impl<U: UInt> MatrixPenalties<U> {
+    /// Create a set of penalties from a substitution matrix and a gap penalty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use distances::strings::{levenshtein_custom, MatrixPenalties, SubstitutionMatrix};
+    ///
+    /// let penalties = MatrixPenalties::new(&SubstitutionMatrix::blosum62(), 6);
+    /// let metric = levenshtein_custom(penalties);
+    ///
+    /// // Isoleucine is more similar to valine than to glycine.
+    /// let d_iv: u16 = metric("MKIL", "MKVL");
+    /// let d_ig: u16 = metric("MKIL", "MKGL");
+    /// assert_eq!(d_iv, 1);
+    /// assert_eq!(d_ig, 10);
+    /// ```
+    #[must_use]
+    pub fn new(matrix: &SubstitutionMatrix, gap: U) -> Self {
+        let size = matrix.alphabet.len();
+        let diagonal = (0..size)
+            .map(|i| matrix.scores[i * size + i])
+            .collect::<Vec<_>>();
+
+        let penalties = matrix
+            .scores
+            .iter()
+            .enumerate()
+            .map(|(k, &s)| {
+                let (i, j) = (k / size, k % size);
+                let penalty = diagonal[i].max(diagonal[j]).saturating_sub(s).max(0);
+                U::from(penalty)
+            })
+            .collect::<Vec<_>>();
+        let unknown = penalties.iter().copied().max().unwrap_or_else(U::zero);
+
+        let mut index = [None; 128];
+        for (i, &c) in matrix.alphabet.iter().enumerate() {
+            // The alphabet has at most 128 distinct ASCII characters.
+            index[c as usize] = u8::try_from(i).ok();
+        }
+
+        Self {
+            index,
+            size,
+            penalties,
+            unknown,
+            gap,
+        }
+    }
+
+    /// Returns the index of `c` in the alphabet, if it is in the alphabet.
+    fn index_of(&self, c: char) -> Option<usize> {
+        if c.is_ascii() {
+            self.index[c as usize].map(usize::from)
+        } else {
+            None
+        }
+    }
+}
+
+impl<U: UInt> PenaltyScheme<U> for MatrixPenalties<U> {
+    fn substitution(&self, &a: &char, &b: &char) -> U {
+        match (self.index_of(a), self.index_of(b)) {
+            (Some(i), Some(j)) => self.penalties[i * self.size + j],
+            _ if a == b => U::zero(),
+            _ => self.unknown,
+        }
+    }
+
+    fn gap(&self) -> U {
+        self.gap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MatrixParseError, SubstitutionMatrix};
+
+    #[test]
+    fn built_in() {
+        let blosum62 = SubstitutionMatrix::blosum62();
+        assert_eq!(blosum62.alphabet().len(), 24);
+        assert_eq!(blosum62.score('W', 'W'), Some(11));
+        assert_eq!(blosum62.score('A', '*'), Some(-4));
+
+        assert_eq!(SubstitutionMatrix::blosum45().score('C', 'C'), Some(12));
+        assert_eq!(SubstitutionMatrix::pam250().score('W', 'W'), Some(17));
+
+        let dna = SubstitutionMatrix::dna_transition_transversion();
+        assert_eq!(dna.score('A', 'G'), Some(-1));
+        assert_eq!(dna.score('A', 'T'), Some(-2));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let parse = SubstitutionMatrix::from_ncbi;
+        assert_eq!(
+            parse("# only a comment"),
+            Err(MatrixParseError::MissingHeader)
+        );
+        assert_eq!(parse("A AB\nA 1 0"), Err(MatrixParseError::InvalidLabel(1)));
+        assert_eq!(
+            parse("A B\nA 1\nB 0 1"),
+            Err(MatrixParseError::RowLength(2))
+        );
+        assert_eq!(
+            parse("A B\nA 1 x\nB 0 1"),
+            Err(MatrixParseError::InvalidScore(2))
+        );
+        assert_eq!(parse("A B\nA 1 0"), Err(MatrixParseError::MissingRow('B')));
+        assert_eq!(
+            parse("A B\nA 1 0\nA 1 0"),
+            Err(MatrixParseError::InvalidLabel(3))
+        );
+        assert_eq!(
+            parse("A B\nA 1 0\nB -1 1"),
+            Err(MatrixParseError::Asymmetric('A', 'B'))
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! Dynamic Time Warping.
+
+use alloc::{vec, vec::Vec};
+
+use crate::Number;
+
+use super::{banded_dp, fill_row, minimum, neighbors, Window};
+
+/// The absolute difference between two elements, converted to the output type.
+///
+/// This is the default pointwise cost used by [`dtw`].
+///
+/// # Arguments
+///
+/// * `a`: An element of the first time series.
+/// * `b`: An element of the second time series.
+pub fn absolute_difference<T: Number, U: Number>(a: T, b: T) -> U {
+    U::from(a.abs_diff(b))
+}
+
+/// Computes the Dynamic Time Warping distance between two time series, using
+/// the absolute difference as the pointwise cost and no window.
+///
+/// This is a shorthand for `dtw_custom(absolute_difference, None)`. See
+/// [`dtw_custom`] for details.
+///
+/// # Arguments
+///
+/// * `x`: A time series.
+/// * `y`: A time series.
+///
+/// # Examples
+///
+/// ```
+/// use distances::time_series::dtw;
+///
+/// let x: Vec<i32> = vec![0, 1, 2, 3];
+/// let y: Vec<i32> = vec![0, 0, 1, 1, 2, 3, 3];
+///
+/// let distance: u32 = dtw(&x, &y);
+/// assert_eq!(distance, 0);
+///
+/// let y: Vec<i32> = vec![1, 2, 3];
+///
+/// let distance: u32 = dtw(&x, &y);
+/// assert_eq!(distance, 1);
+/// ```
+///
+/// # References
+///
+/// * [Dynamic time warping](https://en.wikipedia.org/wiki/Dynamic_time_warping)
+pub fn dtw<T: Number, U: Number>(x: &[T], y: &[T]) -> U {
+    dtw_custom(absolute_difference, None)(x, y)
+}
+
+/// Creates a function that computes the Dynamic Time Warping distance between
+/// two time series.
+///
+/// The distance is the smallest total pointwise cost over all warping paths,
+/// i.e. monotone alignments of the two series that match their first elements
+/// and their last elements. The computation takes `O(n * m)` time, or less
+/// with a window, and keeps only two rows of `O(min(n, m))` length in memory.
+///
+/// DTW is not a metric, since it does not obey the triangle inequality. If
+/// either series is empty, the distance is zero.
+///
+/// # Arguments
+///
+/// * `cost`: The pointwise cost of aligning `x[i]` with `y[j]`.
+/// * `window`: An optional global constraint on the warping path.
+///
+/// # Examples
+///
+/// ```
+/// use distances::time_series::{dtw_custom, Window};
+///
+/// let x: Vec<f32> = vec![0.0, 1.0, 0.0, 0.0, 0.0];
+/// let y: Vec<f32> = vec![0.0, 0.0, 0.0, 1.0, 0.0];
+///
+/// let square = |a: f32, b: f32| (a - b) * (a - b);
+///
+/// let metric = dtw_custom(square, None);
+/// let distance: f32 = metric(&x, &y);
+/// assert!(distance.abs() <= f32::EPSILON);
+///
+/// // The band forbids warping far enough to align the two spikes.
+/// let metric = dtw_custom(square, Some(Window::SakoeChiba(1)));
+/// let distance: f32 = metric(&x, &y);
+/// assert!((distance - 2.0).abs() <= f32::EPSILON);
+/// ```
+pub fn dtw_custom<T: Number, U: Number, F: Fn(T, T) -> U>(
+    cost: F,
+    window: Option<Window>,
+) -> impl Fn(&[T], &[T]) -> U {
+    move |x: &[T], y: &[T]| {
+        if x.is_empty() || y.is_empty() {
+            return U::zero();
+        }
+
+        let grid = Grid::new(x, y, &cost);
+        banded_dp(grid.rows, grid.cols, window, |i, j, neighbors| {
+            let cost = grid.cell_cost(i, j);
+            minimum(neighbors).map_or(cost, |best| best + cost)
+        })
+    }
+}
+
+/// Creates a function that computes the Dynamic Time Warping distance between
+/// two time series along with an optimal warping path.
+///
+/// The path is a list of index pairs `(i, j)`, aligning `x[i]` with `y[j]`,
+/// from `(0, 0)` to `(x.len() - 1, y.len() - 1)`. When several paths are
+/// optimal, diagonal steps are preferred during the traceback. Unlike
+/// [`dtw_custom`], this keeps the whole `O(n * m)` cost matrix in memory.
+///
+/// If either series is empty, the distance is zero and the path is empty.
+///
+/// # Arguments
+///
+/// * `cost`: The pointwise cost of aligning `x[i]` with `y[j]`.
+/// * `window`: An optional global constraint on the warping path.
+///
+/// # Examples
+///
+/// ```
+/// use distances::time_series::{absolute_difference, dtw_with_path};
+///
+/// let x: Vec<u8> = vec![1, 2, 3];
+/// let y: Vec<u8> = vec![1, 2, 2, 3];
+///
+/// let metric = dtw_with_path(absolute_difference, None);
+/// let (distance, path): (u8, _) = metric(&x, &y);
+///
+/// assert_eq!(distance, 0);
+/// assert_eq!(path, vec![(0, 0), (1, 1), (1, 2), (2, 3)]);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn dtw_with_path<T: Number, U: Number, F: Fn(T, T) -> U>(
+    cost: F,
+    window: Option<Window>,
+) -> impl Fn(&[T], &[T]) -> (U, Vec<(usize, usize)>) {
+    move |x: &[T], y: &[T]| {
+        if x.is_empty() || y.is_empty() {
+            return (U::zero(), Vec::new());
+        }
+
+        let grid = Grid::new(x, y, &cost);
+        let mut matrix = vec![vec![U::zero(); grid.cols]; grid.rows];
+        let mut ranges = Vec::with_capacity(grid.rows);
+
+        let mut prev = vec![U::zero(); grid.cols];
+        let mut prev_range = (0, 0);
+        for (i, row) in matrix.iter_mut().enumerate() {
+            prev_range = fill_row(
+                i,
+                grid.rows,
+                grid.cols,
+                window,
+                &prev,
+                prev_range,
+                row,
+                &mut |i, j, neighbors| {
+                    let cost = grid.cell_cost(i, j);
+                    minimum(neighbors).map_or(cost, |best| best + cost)
+                },
+            );
+            ranges.push(prev_range);
+            prev.clone_from(row);
+        }
+
+        let distance = matrix[grid.rows - 1][grid.cols - 1];
+
+        let mut path = Vec::with_capacity(grid.rows + grid.cols);
+        let (mut i, mut j) = (grid.rows - 1, grid.cols - 1);
+        path.push((i, j));
+        while i > 0 || j > 0 {
+            let prev_range = if i > 0 { ranges[i - 1] } else { (0, 0) };
+            let step = neighbors(i, j, ranges[i].0, prev_range)
+                .into_iter()
+                .flatten()
+                .fold(None, |best: Option<(usize, usize)>, (a, b)| match best {
+                    Some((c, d)) if matrix[c][d] <= matrix[a][b] => best,
+                    _ => Some((a, b)),
+                });
+            // The first cell is always reachable, so some predecessor exists.
+            if let Some((a, b)) = step {
+                (i, j) = (a, b);
+                path.push((i, j));
+            } else {
+                break;
+            }
+        }
+        path.reverse();
+
+        if grid.transposed {
+            for (a, b) in &mut path {
+                core::mem::swap(a, b);
+            }
+        }
+
+        (distance, path)
+    }
+}
+
+/// The alignment matrix of two non-empty time series.
+///
+/// The longer series indexes the rows so that each row has the length of the
+/// shorter series.
+struct Grid<'a, T: Number, U: Number, F: Fn(T, T) -> U> {
+    /// The series indexing the rows.
+    row_series: &'a [T],
+    /// The series indexing the columns.
+    col_series: &'a [T],
+    /// Whether `x` indexes the columns, i.e. whether the grid is transposed.
+    transposed: bool,
+    /// The number of rows.
+    rows: usize,
+    /// The number of columns.
+    cols: usize,
+    /// The pointwise cost.
+    cost: &'a F,
+}
+
+impl<'a, T: Number, U: Number, F: Fn(T, T) -> U> Grid<'a, T, U, F> {
+    /// Creates the alignment matrix of `x` and `y`.
+    const fn new(x: &'a [T], y: &'a [T], cost: &'a F) -> Self {
+        let (row_series, col_series, transposed) = if x.len() >= y.len() {
+            (x, y, false)
+        } else {
+            (y, x, true)
+        };
+        Self {
+            row_series,
+            col_series,
+            transposed,
+            rows: row_series.len(),
+            cols: col_series.len(),
+            cost,
+        }
+    }
+
+    /// Returns the pointwise cost of the cell at row `i` and column `j`.
+    fn cell_cost(&self, i: usize, j: usize) -> U {
+        let (a, b) = (self.row_series[i], self.col_series[j]);
+        if self.transposed {
+            (self.cost)(b, a)
+        } else {
+            (self.cost)(a, b)
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Edit distance with Real Penalty.
+
+use crate::{number::Float, Number};
+
+use super::{banded_dp, minimum, Window};
+
+/// Creates a function that computes the Edit distance with Real Penalty (ERP)
+/// between two time series.
+///
+/// ERP aligns the two series like an edit distance, where matching `x[i]` with
+/// `y[j]` costs `|x[i] - y[j]|` and a gap opposite an element `v` costs
+/// `|v - g|`. Unlike DTW, ERP is a metric when no window is used.
+///
+/// The computation takes `O(n * m)` time, or less with a window, and keeps
+/// only two rows of `O(min(n, m))` length in memory. The window applies to the
+/// lengths of the prefixes being aligned, i.e. to the rows and columns of the
+/// `(n + 1) x (m + 1)` alignment matrix. An empty series is at distance
+/// `sum(|v - g|)` from a series with elements `v`.
+///
+/// # Arguments
+///
+/// * `g`: The gap value.
+/// * `window`: An optional global constraint on which elements may be aligned.
+///
+/// # Examples
+///
+/// ```
+/// use distances::time_series::erp;
+///
+/// let x: Vec<i32> = vec![1, 2, 3];
+/// let y: Vec<i32> = vec![1, 3];
+///
+/// // The `2` in `x` is matched with a gap.
+/// let distance: f32 = erp(0, None)(&x, &y);
+/// assert!((distance - 2.0).abs() <= f32::EPSILON);
+///
+/// let distance: f32 = erp(2, None)(&x, &y);
+/// assert!(distance.abs() <= f32::EPSILON);
+/// ```
+///
+/// # References
+///
+/// * [On the marriage of Lp-norms and edit distance](https://doi.org/10.1016/B978-012088469-8.50070-X)
+pub fn erp<T: Number, U: Float>(g: T, window: Option<Window>) -> impl Fn(&[T], &[T]) -> U {
+    move |x: &[T], y: &[T]| {
+        // ERP is symmetric, so the longer series may index the rows.
+        let (x, y) = if x.len() >= y.len() { (x, y) } else { (y, x) };
+        let g = U::from(g);
+
+        banded_dp(
+            x.len() + 1,
+            y.len() + 1,
+            window,
+            |i, j, [diagonal, vertical, horizontal]| {
+                if i == 0 && j == 0 {
+                    return U::zero();
+                }
+
+                let matched = diagonal.map(|d| d + U::from(x[i - 1]).abs_diff(U::from(y[j - 1])));
+                let gap_y = vertical.map(|d| d + U::from(x[i - 1]).abs_diff(g));
+                let gap_x = horizontal.map(|d| d + U::from(y[j - 1]).abs_diff(g));
+
+                minimum([matched, gap_y, gap_x]).unwrap_or_else(U::zero)
+            },
+        )
+    }
+}
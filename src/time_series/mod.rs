@@ -0,0 +1,198 @@
+//! Distance functions for time series.
+//!
+//! Each time series is a slice of `Number`s sampled at regular intervals. The
+//! two series being compared need not have the same length.
+//!
+//! # Windows
+//! The elastic distances in this module can be constrained to a [`Window`]
+//! around the diagonal of the alignment matrix. This speeds up the computation
+//! and prevents pathological alignments that map a short stretch of one series
+//! onto a long stretch of the other.
+
+mod dtw;
+mod erp;
+mod msm;
+
+use alloc::vec;
+
+use crate::Number;
+
+pub use dtw::{absolute_difference, dtw, dtw_custom, dtw_with_path};
+pub use erp::erp;
+pub use msm::{msm, try_msm};
+
+/// A global constraint on which elements of two time series may be aligned.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Window {
+    /// The Sakoe-Chiba band of the given radius.
+    ///
+    /// Elements `x[i]` and `y[j]` may only be aligned if `|i - j|` is at most
+    /// the radius. The radius is widened to the difference in the lengths of
+    /// the two series when it is smaller, so that the series can always be
+    /// aligned end to end.
+    SakoeChiba(usize),
+    /// The Itakura parallelogram with the given maximum slope.
+    ///
+    /// The warping path may not be steeper than the slope, nor shallower than
+    /// its inverse, relative to the diagonal from the first elements to the
+    /// last elements. The cells nearest the diagonal are always allowed, so a
+    /// slope less than or equal to `1` restricts the path to the diagonal.
+    Itakura(f64),
+}
+
+impl Window {
+    /// Returns the inclusive range of columns allowed in row `i` of a matrix
+    /// with `rows` rows and `cols` columns.
+    ///
+    /// Callers must ensure that `rows >= cols >= 1`. Under this condition, the
+    /// ranges of consecutive rows always overlap or touch, so the last cell is
+    /// reachable from the first.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub(crate) fn columns(self, i: usize, rows: usize, cols: usize) -> (usize, usize) {
+        let (last_row, last_col) = (rows - 1, cols - 1);
+        match self {
+            Self::SakoeChiba(radius) => {
+                let radius = radius.max(rows - cols);
+                (i.saturating_sub(radius), (i + radius).min(last_col))
+            }
+            Self::Itakura(slope) => {
+                if last_row == 0 {
+                    return (0, 0);
+                }
+
+                // The cells on either side of the diagonal.
+                let diag_lo = i * last_col / last_row;
+                let diag_hi = (i * last_col).div_ceil(last_row);
+
+                let slope = slope.max(1.0);
+                let u = i as f64 / last_row as f64;
+                let lower = f64::max(u / slope, 1.0 - slope * (1.0 - u)).max(0.0);
+                let upper = f64::min(u * slope, 1.0 - (1.0 - u) / slope).min(1.0);
+
+                // Small tolerances guard against rounding in the products.
+                let lower = lower * last_col as f64 - 1e-9;
+                let upper = upper * last_col as f64 + 1e-9;
+                let lo = if lower <= 0.0 {
+                    0
+                } else {
+                    let floor = lower as usize;
+                    if (floor as f64) < lower {
+                        floor + 1
+                    } else {
+                        floor
+                    }
+                };
+                let hi = if upper <= 0.0 { 0 } else { upper as usize };
+
+                (lo.min(diag_lo), hi.max(diag_hi).min(last_col))
+            }
+        }
+    }
+}
+
+/// Returns the inclusive range of columns allowed in row `i` of a matrix with
+/// `rows` rows and `cols` columns under an optional window.
+///
+/// Callers must ensure that `rows >= cols >= 1`.
+pub(crate) fn columns(
+    window: Option<Window>,
+    i: usize,
+    rows: usize,
+    cols: usize,
+) -> (usize, usize) {
+    window.map_or((0, cols - 1), |w| w.columns(i, rows, cols))
+}
+
+/// Returns the diagonal, vertical and horizontal neighbors preceding the cell
+/// at row `i` and column `j`, in that order, if they lie inside the allowed
+/// ranges. `lo` is the first allowed column of row `i` and `prev_range` is the
+/// allowed range of row `i - 1`.
+pub(crate) const fn neighbors(
+    i: usize,
+    j: usize,
+    lo: usize,
+    prev_range: (usize, usize),
+) -> [Option<(usize, usize)>; 3] {
+    let (prev_lo, prev_hi) = prev_range;
+    let diagonal = if i > 0 && j > 0 && prev_lo < j && j <= prev_hi + 1 {
+        Some((i - 1, j - 1))
+    } else {
+        None
+    };
+    let vertical = if i > 0 && prev_lo <= j && j <= prev_hi {
+        Some((i - 1, j))
+    } else {
+        None
+    };
+    let horizontal = if j > lo { Some((i, j - 1)) } else { None };
+    [diagonal, vertical, horizontal]
+}
+
+/// Returns the smallest of the given values, preferring the earliest one in
+/// case of ties, or `None` if there are no values.
+pub(crate) fn minimum<U: Number>(values: [Option<U>; 3]) -> Option<U> {
+    values
+        .into_iter()
+        .flatten()
+        .fold(None, |best, v| match best {
+            Some(b) if b <= v => best,
+            _ => Some(v),
+        })
+}
+
+/// Fills the allowed cells of row `i` of a dynamic programming matrix with
+/// `rows` rows and `cols` columns, given the previous row and its allowed
+/// range, and returns the allowed range of row `i`.
+///
+/// The value of each cell is computed by `cell` from its row, its column and
+/// the values of its diagonal, vertical and horizontal neighbors, which are
+/// `None` outside the allowed ranges. Cells outside the allowed ranges are
+/// never read, so they may hold stale values.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn fill_row<U: Number>(
+    i: usize,
+    rows: usize,
+    cols: usize,
+    window: Option<Window>,
+    prev: &[U],
+    prev_range: (usize, usize),
+    cur: &mut [U],
+    cell: &mut impl FnMut(usize, usize, [Option<U>; 3]) -> U,
+) -> (usize, usize) {
+    let (lo, hi) = columns(window, i, rows, cols);
+    for j in lo..=hi {
+        let values = neighbors(i, j, lo, prev_range)
+            .map(|n| n.map(|(a, b)| if a == i { cur[b] } else { prev[b] }));
+        cur[j] = cell(i, j, values);
+    }
+    (lo, hi)
+}
+
+/// Computes the value of the last cell of a dynamic programming matrix with
+/// `rows` rows and `cols` columns, keeping only two rows in memory.
+///
+/// See [`fill_row`] for the meaning of `cell`. Callers must ensure that
+/// `rows >= cols >= 1`.
+pub(crate) fn banded_dp<U: Number>(
+    rows: usize,
+    cols: usize,
+    window: Option<Window>,
+    mut cell: impl FnMut(usize, usize, [Option<U>; 3]) -> U,
+) -> U {
+    let mut prev = vec![U::zero(); cols];
+    let mut cur = vec![U::zero(); cols];
+    let mut prev_range = (0, 0);
+
+    for i in 0..rows {
+        prev_range = fill_row(
+            i, rows, cols, window, &prev, prev_range, &mut cur, &mut cell,
+        );
+        core::mem::swap(&mut prev, &mut cur);
+    }
+
+    prev[cols - 1]
+}
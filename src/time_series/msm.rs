@@ -0,0 +1,128 @@
+//! Move-Split-Merge distance.
+
+use crate::{number::Float, DistanceError, Number};
+
+use super::{banded_dp, minimum, Window};
+
+/// Creates a function that computes the Move-Split-Merge (MSM) distance
+/// between two time series.
+///
+/// MSM transforms one series into the other with three operations: a *move*
+/// changes the value of an element at a cost of the absolute change, a *split*
+/// duplicates an element and a *merge* removes an element equal to its
+/// neighbor. Splits and merges cost `c`, plus the distance to the nearer of
+/// the two neighboring values when the element does not lie between them.
+///
+/// Unlike DTW, MSM is a metric on non-empty series when `c` is positive and no
+/// window is used. The computation takes `O(n * m)` time, or less with a
+/// window, and keeps only two rows of `O(min(n, m))` length in memory.
+///
+/// MSM has no operation that creates a series from nothing or deletes the last
+/// element, so it is not defined when either series is empty. This function
+/// then returns zero, which breaks the metric properties. Use [`try_msm`] to
+/// get an error instead.
+///
+/// # Arguments
+///
+/// * `c`: The cost of a split or merge operation.
+/// * `window`: An optional global constraint on which elements may be aligned.
+///
+/// # Examples
+///
+/// ```
+/// use distances::time_series::msm;
+///
+/// let metric = msm(0.5, None);
+///
+/// let x: Vec<i32> = vec![1, 2, 3];
+/// let y: Vec<i32> = vec![1, 2, 2, 3];
+///
+/// // Split the `2` in `x`.
+/// let distance: f64 = metric(&x, &y);
+/// assert!((distance - 0.5).abs() <= f64::EPSILON);
+///
+/// let y: Vec<i32> = vec![1, 5, 3];
+///
+/// // Move the `2` in `x` to `5`.
+/// let distance: f64 = metric(&x, &y);
+/// assert!((distance - 3.0).abs() <= f64::EPSILON);
+/// ```
+///
+/// # References
+///
+/// * [The Move-Split-Merge Metric for Time Series](https://doi.org/10.1109/TKDE.2012.88)
+pub fn msm<T: Number, U: Float>(c: U, window: Option<Window>) -> impl Fn(&[T], &[T]) -> U {
+    move |x: &[T], y: &[T]| {
+        if x.is_empty() || y.is_empty() {
+            return U::zero();
+        }
+
+        // MSM is symmetric, so the longer series may index the rows.
+        let (x, y) = if x.len() >= y.len() { (x, y) } else { (y, x) };
+
+        banded_dp(
+            x.len(),
+            y.len(),
+            window,
+            |i, j, [diagonal, vertical, horizontal]| {
+                let (x_i, y_j) = (U::from(x[i]), U::from(y[j]));
+                if i == 0 && j == 0 {
+                    return x_i.abs_diff(y_j);
+                }
+
+                let moved = diagonal.map(|d| d + x_i.abs_diff(y_j));
+                let split_x = vertical.map(|d| d + split_merge(x_i, U::from(x[i - 1]), y_j, c));
+                let split_y = horizontal.map(|d| d + split_merge(y_j, U::from(y[j - 1]), x_i, c));
+
+                minimum([moved, split_x, split_y]).unwrap_or_else(U::zero)
+            },
+        )
+    }
+}
+
+/// Checked version of [`msm`].
+///
+/// The generated function returns [`DistanceError::EmptyInput`] if either
+/// series is empty and [`DistanceError::NanInput`] if either series contains a
+/// `NaN`.
+///
+/// # Examples
+///
+/// ```
+/// use distances::{time_series::try_msm, DistanceError};
+///
+/// let metric = try_msm(0.5, None);
+///
+/// let distance: Result<f64, _> = metric(&[1, 2, 3], &[1, 2, 2, 3]);
+/// assert_eq!(distance, Ok(0.5));
+///
+/// let distance: Result<f64, _> = metric(&[1, 2, 3], &[]);
+/// assert_eq!(distance, Err(DistanceError::EmptyInput));
+/// ```
+pub fn try_msm<T: Number, U: Float>(
+    c: U,
+    window: Option<Window>,
+) -> impl Fn(&[T], &[T]) -> Result<U, DistanceError> {
+    let metric = msm(c, window);
+    move |x: &[T], y: &[T]| {
+        if x.is_empty() || y.is_empty() {
+            Err(DistanceError::EmptyInput)
+        } else if x.iter().chain(y).any(|&v| U::from(v).is_nan()) {
+            Err(DistanceError::NanInput)
+        } else {
+            Ok(metric(x, y))
+        }
+    }
+}
+
+/// The cost of a split or merge that introduces `new` next to `prev`, while
+/// `other` is the element of the other series it is aligned with.
+fn split_merge<U: Float>(new: U, prev: U, other: U, c: U) -> U {
+    if (prev <= new && new <= other) || (prev >= new && new >= other) {
+        c
+    } else {
+        let d_prev = new.abs_diff(prev);
+        let d_other = new.abs_diff(other);
+        c + if d_prev < d_other { d_prev } else { d_other }
+    }
+}
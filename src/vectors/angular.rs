@@ -15,6 +15,10 @@ use crate::{
 /// The cosine similarity is defined as the dot product of the two vectors
 /// divided by the product of their magnitudes.
 ///
+/// If either vector has zero norm, or if their dot product is negative, the
+/// distance is taken to be `1.0`. Use [`try_cosine`](super::try_cosine) for
+/// the exact distance, which lies in `[0, 2]`.
+///
 /// See the [`crate::vectors`] module documentation for information on this
 /// function's potentially unexpected behaviors
 ///
@@ -40,20 +44,110 @@ use crate::{
 ///
 /// * [Cosine similarity](https://en.wikipedia.org/wiki/Cosine_similarity)
 pub fn cosine<T: Number, U: Float>(x: &[T], y: &[T]) -> U {
+    let [xx, yy, xy] = cosine_products::<_, U>(x, y);
+
+    if xx < U::epsilon() || yy < U::epsilon() || xy < U::epsilon() {
+        U::one()
+    } else {
+        let d = U::one() - xy * (xx * yy).inv_sqrt();
+        if d < U::epsilon() {
+            U::zero()
+        } else {
+            d
+        }
+    }
+}
+
+/// Computes the squared norms of two vectors and their dot product, in the
+/// `Accumulator` type of `T`, and returns them as `[xx, yy, xy]`.
+pub fn cosine_products<T: Number, U: Float>(x: &[T], y: &[T]) -> [U; 3] {
     let [xx, yy, xy] = x
         .iter()
-        .zip(y.iter())
-        .fold([T::zero(); 3], |[xx, yy, xy], (&a, &b)| {
+        .map(|&a| a.as_accumulator())
+        .zip(y.iter().map(|&b| b.as_accumulator()))
+        .fold([T::Accumulator::zero(); 3], |[xx, yy, xy], (a, b)| {
             [a.mul_add(a, xx), b.mul_add(b, yy), a.mul_add(b, xy)]
         });
-    let [xx, yy, xy] = [U::from(xx), U::from(yy), U::from(xy)];
+    [U::from(xx), U::from(yy), U::from(xy)]
+}
 
-    if xx < U::epsilon() || yy < U::epsilon() || xy < U::epsilon() {
+/// Computes the Pearson correlation distance between two vectors.
+///
+/// The Pearson distance is defined as `1.0 - r` where `r` is the Pearson
+/// correlation coefficient, i.e. the cosine similarity of the two vectors after
+/// centering each of them on its mean. The distance lies in `[0, 2]`.
+///
+/// The means, variances and covariance are computed in a single pass with
+/// Welford's algorithm, which avoids the catastrophic cancellation of the
+/// textbook `E[xy] - E[x]E[y]` formula.
+///
+/// If either vector is constant, `r` is undefined and, as with zero-norm
+/// vectors in [`cosine`], we return `1.0`.
+///
+/// See the [`crate::vectors`] module documentation for information on this
+/// function's potentially unexpected behaviors
+///
+/// # Arguments
+///
+/// * `x`: A slice of numbers.
+/// * `y`: A slice of numbers.
+///
+/// # Examples
+///
+/// ```
+/// use distances::vectors::pearson;
+///
+/// let x: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0];
+/// let y: Vec<f64> = vec![2.0, 4.0, 6.0, 8.0];
+///
+/// let distance: f64 = pearson(&x, &y);
+/// assert!(distance.abs() <= f64::EPSILON);
+///
+/// let y: Vec<f64> = vec![8.0, 6.0, 4.0, 2.0];
+///
+/// let distance: f64 = pearson(&x, &y);
+/// assert!((distance - 2.0).abs() <= f64::EPSILON);
+///
+/// let y: Vec<f64> = vec![5.0, 5.0, 5.0, 5.0];
+///
+/// let distance: f64 = pearson(&x, &y);
+/// assert!((distance - 1.0).abs() <= f64::EPSILON);
+/// ```
+///
+/// # References
+///
+/// * [Pearson correlation coefficient](https://en.wikipedia.org/wiki/Pearson_correlation_coefficient)
+/// * [Welford's online algorithm](https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Welford's_online_algorithm)
+pub fn pearson<T: Number, U: Float>(x: &[T], y: &[T]) -> U {
+    let [_, _, _, var_x, var_y, cov] = x
+        .iter()
+        .map(|&a| U::from(a))
+        .zip(y.iter().map(|&b| U::from(b)))
+        .fold(
+            [U::zero(); 6],
+            |[n, mean_x, mean_y, var_x, var_y, cov], (a, b)| {
+                let n = n + U::one();
+                let (dx, dy) = (a - mean_x, b - mean_y);
+                let (mean_x, mean_y) = (mean_x + dx / n, mean_y + dy / n);
+                [
+                    n,
+                    mean_x,
+                    mean_y,
+                    dx.mul_add(a - mean_x, var_x),
+                    dy.mul_add(b - mean_y, var_y),
+                    dx.mul_add(b - mean_y, cov),
+                ]
+            },
+        );
+
+    if var_x == U::zero() || var_y == U::zero() {
         U::one()
     } else {
-        let d = U::one() - xy * (xx * yy).inv_sqrt();
+        let d = U::one() - cov * (var_x * var_y).inv_sqrt();
         if d < U::epsilon() {
             U::zero()
+        } else if d > U::one() + U::one() {
+            U::one() + U::one()
         } else {
             d
         }
@@ -104,7 +198,9 @@ pub fn hamming<T: Int, U: UInt>(x: &[T], y: &[T]) -> U {
 ///
 /// The Canberra distance is defined as the sum of the absolute differences
 /// between the elements of the two vectors divided by the sum of the absolute
-/// values of the elements of the two vectors.
+/// values of the elements of the two vectors. Following the usual convention,
+/// the positions at which both elements are zero add nothing to the sum,
+/// rather than `0 / 0`.
 ///
 /// See the [`crate::vectors`] module documentation for information on this
 /// function's potentially unexpected behaviors
@@ -125,6 +221,10 @@ pub fn hamming<T: Int, U: UInt>(x: &[T], y: &[T]) -> U {
 /// let distance: f32 = canberra(&x, &y);
 ///
 /// assert!((distance - 143.0 / 105.0).abs() <= f32::EPSILON);
+///
+/// let distance: f32 = canberra(&[0.0, 1.0], &[0.0, 2.0]);
+///
+/// assert!((distance - 1.0 / 3.0).abs() <= f32::EPSILON);
 /// ```
 ///
 /// # References
@@ -134,6 +234,7 @@ pub fn canberra<T: Number, U: Float>(x: &[T], y: &[T]) -> U {
     x.iter()
         .map(|&v| U::from(v))
         .zip(y.iter().map(|&v| U::from(v)))
+        .filter(|&(a, b)| a != U::zero() || b != U::zero())
         .map(|(a, b)| a.abs_diff(b) / (a.abs() + b.abs()))
         .fold(U::zero(), |acc, v| acc + v)
 }
@@ -169,14 +270,19 @@ pub fn canberra<T: Number, U: Float>(x: &[T], y: &[T]) -> U {
 ///
 /// * [Bray-Curtis dissimilarity](https://en.wikipedia.org/wiki/Bray%E2%80%93Curtis_dissimilarity)
 pub fn bray_curtis<T: UInt, U: Float>(x: &[T], y: &[T]) -> U {
-    let [sum_x, sum_y, sum_min] = x
-        .iter()
-        .zip(y.iter())
-        .fold([T::zero(); 3], |[sum_a, sum_b, sum_min], (&a, &b)| {
-            [a + sum_a, b + sum_b, min(a, b) + sum_min]
-        });
+    let [sum_x, sum_y, sum_min] = x.iter().zip(y.iter()).fold(
+        [T::Accumulator::zero(); 3],
+        |[sum_a, sum_b, sum_min], (&a, &b)| {
+            [
+                sum_a + a.as_accumulator(),
+                sum_b + b.as_accumulator(),
+                sum_min + min(a, b).as_accumulator(),
+            ]
+        },
+    );
 
-    if sum_x == T::zero() || sum_y == T::zero() || sum_min == T::zero() {
+    let zero = T::Accumulator::zero();
+    if sum_x == zero || sum_y == zero || sum_min == zero {
         U::one()
     } else {
         let (numerator, denominator) = (sum_min + sum_min, sum_x + sum_y);
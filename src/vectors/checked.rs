@@ -0,0 +1,217 @@
+//! Checked versions of the vector distance functions.
+
+use crate::{
+    number::{Float, Int, UInt},
+    DistanceError, Number,
+};
+
+use super::{
+    angular::cosine_products,
+    bray_curtis, canberra, chebyshev, euclidean, euclidean_sq, hamming, l3_norm, l4_norm,
+    manhattan, minkowski, minkowski_p, pearson,
+    utils::{check_vectors, is_constant, is_zero},
+};
+
+/// Checked version of [`euclidean`].
+///
+/// # Errors
+///
+/// See the [`crate::vectors`] module documentation.
+///
+/// # Examples
+///
+/// ```
+/// use distances::{vectors::try_euclidean, DistanceError};
+///
+/// let x: Vec<f64> = vec![1.0, 2.0, 3.0];
+/// let y: Vec<f64> = vec![4.0, 5.0, 6.0];
+///
+/// let distance: f64 = try_euclidean(&x, &y).unwrap();
+/// assert!((distance - (27.0_f64).sqrt()).abs() <= f64::EPSILON);
+///
+/// let distance: Result<f64, _> = try_euclidean(&x, &y[..2]);
+/// assert_eq!(distance, Err(DistanceError::LengthMismatch(3, 2)));
+///
+/// let distance: Result<f64, _> = try_euclidean(&x, &[1.0, f64::NAN, 3.0]);
+/// assert_eq!(distance, Err(DistanceError::NanInput));
+/// ```
+pub fn try_euclidean<T: Number, U: Float>(x: &[T], y: &[T]) -> Result<U, DistanceError> {
+    check_vectors(x, y)?;
+    Ok(euclidean(x, y))
+}
+
+/// Checked version of [`euclidean_sq`].
+///
+/// # Errors
+///
+/// See the [`crate::vectors`] module documentation.
+pub fn try_euclidean_sq<T: Number, U: Number>(x: &[T], y: &[T]) -> Result<U, DistanceError> {
+    check_vectors(x, y)?;
+    Ok(euclidean_sq(x, y))
+}
+
+/// Checked version of [`manhattan`].
+///
+/// # Errors
+///
+/// See the [`crate::vectors`] module documentation.
+pub fn try_manhattan<T: Number, U: Number>(x: &[T], y: &[T]) -> Result<U, DistanceError> {
+    check_vectors(x, y)?;
+    Ok(manhattan(x, y))
+}
+
+/// Checked version of [`l3_norm`].
+///
+/// # Errors
+///
+/// See the [`crate::vectors`] module documentation.
+pub fn try_l3_norm<T: Number, U: Float>(x: &[T], y: &[T]) -> Result<U, DistanceError> {
+    check_vectors(x, y)?;
+    Ok(l3_norm(x, y))
+}
+
+/// Checked version of [`l4_norm`].
+///
+/// # Errors
+///
+/// See the [`crate::vectors`] module documentation.
+pub fn try_l4_norm<T: Number, U: Float>(x: &[T], y: &[T]) -> Result<U, DistanceError> {
+    check_vectors(x, y)?;
+    Ok(l4_norm(x, y))
+}
+
+/// Checked version of [`chebyshev`].
+///
+/// # Errors
+///
+/// See the [`crate::vectors`] module documentation.
+pub fn try_chebyshev<T: Number, U: Number>(x: &[T], y: &[T]) -> Result<U, DistanceError> {
+    check_vectors(x, y)?;
+    Ok(chebyshev(x, y))
+}
+
+/// Checked version of [`minkowski_p`].
+///
+/// The generated function returns a [`DistanceError`] in the cases described
+/// in the [`crate::vectors`] module documentation.
+pub fn try_minkowski_p<T: Number, U: Float>(
+    p: i32,
+) -> impl Fn(&[T], &[T]) -> Result<U, DistanceError> {
+    let metric = minkowski_p(p);
+    move |x: &[T], y: &[T]| {
+        check_vectors(x, y)?;
+        Ok(metric(x, y))
+    }
+}
+
+/// Checked version of [`minkowski`].
+///
+/// The generated function returns a [`DistanceError`] in the cases described
+/// in the [`crate::vectors`] module documentation.
+pub fn try_minkowski<T: Number, U: Float>(
+    p: i32,
+) -> impl Fn(&[T], &[T]) -> Result<U, DistanceError> {
+    let metric = minkowski(p);
+    move |x: &[T], y: &[T]| {
+        check_vectors(x, y)?;
+        Ok(metric(x, y))
+    }
+}
+
+/// Checked version of [`cosine`](super::cosine).
+///
+/// Unlike `cosine`, this does not round negative dot products up to a
+/// distance of `1.0`, so that the distance lies in `[0, 2]`, e.g. it is `2.0`
+/// for anti-parallel vectors.
+///
+/// # Errors
+///
+/// See the [`crate::vectors`] module documentation. In addition,
+/// returns [`DistanceError::ZeroNorm`] if either vector has only zeros.
+///
+/// # Examples
+///
+/// ```
+/// use distances::{vectors::try_cosine, DistanceError};
+///
+/// let x: Vec<f32> = vec![1.0, 0.0, 0.0];
+/// let y: Vec<f32> = vec![0.0, 0.0, 0.0];
+///
+/// let distance: Result<f32, _> = try_cosine(&x, &y);
+/// assert_eq!(distance, Err(DistanceError::ZeroNorm));
+///
+/// let y: Vec<f32> = vec![-1.0, 0.0, 0.0];
+///
+/// let distance: Result<f32, _> = try_cosine(&x, &y);
+/// assert_eq!(distance, Ok(2.0));
+/// ```
+pub fn try_cosine<T: Number, U: Float>(x: &[T], y: &[T]) -> Result<U, DistanceError> {
+    check_vectors(x, y)?;
+    if is_zero(x) || is_zero(y) {
+        return Err(DistanceError::ZeroNorm);
+    }
+
+    let [xx, yy, xy] = cosine_products::<_, U>(x, y);
+    let d = U::one() - xy * (xx * yy).inv_sqrt();
+
+    // Rounding may take the distance just outside of its range.
+    let two = U::one() + U::one();
+    Ok(if d < U::zero() {
+        U::zero()
+    } else if d > two {
+        two
+    } else {
+        d
+    })
+}
+
+/// Checked version of [`pearson`].
+///
+/// # Errors
+///
+/// See the [`crate::vectors`] module documentation. In addition,
+/// returns [`DistanceError::ZeroNorm`] if either vector is constant, i.e. has
+/// zero norm after centering.
+pub fn try_pearson<T: Number, U: Float>(x: &[T], y: &[T]) -> Result<U, DistanceError> {
+    check_vectors(x, y)?;
+    if is_constant(x) || is_constant(y) {
+        Err(DistanceError::ZeroNorm)
+    } else {
+        Ok(pearson(x, y))
+    }
+}
+
+/// Checked version of [`hamming`].
+///
+/// # Errors
+///
+/// See the [`crate::vectors`] module documentation.
+pub fn try_hamming<T: Int, U: UInt>(x: &[T], y: &[T]) -> Result<U, DistanceError> {
+    check_vectors(x, y)?;
+    Ok(hamming(x, y))
+}
+
+/// Checked version of [`canberra`].
+///
+/// # Errors
+///
+/// See the [`crate::vectors`] module documentation.
+pub fn try_canberra<T: Number, U: Float>(x: &[T], y: &[T]) -> Result<U, DistanceError> {
+    check_vectors(x, y)?;
+    Ok(canberra(x, y))
+}
+
+/// Checked version of [`bray_curtis`].
+///
+/// # Errors
+///
+/// See the [`crate::vectors`] module documentation. In addition,
+/// returns [`DistanceError::ZeroNorm`] if both vectors have only zeros.
+pub fn try_bray_curtis<T: UInt, U: Float>(x: &[T], y: &[T]) -> Result<U, DistanceError> {
+    check_vectors(x, y)?;
+    if is_zero(x) && is_zero(y) {
+        Err(DistanceError::ZeroNorm)
+    } else {
+        Ok(bray_curtis(x, y))
+    }
+}
@@ -4,7 +4,7 @@ use core::cmp::Ordering;
 
 use crate::{number::Float, Number};
 
-use super::utils::abs_diff_iter;
+use super::utils::acc_abs_diff_iter;
 
 /// Euclidean distance between two vectors.
 ///
@@ -63,7 +63,11 @@ pub fn euclidean<T: Number, U: Float>(x: &[T], y: &[T]) -> U {
 /// assert!((distance - 27.0).abs() <= f64::EPSILON);
 /// ```
 pub fn euclidean_sq<T: Number, U: Number>(x: &[T], y: &[T]) -> U {
-    abs_diff_iter(x, y).map(U::from).map(|v| v * v).sum()
+    U::from(
+        acc_abs_diff_iter(x, y)
+            .map(|v| v * v)
+            .sum::<T::Accumulator>(),
+    )
 }
 
 /// Manhattan distance between two vectors.
@@ -91,9 +95,16 @@ pub fn euclidean_sq<T: Number, U: Number>(x: &[T], y: &[T]) -> U {
 /// let distance: f64 = manhattan(&x, &y);
 ///
 /// assert!((distance - 9.0).abs() <= f64::EPSILON);
+///
+/// let x: Vec<u8> = vec![0, 0, 0];
+/// let y: Vec<u8> = vec![255, 255, 255];
+///
+/// let distance: u32 = manhattan(&x, &y);
+///
+/// assert_eq!(distance, 765);
 /// ```
-pub fn manhattan<T: Number>(x: &[T], y: &[T]) -> T {
-    abs_diff_iter(x, y).sum()
+pub fn manhattan<T: Number, U: Number>(x: &[T], y: &[T]) -> U {
+    U::from(acc_abs_diff_iter(x, y).sum::<T::Accumulator>())
 }
 
 /// L3-norm between two vectors.
@@ -122,7 +133,7 @@ pub fn manhattan<T: Number>(x: &[T], y: &[T]) -> T {
 /// assert!((distance - (81.0_f64).cbrt()).abs() <= f64::EPSILON);
 /// ```
 pub fn l3_norm<T: Number, U: Float>(x: &[T], y: &[T]) -> U {
-    abs_diff_iter(x, y)
+    acc_abs_diff_iter(x, y)
         .map(U::from)
         .map(|v| v * v * v)
         .sum::<U>()
@@ -155,7 +166,7 @@ pub fn l3_norm<T: Number, U: Float>(x: &[T], y: &[T]) -> U {
 /// assert!((distance - (243.0_f64).sqrt().sqrt()).abs() <= f64::EPSILON);
 /// ```
 pub fn l4_norm<T: Number, U: Float>(x: &[T], y: &[T]) -> U {
-    abs_diff_iter(x, y)
+    acc_abs_diff_iter(x, y)
         .map(U::from)
         .map(|v| v * v)
         .map(|v| v * v)
@@ -188,11 +199,20 @@ pub fn l4_norm<T: Number, U: Float>(x: &[T], y: &[T]) -> U {
 /// let distance: f64 = chebyshev(&x, &y);
 ///
 /// assert!((distance - 5.0).abs() <= f64::EPSILON);
+///
+/// let x: Vec<i8> = vec![-128, 0, 0];
+/// let y: Vec<i8> = vec![127, 0, 0];
+///
+/// let distance: u8 = chebyshev(&x, &y);
+///
+/// assert_eq!(distance, 255);
 /// ```
-pub fn chebyshev<T: Number>(x: &[T], y: &[T]) -> T {
-    abs_diff_iter(x, y)
-        .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Less))
-        .unwrap_or_else(T::zero)
+pub fn chebyshev<T: Number, U: Number>(x: &[T], y: &[T]) -> U {
+    U::from(
+        acc_abs_diff_iter(x, y)
+            .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Less))
+            .unwrap_or_else(T::Accumulator::zero),
+    )
 }
 
 /// General (Lp-norm)^p between two vectors.
@@ -222,7 +242,12 @@ pub fn chebyshev<T: Number>(x: &[T], y: &[T]) -> T {
 /// assert!((distance - 81.0).abs() <= 1e-12);
 /// ```
 pub fn minkowski_p<T: Number, U: Float>(p: i32) -> impl Fn(&[T], &[T]) -> U {
-    move |x: &[T], y: &[T]| abs_diff_iter(x, y).map(U::from).map(|v| v.powi(p)).sum()
+    move |x: &[T], y: &[T]| {
+        acc_abs_diff_iter(x, y)
+            .map(U::from)
+            .map(|v| v.powi(p))
+            .sum()
+    }
 }
 
 /// General Lp-norm between two vectors.
@@ -5,12 +5,34 @@
 //! dimensionality may give unexpected results. Specifically, when one vector is
 //! shorter than the other, elements in the longer vector past the end of the
 //! shorter vector will be ignored.
+//!
+//! # Checked variants
+//! Each function has a `try_*` variant that, instead of guessing a value,
+//! returns a [`crate::DistanceError`] when:
+//!
+//! * either vector is empty,
+//! * the vectors have different lengths,
+//! * either vector contains a `NaN`, or
+//! * the distance is undefined because of a zero-norm vector (only for
+//!   `try_cosine`, `try_pearson` and `try_bray_curtis`).
+//!
+//! # Accumulation
+//! Sums, products and differences of elements are accumulated in the
+//! `Accumulator` type of the input `Number` before being converted to the
+//! output type. This means that, e.g., `manhattan::<u8, u32>` and
+//! `cosine::<i8, f32>` do not overflow on quantized vectors.
 
 mod angular;
+mod checked;
 mod lp_norms;
 pub(crate) mod utils;
 
-pub use angular::{bray_curtis, canberra, cosine, hamming};
+pub use angular::{bray_curtis, canberra, cosine, hamming, pearson};
+pub use checked::{
+    try_bray_curtis, try_canberra, try_chebyshev, try_cosine, try_euclidean, try_euclidean_sq,
+    try_hamming, try_l3_norm, try_l4_norm, try_manhattan, try_minkowski, try_minkowski_p,
+    try_pearson,
+};
 pub use lp_norms::{
     chebyshev, euclidean, euclidean_sq, l3_norm, l4_norm, manhattan, minkowski, minkowski_p,
 };
@@ -1,13 +1,42 @@
 //! Utility functions for vector based distance calculations.
 
-use crate::Number;
+use crate::{DistanceError, Number};
+
+/// Checks that two vectors are non-empty, of equal length and free of `NaN`s.
+pub fn check_vectors<T: Number>(x: &[T], y: &[T]) -> Result<(), DistanceError> {
+    if x.is_empty() || y.is_empty() {
+        Err(DistanceError::EmptyInput)
+    } else if x.len() != y.len() {
+        Err(DistanceError::LengthMismatch(x.len(), y.len()))
+    } else if x.iter().chain(y.iter()).any(|v| v.is_nan()) {
+        Err(DistanceError::NanInput)
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns whether every element of a vector is equal to the first.
+pub fn is_constant<T: Number>(x: &[T]) -> bool {
+    x.iter().all(|&v| v == x[0])
+}
+
+/// Returns whether every element of a vector is zero.
+pub fn is_zero<T: Number>(x: &[T]) -> bool {
+    x.iter().all(|&v| v == T::zero())
+}
 
 /// An iterator over the absolute differences between the corresponding elements
-/// of two vectors. If one vector is longer than the other, only the elements
-/// from the start with a matching element in the shorter vector will be
-/// included.
-pub fn abs_diff_iter<'a, T: Number>(x: &'a [T], y: &'a [T]) -> impl Iterator<Item = T> + 'a {
-    x.iter().zip(y.iter()).map(|(a, &b)| a.abs_diff(b))
+/// of two vectors, computed in the `Accumulator` type of `T` so that signed
+/// inputs cannot overflow. If one vector is longer than the other, only the
+/// elements from the start with a matching element in the shorter vector will
+/// be included.
+pub fn acc_abs_diff_iter<'a, T: Number>(
+    x: &'a [T],
+    y: &'a [T],
+) -> impl Iterator<Item = T::Accumulator> + 'a {
+    x.iter()
+        .zip(y.iter())
+        .map(|(&a, &b)| a.as_accumulator().abs_diff(b.as_accumulator()))
 }
 
 // /// An iterator over the differences between the corresponding elements of two
@@ -0,0 +1,158 @@
+use test_case::test_case;
+
+use distances::{
+    sets::try_jaccard,
+    strings::{try_hamming, try_levenshtein, try_nw_distance},
+    vectors::{
+        try_bray_curtis, try_canberra, try_chebyshev, try_cosine, try_euclidean, try_euclidean_sq,
+        try_hamming as try_hamming_vec, try_l3_norm, try_l4_norm, try_manhattan, try_minkowski,
+        try_minkowski_p, try_pearson,
+    },
+    DistanceError,
+};
+
+type Metric = fn(&[f32], &[f32]) -> Result<f32, DistanceError>;
+
+#[test_case(try_euclidean; "euclidean")]
+#[test_case(try_euclidean_sq; "euclidean_sq")]
+#[test_case(try_manhattan; "manhattan")]
+#[test_case(try_l3_norm; "l3_norm")]
+#[test_case(try_l4_norm; "l4_norm")]
+#[test_case(try_chebyshev; "chebyshev")]
+#[test_case(try_canberra; "canberra")]
+#[test_case(try_cosine; "cosine")]
+fn vectors_f32(metric: Metric) {
+    let x = [1.0, 2.0, 3.0];
+    let y = [4.0, 5.0, 6.0];
+
+    assert!(metric(&x, &y).is_ok());
+    assert_eq!(
+        metric(&x, &y[..2]),
+        Err(DistanceError::LengthMismatch(3, 2))
+    );
+    assert_eq!(
+        metric(&x[..1], &y),
+        Err(DistanceError::LengthMismatch(1, 3))
+    );
+    assert_eq!(metric(&[], &y), Err(DistanceError::EmptyInput));
+    assert_eq!(metric(&x, &[]), Err(DistanceError::EmptyInput));
+    assert_eq!(
+        metric(&x, &[4.0, f32::NAN, 6.0]),
+        Err(DistanceError::NanInput)
+    );
+}
+
+#[test]
+fn minkowski() {
+    let x = [1.0_f64, 2.0, 3.0];
+    let y = [4.0_f64, 5.0, 6.0];
+
+    let metric = try_minkowski::<_, f64>(3);
+    assert!((metric(&x, &y).unwrap() - 81_f64.cbrt()).abs() <= 1e-12);
+    assert_eq!(
+        metric(&x, &y[1..]),
+        Err(DistanceError::LengthMismatch(3, 2))
+    );
+
+    let metric = try_minkowski_p::<_, f64>(3);
+    assert!((metric(&x, &y).unwrap() - 81.0).abs() <= 1e-12);
+    assert_eq!(metric(&[], &[]), Err(DistanceError::EmptyInput));
+}
+
+#[test]
+fn zero_norm() {
+    let zeros = [0_u32; 3];
+    let ones = [1_u32; 3];
+
+    assert_eq!(
+        try_cosine::<_, f32>(&zeros, &ones),
+        Err(DistanceError::ZeroNorm)
+    );
+    assert_eq!(
+        try_cosine::<_, f32>(&ones, &zeros),
+        Err(DistanceError::ZeroNorm)
+    );
+
+    assert_eq!(try_bray_curtis::<_, f32>(&zeros, &ones), Ok(1.0));
+    assert_eq!(
+        try_bray_curtis::<_, f32>(&zeros, &zeros),
+        Err(DistanceError::ZeroNorm)
+    );
+
+    assert_eq!(try_hamming_vec::<_, u32>(&zeros, &ones), Ok(3));
+}
+
+#[test]
+fn cosine_negative_dot_product() {
+    assert_eq!(try_cosine::<_, f64>(&[1.0, 0.0], &[-1.0, 0.0]), Ok(2.0));
+    assert_eq!(try_cosine::<_, f64>(&[1.0, 0.0], &[0.0, -1.0]), Ok(1.0));
+
+    let d = try_cosine::<_, f64>(&[1.0, 0.0], &[-1.0, 1.0]).unwrap();
+    assert!((d - (1.0 + 0.5_f64.sqrt())).abs() <= 1e-12);
+
+    let x = vec![i8::MAX; 1_000];
+    let y = vec![i8::MIN; 1_000];
+    let d = try_cosine::<_, f64>(&x, &y).unwrap();
+    assert!((d - 2.0).abs() <= f64::EPSILON);
+    assert_eq!(try_cosine::<_, f64>(&y, &y), Ok(0.0));
+
+    let d = try_cosine::<_, f32>(&[-3_i8, 4], &[3, -4]).unwrap();
+    assert!((d - 2.0).abs() <= f32::EPSILON);
+}
+
+#[test]
+fn canberra_zeros() {
+    let d = try_canberra::<_, f64>(&[0.0, 1.0], &[0.0, 2.0]).unwrap();
+    assert!((d - 1.0 / 3.0).abs() <= f64::EPSILON);
+    assert_eq!(try_canberra::<_, f64>(&[0.0, 0.0], &[0.0, 0.0]), Ok(0.0));
+}
+
+#[test]
+fn sets() {
+    let x = [1_u32, 2, 3];
+    let y = [2_u32, 3, 4];
+
+    assert_eq!(try_jaccard::<_, f32>(&x, &y), Ok(0.5));
+    assert_eq!(
+        try_jaccard::<_, f32>(&x, &[]),
+        Err(DistanceError::EmptyInput)
+    );
+}
+
+#[test]
+fn strings() {
+    assert_eq!(try_levenshtein::<u16>("NAJIB", "NAJIBE"), Ok(1));
+    assert_eq!(
+        try_levenshtein::<u16>("", "NAJIBE"),
+        Err(DistanceError::EmptyInput)
+    );
+
+    assert_eq!(try_nw_distance::<u16>("NAJIB", "NAJIBE"), Ok(1));
+    assert_eq!(
+        try_nw_distance::<u16>("NAJIB", ""),
+        Err(DistanceError::EmptyInput)
+    );
+
+    assert_eq!(try_hamming::<u16>("NAJIB", "NAJIC"), Ok(1));
+    assert_eq!(
+        try_hamming::<u16>("NAJIB", "NAJIBE"),
+        Err(DistanceError::LengthMismatch(5, 6))
+    );
+    assert_eq!(
+        try_hamming::<u16>("ÉTÉ", "ETE!"),
+        Err(DistanceError::LengthMismatch(3, 4))
+    );
+}
+
+#[test]
+fn pearson() {
+    let x = [1_i32, 2, 3];
+    let y = [4_i32, 4, 4];
+
+    assert_eq!(try_pearson::<_, f64>(&x, &x), Ok(0.0));
+    assert_eq!(try_pearson::<_, f64>(&x, &y), Err(DistanceError::ZeroNorm));
+    assert_eq!(
+        try_pearson::<_, f64>(&x, &y[1..]),
+        Err(DistanceError::LengthMismatch(3, 2))
+    );
+}
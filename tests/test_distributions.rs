@@ -0,0 +1,225 @@
+use symagen::random_data;
+use test_case::test_case;
+
+use distances::{
+    distributions::{
+        bhattacharyya, hellinger, jensen_shannon, jensen_shannon_divergence, kl_divergence,
+        normalize, normalized, total_variation, try_wasserstein_histograms, wasserstein,
+        wasserstein_histograms, wasserstein_samples,
+    },
+    DistanceError,
+};
+
+fn random_distributions(cardinality: usize, dimensionality: usize) -> Vec<Vec<f64>> {
+    random_data::random_tabular_seedable(cardinality, dimensionality, 0_f64, 1.0, 42)
+        .iter()
+        .map(|p| normalize(p))
+        .collect()
+}
+
+#[test_case(hellinger; "hellinger")]
+#[test_case(jensen_shannon; "jensen_shannon")]
+#[test_case(total_variation; "total_variation")]
+fn metrics(metric: fn(&[f64], &[f64]) -> f64) {
+    let data = random_distributions(20, 10);
+
+    for p in data.iter() {
+        assert!(metric(p, p).abs() <= 1e-12);
+        for q in data.iter() {
+            let d_pq = metric(p, q);
+            assert!((d_pq - metric(q, p)).abs() <= 1e-12, "not symmetric");
+            assert!((0.0..=1.0).contains(&d_pq), "out of range: {d_pq}");
+            for r in data.iter() {
+                let d_pr = metric(p, r);
+                let d_rq = metric(r, q);
+                assert!(d_pq <= d_pr + d_rq + 1e-12, "triangle inequality");
+            }
+        }
+    }
+}
+
+#[test]
+fn divergences() {
+    let data = random_distributions(20, 10);
+
+    for p in data.iter() {
+        for q in data.iter() {
+            let kl: f64 = kl_divergence(p, q);
+            assert!(kl >= 0.0);
+
+            let js: f64 = jensen_shannon_divergence(p, q);
+            assert!(js <= 2_f64.ln());
+            assert!(js <= kl / 2.0 + kl_divergence::<_, f64>(q, p) / 2.0 + 1e-12);
+
+            let h: f64 = hellinger(p, q);
+            let b: f64 = bhattacharyya(p, q);
+            assert!((h * h - (1.0 - (-b).exp())).abs() <= 1e-12);
+        }
+    }
+}
+
+#[test]
+fn disjoint_supports() {
+    let p = [1.0_f32, 0.0];
+    let q = [0.0_f32, 1.0];
+
+    let kl: f32 = kl_divergence(&p, &q);
+    assert!(kl.is_infinite());
+
+    let b: f32 = bhattacharyya(&p, &q);
+    assert!(b.is_infinite());
+
+    let tv: f32 = total_variation(&p, &q);
+    assert!((tv - 1.0).abs() <= f32::EPSILON);
+}
+
+#[test]
+fn unnormalized() {
+    let p = [1.0_f64, 2.0, 1.0];
+    let q = [10.0_f64, 20.0, 10.0];
+
+    let d = normalized(hellinger::<f64, f64>)(&p, &q);
+    assert!(d.abs() <= f64::EPSILON);
+
+    let d = normalized(jensen_shannon::<f64, f64>)(&p, &q);
+    assert!(d.abs() <= f64::EPSILON);
+
+    let zeros = [0.0_f64; 3];
+    assert_eq!(normalize(&zeros), zeros.to_vec());
+}
+
+/// Wasserstein-1 as the L1 distance between the empirical CDFs of two sample
+/// sets of equal size.
+fn wasserstein_1_naive(x: &[f64], y: &[f64]) -> f64 {
+    let (mut x, mut y) = (x.to_vec(), y.to_vec());
+    x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    y.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    x.iter()
+        .zip(y.iter())
+        .map(|(a, b)| (a - b).abs())
+        .sum::<f64>()
+        / x.len() as f64
+}
+
+#[test]
+fn wasserstein_samples_metric() {
+    let data = random_data::random_tabular_seedable::<f64>(20, 50, -10.0, 10.0, 42);
+
+    for x in data.iter() {
+        assert!(wasserstein::<_, f64>(x, x).abs() <= 1e-12);
+        for y in data.iter() {
+            let d_xy: f64 = wasserstein(x, y);
+            assert!((d_xy - wasserstein_1_naive(x, y)).abs() <= 1e-9);
+            assert!((d_xy - wasserstein::<_, f64>(y, x)).abs() <= 1e-9);
+
+            let w2 = wasserstein_samples::<_, f64>(2);
+            let d2 = w2(x, y);
+            assert!(d2 + 1e-9 >= d_xy, "W2 must dominate W1");
+            for z in data.iter() {
+                assert!(d2 <= w2(x, z) + w2(z, y) + 1e-9, "triangle inequality");
+            }
+        }
+    }
+}
+
+#[test]
+fn wasserstein_unequal_sizes() {
+    // Shifting a distribution moves every unit of mass by the same amount.
+    let x = [1_i32, 5, 3];
+    let y = [11_i32, 15, 13, 11, 15, 13];
+
+    let d: f32 = wasserstein(&x, &y);
+    assert!((d - 10.0).abs() <= 1e-5);
+
+    let d: f32 = wasserstein_samples(3)(&x, &y);
+    assert!((d - 10.0).abs() <= 1e-4);
+
+    let d: f32 = wasserstein(&x, &[]);
+    assert!(d.abs() <= f32::EPSILON);
+}
+
+#[test]
+fn wasserstein_nan_samples() {
+    // A `NaN` sample must not break the sort, and poisons the distance.
+    let x = [1.0_f32, f32::NAN, 3.0, 0.5, f32::NAN, 2.0];
+    let y = [4.0_f32, 2.0, 6.0, 5.0, 1.0, 3.0];
+
+    let d: f32 = wasserstein(&x, &y);
+    assert!(d.is_nan());
+
+    let d: f32 = wasserstein_samples(2)(&y, &x);
+    assert!(d.is_nan());
+
+    let d: f32 = wasserstein(&y, &y);
+    assert!(d.abs() <= f32::EPSILON);
+}
+
+#[test]
+fn wasserstein_histograms_samples_agree() {
+    // Histograms of the samples over the integer bins `0..5`.
+    let x = [0_u8, 1, 1, 4];
+    let y = [2_u8, 3, 3, 3];
+    let hist_x = [1_u32, 2, 0, 0, 1];
+    let hist_y = [0_u32, 0, 1, 3, 0];
+
+    for p in 1..=3 {
+        let expected: f64 = wasserstein_samples(p)(&x, &y);
+        let actual: f64 = wasserstein_histograms(p, None)(&hist_x, &hist_y);
+        assert!((expected - actual).abs() <= 1e-12, "p = {p}");
+
+        let positions = [0.0, 2.0, 4.0, 6.0, 8.0];
+        let actual: f64 = wasserstein_histograms(p, Some(&positions))(&hist_x, &hist_y);
+        assert!((2.0 * expected - actual).abs() <= 1e-12, "p = {p}");
+    }
+}
+
+#[test]
+#[should_panic(expected = "one bin per position")]
+fn wasserstein_histograms_too_few_positions() {
+    let positions = [0.0, 1.0];
+    let _: f64 = wasserstein_histograms(1, Some(&positions))(&[1_u32, 0, 0], &[0, 0, 1]);
+}
+
+#[test]
+#[should_panic(expected = "same length")]
+fn wasserstein_histograms_length_mismatch() {
+    let _: f64 = wasserstein_histograms(1, None)(&[1_u32, 0, 0], &[0, 1]);
+}
+
+#[test]
+#[should_panic(expected = "sorted in ascending order")]
+fn wasserstein_histograms_unsorted_positions() {
+    let positions = [0.0, 2.0, 1.0];
+    let _ = wasserstein_histograms::<u32, f64>(1, Some(&positions));
+}
+
+#[test]
+fn try_wasserstein_histograms_errors() {
+    let positions = [0.0, 2.0, 4.0];
+    let metric = try_wasserstein_histograms(2, Some(&positions));
+
+    let d: Result<f64, _> = metric(&[1_u32, 0, 1], &[0, 2, 0]);
+    assert_eq!(d, Ok(2.0));
+    let d: Result<f64, _> = metric(&[], &[]);
+    assert_eq!(d, Err(DistanceError::EmptyInput));
+    let d: Result<f64, _> = metric(&[1, 0, 1], &[0, 2]);
+    assert_eq!(d, Err(DistanceError::LengthMismatch(3, 2)));
+    let d: Result<f64, _> = metric(&[1, 0, 1, 0], &[0, 2, 0, 0]);
+    assert_eq!(d, Err(DistanceError::LengthMismatch(3, 4)));
+    let d: Result<f64, _> = metric(&[0, 0, 0], &[0, 2, 0]);
+    assert_eq!(d, Err(DistanceError::ZeroNorm));
+
+    let metric = try_wasserstein_histograms(1, None);
+    let d: Result<f64, _> = metric(&[1.0, f64::NAN], &[0.0, 1.0]);
+    assert_eq!(d, Err(DistanceError::NanInput));
+
+    let positions = [0.0, f64::NAN, 4.0];
+    let d: Result<f64, _> =
+        try_wasserstein_histograms(1, Some(&positions))(&[1_u32, 0, 1], &[0, 2, 0]);
+    assert_eq!(d, Err(DistanceError::NanInput));
+
+    let positions = [4.0, 2.0, 0.0];
+    let d: Result<f64, _> =
+        try_wasserstein_histograms(1, Some(&positions))(&[1_u32, 0, 1], &[0, 2, 0]);
+    assert_eq!(d, Err(DistanceError::UnsortedInput));
+}
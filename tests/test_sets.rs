@@ -0,0 +1,56 @@
+use symagen::random_data;
+use test_case::test_case;
+
+use distances::{
+    sets::{directed_hausdorff, hausdorff},
+    vectors::{chebyshev, euclidean, manhattan},
+};
+
+/// The directed Hausdorff distance by brute force.
+fn directed_naive(x: &[Vec<f64>], y: &[Vec<f64>], metric: fn(&[f64], &[f64]) -> f64) -> f64 {
+    x.iter()
+        .map(|a| y.iter().map(|b| metric(a, b)).fold(f64::INFINITY, f64::min))
+        .fold(0.0, f64::max)
+}
+
+#[test_case(euclidean; "euclidean")]
+#[test_case(manhattan; "manhattan")]
+#[test_case(chebyshev; "chebyshev")]
+fn hausdorff_matches_naive(metric: fn(&[f64], &[f64]) -> f64) {
+    let sets = (0..6)
+        .map(|i| random_data::random_tabular_seedable::<f64>(50 + 10 * i, 3, -1.0, 1.0, i as u64))
+        .collect::<Vec<_>>();
+
+    let directed = directed_hausdorff(metric);
+    let symmetric = hausdorff(metric);
+
+    for x in sets.iter() {
+        assert_eq!(symmetric(x, x), 0.0);
+        for y in sets.iter() {
+            let expected = directed_naive(x, y, metric);
+            assert!((directed(x, y) - expected).abs() <= 1e-12);
+
+            let expected = expected.max(directed_naive(y, x, metric));
+            assert!((symmetric(x, y) - expected).abs() <= 1e-12);
+            assert_eq!(symmetric(x, y), symmetric(y, x), "not symmetric");
+
+            for z in sets.iter() {
+                assert!(symmetric(x, y) <= symmetric(x, z) + symmetric(z, y) + 1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn hausdorff_subsets() {
+    let x = random_data::random_tabular_seedable::<f32>(1_000, 2, 0.0, 1.0, 42);
+    let metric = directed_hausdorff(euclidean::<f32, f32>);
+
+    // Every subset is at zero directed distance from its superset.
+    assert_eq!(metric(&x[..100], &x), 0.0);
+    assert!(metric(&x, &x[..100]) > 0.0);
+
+    let empty: Vec<Vec<f32>> = Vec::new();
+    assert_eq!(metric(&x, &empty), 0.0);
+    assert_eq!(metric(&empty, &x), 0.0);
+}
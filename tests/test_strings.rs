@@ -0,0 +1,1082 @@
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+
+use symagen::random_data;
+use test_case::test_case;
+
+use distances::strings::{
+    damerau_levenshtein, damerau_levenshtein_custom, hamming, hamming_normalized, jaro,
+    jaro_winkler, jaro_winkler_custom, levenshtein, levenshtein_bit_parallel, levenshtein_bounded,
+    levenshtein_bounded_custom, levenshtein_custom, levenshtein_normalized, levenshtein_yujian_bo,
+    needleman_wunsch::{
+        apply_edits, co_optimal_alignments, co_optimal_alignments_custom, co_optimal_count,
+        co_optimal_count_custom, compose_edits, edits_hirschberg, edits_hirschberg_custom,
+        edits_iterative, edits_iterative_affine, edits_iterative_custom, edits_recursive,
+        edits_recursive_affine, edits_recursive_custom, invert_edits, nw_cigar, nw_cigar_custom,
+        nw_distance, nw_distance_affine, nw_distance_custom, random_co_optimal_alignment,
+        AffinePenalties, AlignmentMode, Cigar, CigarOp, Edit,
+    },
+    nw_distance_normalized, osa, osa_custom,
+    sequences::{self, AlignedPair},
+    smith_waterman::{sw_alignment, sw_alignment_custom, sw_score, sw_score_custom, Scores},
+    DamerauPenalties, MatrixPenalties, Penalties, PenaltyScheme, SubstitutionMatrix,
+};
+
+/// The Wagner-Fischer algorithm over Unicode scalar values.
+fn levenshtein_naive(x: &str, y: &str) -> usize {
+    levenshtein_naive_custom(x, y, (0, 1, 1))
+}
+
+/// The Wagner-Fischer algorithm with `(match, mismatch, gap)` penalties.
+fn levenshtein_naive_custom(
+    x: &str,
+    y: &str,
+    (match_, mismatch, gap): (usize, usize, usize),
+) -> usize {
+    let (x, y) = (x.chars().collect::<Vec<_>>(), y.chars().collect::<Vec<_>>());
+    let mut cur = (0..=y.len()).map(|j| j * gap).collect::<Vec<_>>();
+    for (i, &a) in x.iter().enumerate() {
+        let mut pre = cur[0];
+        cur[0] = (i + 1) * gap;
+        for (j, &b) in y.iter().enumerate() {
+            let tmp = cur[j + 1];
+            let diagonal = pre + if a == b { match_ } else { mismatch };
+            cur[j + 1] = (tmp + gap).min(cur[j] + gap).min(diagonal);
+            pre = tmp;
+        }
+    }
+    cur[y.len()]
+}
+
+#[test_case("ACGT", 0, 40; "dna_short")]
+#[test_case("ACGT", 50, 300; "dna_blocks")]
+#[test_case("AB", 60, 70; "block_boundary")]
+#[test_case("aéαβ木", 0, 150; "unicode")]
+fn bit_parallel_levenshtein(alphabet: &str, min_len: usize, max_len: usize) {
+    let strings = random_data::random_string(30, min_len, max_len, alphabet, 42);
+
+    for x in strings.iter() {
+        for y in strings.iter() {
+            let expected = levenshtein_naive(x, y);
+            assert_eq!(
+                levenshtein_bit_parallel::<u32>(x, y),
+                expected as u32,
+                "{x} {y}"
+            );
+            assert_eq!(levenshtein::<u32>(x, y), expected as u32);
+        }
+    }
+}
+
+#[test]
+fn levenshtein_custom_dispatch() {
+    let strings = random_data::random_string(20, 1, 100, "ACGT", 7);
+    let unit = levenshtein_custom::<u32>(Penalties::default());
+    let explicit = levenshtein_custom::<u32>(Penalties::new(0, 1, 1));
+
+    for x in strings.iter() {
+        for y in strings.iter() {
+            let d = levenshtein::<u32>(x, y);
+            assert_eq!(unit(x, y), d);
+            assert_eq!(explicit(x, y), d);
+        }
+    }
+}
+
+#[test_case((0, 1, 1); "unit")]
+#[test_case((0, 2, 2); "double")]
+#[test_case((0, 3, 1); "cheap_gaps")]
+#[test_case((1, 2, 3); "match_penalty")]
+#[test_case((0, 1, 0); "free_gaps")]
+fn levenshtein_custom_penalties(penalties: (u32, u32, u32)) {
+    let strings = random_data::random_string(20, 0, 40, "ACGTé", 3);
+    let (match_, mismatch, gap) = penalties;
+    let metric = levenshtein_custom(Penalties::new(match_, mismatch, gap));
+    let bounded = levenshtein_bounded_custom(Penalties::new(match_, mismatch, gap));
+    let naive = (match_ as usize, mismatch as usize, gap as usize);
+
+    for x in strings.iter() {
+        for y in strings.iter() {
+            let expected = levenshtein_naive_custom(x, y, naive) as u32;
+            assert_eq!(metric(x, y), expected, "{x} {y}");
+
+            for k in [
+                0,
+                1,
+                5,
+                expected.saturating_sub(1),
+                expected,
+                expected + 1,
+                u32::MAX,
+            ] {
+                let distance = bounded(x, y, k);
+                assert_eq!(distance, (expected <= k).then_some(expected), "{x} {y} {k}");
+            }
+        }
+    }
+}
+
+#[test]
+fn levenshtein_bounded_long() {
+    let strings = random_data::random_string(10, 900, 1_000, "ACGT", 11);
+
+    for x in strings.iter() {
+        assert_eq!(levenshtein_bounded::<u16>(x, x, 0), Some(0));
+        for y in strings.iter().filter(|&y| y != x) {
+            let expected = levenshtein::<u16>(x, y);
+            assert_eq!(levenshtein_bounded(x, y, expected), Some(expected));
+            assert_eq!(levenshtein_bounded(x, y, expected - 1), None);
+            assert_eq!(levenshtein_bounded::<u16>(x, y, 10), None);
+        }
+    }
+
+    // The lengths alone rule out a small distance.
+    assert_eq!(levenshtein_bounded::<u8>("A", "AAAAAAAAAA", 8), None);
+    assert_eq!(levenshtein_bounded::<u8>("", "AAAAAAAAAA", 10), Some(10));
+}
+
+/// All strings over `alphabet` with at most `max_len` characters.
+fn all_strings(alphabet: &[char], max_len: usize) -> Vec<String> {
+    let mut strings = vec![String::new()];
+    let mut last = strings.clone();
+    for _ in 0..max_len {
+        last = last
+            .iter()
+            .flat_map(|s| alphabet.iter().map(move |&c| format!("{s}{c}")))
+            .collect();
+        strings.extend(last.iter().cloned());
+    }
+    strings
+}
+
+/// The unit-cost Damerau-Levenshtein distances from `x` to all strings over
+/// `alphabet` with at most `max_len` characters, found by breadth-first search
+/// over single edits.
+fn damerau_levenshtein_bfs(x: &str, alphabet: &[char], max_len: usize) -> HashMap<String, usize> {
+    let mut distances = HashMap::from([(x.to_string(), 0)]);
+    let mut queue = VecDeque::from([x.chars().collect::<Vec<_>>()]);
+
+    while let Some(s) = queue.pop_front() {
+        let d = distances[&s.iter().collect::<String>()];
+        let mut neighbors = Vec::new();
+        for i in 0..=s.len() {
+            for &c in alphabet {
+                let mut t = s.clone();
+                t.insert(i, c);
+                neighbors.push(t);
+                if i < s.len() {
+                    let mut t = s.clone();
+                    t[i] = c;
+                    neighbors.push(t);
+                }
+            }
+            if i < s.len() {
+                let mut t = s.clone();
+                t.remove(i);
+                neighbors.push(t);
+            }
+            if i + 1 < s.len() {
+                let mut t = s.clone();
+                t.swap(i, i + 1);
+                neighbors.push(t);
+            }
+        }
+        for t in neighbors.into_iter().filter(|t| t.len() <= max_len) {
+            if let Entry::Vacant(e) = distances.entry(t.iter().collect()) {
+                e.insert(d + 1);
+                queue.push_back(t);
+            }
+        }
+    }
+
+    distances
+}
+
+#[test]
+fn damerau_levenshtein_exhaustive() {
+    let alphabet = ['A', 'B', 'C'];
+    let strings = all_strings(&alphabet, 4);
+
+    for x in strings.iter() {
+        let expected = damerau_levenshtein_bfs(x, &alphabet, 5);
+        for y in strings.iter() {
+            let dl = damerau_levenshtein::<u8>(x, y);
+            assert_eq!(usize::from(dl), expected[y], "{x} {y}");
+
+            let restricted = osa::<u8>(x, y);
+            assert!(dl <= restricted, "{x} {y}");
+            assert!(restricted <= levenshtein::<u8>(x, y), "{x} {y}");
+        }
+    }
+}
+
+#[test]
+fn osa_and_damerau_levenshtein_disagree() {
+    // Transposing "CA" to "AC" and then inserting "B" edits the transposed
+    // substring twice, which OSA does not allow.
+    assert_eq!(osa::<u8>("CA", "ABC"), 3);
+    assert_eq!(damerau_levenshtein::<u8>("CA", "ABC"), 2);
+
+    // As a consequence, OSA does not obey the triangle inequality.
+    assert_eq!(osa::<u8>("CA", "AC"), 1);
+    assert_eq!(osa::<u8>("AC", "ABC"), 1);
+
+    // They agree when no substring needs to be edited twice.
+    assert_eq!(osa::<u8>("NAJIB", "NAIJB"), 1);
+    assert_eq!(damerau_levenshtein::<u8>("NAJIB", "NAIJB"), 1);
+    assert_eq!(osa::<u8>("ÉTÉ", "TÉÉ"), 1);
+    assert_eq!(damerau_levenshtein::<u8>("ÉTÉ", "TÉÉ"), 1);
+}
+
+#[test]
+fn damerau_custom_penalties() {
+    let strings = random_data::random_string(20, 0, 12, "ACGT", 5);
+
+    // With transpositions as expensive as a substitution and a gap, neither
+    // distance ever needs them.
+    let penalties = Penalties::new(0, 1, 2);
+    let lev = levenshtein_custom(penalties);
+    let restricted = osa_custom(DamerauPenalties::from_penalties(penalties, 3));
+    let unrestricted = damerau_levenshtein_custom(DamerauPenalties::from_penalties(penalties, 3));
+
+    // With free transpositions, swapping adjacent characters costs nothing.
+    let free = damerau_levenshtein_custom(DamerauPenalties::new(0, 2, 1, 1));
+
+    for x in strings.iter() {
+        for y in strings.iter() {
+            let d: u32 = lev(x, y);
+            assert_eq!(restricted(x, y), d, "{x} {y}");
+            assert!(unrestricted(x, y) <= d, "{x} {y}");
+            assert!(free(x, y) <= 2 * levenshtein::<u32>(x, y), "{x} {y}");
+        }
+    }
+}
+
+#[test]
+fn normalized_distances() {
+    let strings = all_strings(&['a', 'b', 'é'], 3);
+
+    for x in &strings {
+        for y in &strings {
+            for d in [
+                levenshtein_normalized::<f64>(x, y),
+                levenshtein_yujian_bo(x, y),
+                nw_distance_normalized(x, y),
+                hamming_normalized(x, y),
+            ] {
+                assert!((0.0..=1.0).contains(&d), "{x} {y} {d}");
+            }
+
+            let d: f64 = nw_distance_normalized(x, y);
+            assert!((d - nw_distance_normalized::<f64>(y, x)).abs() <= f64::EPSILON);
+
+            let d: f64 = levenshtein_yujian_bo(x, y);
+            assert_eq!(d == 0.0, x == y, "{x} {y}");
+            assert!((d - levenshtein_yujian_bo::<f64>(y, x)).abs() <= f64::EPSILON);
+
+            for z in &strings {
+                let triangle =
+                    levenshtein_yujian_bo::<f64>(x, y) + levenshtein_yujian_bo::<f64>(y, z);
+                assert!(
+                    levenshtein_yujian_bo::<f64>(x, z) <= triangle + 1e-12,
+                    "{x} {y} {z}"
+                );
+
+                if x.chars().count() == y.chars().count() && y.chars().count() == z.chars().count()
+                {
+                    let triangle =
+                        hamming_normalized::<f64>(x, y) + hamming_normalized::<f64>(y, z);
+                    assert!(
+                        hamming_normalized::<f64>(x, z) <= triangle + 1e-12,
+                        "{x} {y} {z}"
+                    );
+                }
+            }
+        }
+    }
+
+    // The other normalizations are not metrics.
+    let (x, y, z) = ("ab", "aba", "ba");
+    let d_xy: f64 = levenshtein_normalized(x, y);
+    let d_yz: f64 = levenshtein_normalized(y, z);
+    assert!(levenshtein_normalized::<f64>(x, z) > d_xy + d_yz);
+    let (x, y, z) = ("aab", "aabc", "bcc");
+    let d_xy: f64 = nw_distance_normalized(x, y);
+    let d_yz: f64 = nw_distance_normalized(y, z);
+    assert!(nw_distance_normalized::<f64>(x, z) > d_xy + d_yz);
+
+    // The trace back alone finds alignments of different lengths.
+    let d: f64 = nw_distance_normalized("bab", "acba");
+    assert!((d - 0.6).abs() <= f64::EPSILON);
+    let d: f64 = nw_distance_normalized("acba", "bab");
+    assert!((d - 0.6).abs() <= f64::EPSILON);
+
+    let d: f32 = nw_distance_normalized("東京都", "京都");
+    assert!((d - 1.0 / 3.0).abs() <= f32::EPSILON);
+    let d: f32 = levenshtein_normalized("", "");
+    assert!(d.abs() <= f32::EPSILON);
+}
+
+#[test_case("MARTHA", "MARHTA", 0.944_444_444_444_444_4, 0.961_111_111_111_111; "martha")]
+#[test_case("DWAYNE", "DUANE", 0.822_222_222_222_222_2, 0.84; "dwayne")]
+#[test_case("DIXON", "DICKSONX", 0.766_666_666_666_666_7, 0.813_333_333_333_333_3; "dixon")]
+#[test_case("CRATE", "TRACE", 0.733_333_333_333_333_3, 0.733_333_333_333_333_3; "no_prefix")]
+#[test_case("ABC", "XYZ", 0.0, 0.0; "disjoint")]
+#[test_case("", "", 1.0, 1.0; "both_empty")]
+#[test_case("ABC", "", 0.0, 0.0; "one_empty")]
+#[test_case("Zoë", "Zoe", 0.777_777_777_777_777_8, 0.822_222_222_222_222_2; "accented")]
+#[test_case("東京都", "東京府", 0.777_777_777_777_777_8, 0.822_222_222_222_222_2; "cjk")]
+fn jaro_family(x: &str, y: &str, jaro_sim: f64, jaro_winkler_sim: f64) {
+    for (a, b) in [(x, y), (y, x)] {
+        let d: f64 = jaro(a, b);
+        assert!((d - (1.0 - jaro_sim)).abs() <= 1e-12, "{a} {b}: {d}");
+
+        let d: f64 = jaro_winkler(a, b);
+        assert!(
+            (d - (1.0 - jaro_winkler_sim)).abs() <= 1e-12,
+            "{a} {b}: {d}"
+        );
+    }
+}
+
+#[test]
+fn jaro_winkler_parameters() {
+    let strings = random_data::random_string(30, 0, 12, "ACGTé", 9);
+    let no_boost = jaro_winkler_custom::<f32>(0.1, 1.0);
+    let max_boost = jaro_winkler_custom::<f32>(0.25, 0.0);
+
+    for x in strings.iter() {
+        for y in strings.iter() {
+            let d = jaro::<f32>(x, y);
+            assert!((0.0..=1.0).contains(&d));
+            assert_eq!(no_boost(x, y), d);
+
+            let boosted = max_boost(x, y);
+            assert!((0.0..=d).contains(&boosted), "{x} {y}");
+        }
+    }
+
+    // A common prefix of four or more characters gives the largest boost.
+    let d = max_boost("ACGTACGT", "ACGTTGCA");
+    assert!(d.abs() <= f32::EPSILON);
+}
+
+/// The best global alignment score of two strings, maximizing the score.
+fn global_score_naive(x: &[char], y: &[char], (match_, mismatch, gap): (i32, i32, i32)) -> i32 {
+    let mut cur = (0..=y.len()).map(|j| j as i32 * gap).collect::<Vec<_>>();
+    for (i, &a) in x.iter().enumerate() {
+        let mut pre = cur[0];
+        cur[0] = (i as i32 + 1) * gap;
+        for (j, &b) in y.iter().enumerate() {
+            let tmp = cur[j + 1];
+            let diagonal = pre + if a == b { match_ } else { mismatch };
+            cur[j + 1] = (tmp + gap).max(cur[j] + gap).max(diagonal);
+            pre = tmp;
+        }
+    }
+    cur[y.len()]
+}
+
+/// The best local alignment score, as the best global score over all pairs of
+/// substrings.
+fn local_score_naive(x: &str, y: &str, scores: (i32, i32, i32)) -> i32 {
+    let (x, y) = (x.chars().collect::<Vec<_>>(), y.chars().collect::<Vec<_>>());
+    let mut best = 0;
+    for i in 0..x.len() {
+        for k in i + 1..=x.len() {
+            for j in 0..y.len() {
+                for l in j + 1..=y.len() {
+                    best = best.max(global_score_naive(&x[i..k], &y[j..l], scores));
+                }
+            }
+        }
+    }
+    best
+}
+
+#[test_case((2, -1, -1); "default")]
+#[test_case((3, -3, -2); "wikipedia")]
+#[test_case((1, -2, -1); "cheap_gaps")]
+fn smith_waterman(scores: (i32, i32, i32)) {
+    let strings = random_data::random_string(15, 0, 8, "ACGTé", 17);
+    let (match_, mismatch, gap) = scores;
+    let score = sw_score_custom(Scores::new(match_, mismatch, gap));
+    let alignment = sw_alignment_custom(Scores::new(match_, mismatch, gap));
+
+    for x in strings.iter() {
+        for y in strings.iter() {
+            let expected = local_score_naive(x, y, scores);
+            assert_eq!(score(x, y), expected, "{x} {y}");
+
+            let a = alignment(x, y);
+            assert_eq!(a.score, expected);
+            assert_eq!(a.aligned_x.replace('-', ""), x[a.x_range.clone()]);
+            assert_eq!(a.aligned_y.replace('-', ""), y[a.y_range.clone()]);
+
+            let rescored = a
+                .aligned_x
+                .chars()
+                .zip(a.aligned_y.chars())
+                .map(|(c_x, c_y)| match (c_x, c_y) {
+                    ('-', _) | (_, '-') => gap,
+                    _ if c_x == c_y => match_,
+                    _ => mismatch,
+                })
+                .sum::<i32>();
+            assert_eq!(rescored, a.score, "{a:?}");
+        }
+    }
+}
+
+#[test]
+fn smith_waterman_unicode() {
+    let x = "naïve café";
+    let y = "cafés";
+
+    let a = sw_alignment::<i16>(x, y);
+    assert_eq!(a.score, 8);
+    assert_eq!(a.aligned_x, "café");
+    assert_eq!(&x[a.x_range], "café");
+    assert_eq!(&y[a.y_range], "café");
+
+    let s: i64 = sw_score("東京都", "京都府");
+    assert_eq!(s, 4);
+}
+
+#[test_case("aéαβ木", 41; "mixed")]
+#[test_case("àáâãäå", 42; "accented")]
+#[test_case("東京都府県", 43; "cjk")]
+fn nw_unicode(alphabet: &str, seed: u64) {
+    let strings = random_data::random_string(20, 0, 30, alphabet, seed);
+
+    for x in strings.iter() {
+        for y in strings.iter() {
+            let expected = levenshtein_naive(x, y);
+            let d: usize = nw_distance(x, y);
+            assert_eq!(d, expected, "{x} {y}");
+
+            let ([x_to_y, y_to_x], d) = edits_iterative::<usize>(x, y);
+            assert_eq!(d, expected);
+            assert_eq!((x_to_y.len(), y_to_x.len()), (d, d));
+
+            let ([x_to_y, y_to_x], d) = edits_recursive::<usize>(x, y);
+            assert_eq!(d, expected);
+            assert_eq!((x_to_y.len(), y_to_x.len()), (d, d));
+        }
+    }
+
+    let d: u8 = nw_distance("naïve café", "naive cafe");
+    assert_eq!(d, 2);
+    let d: u8 = nw_distance("東京都", "京都府");
+    assert_eq!(d, 2);
+    let d: u8 =
+        nw_distance_custom(Penalties::new(0, 1, 3), AlignmentMode::Global)("東京都", "東京府");
+    assert_eq!(d, 1);
+}
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn nw_graphemes() {
+    use distances::strings::needleman_wunsch::{
+        edits_graphemes, nw_distance_graphemes, nw_distance_graphemes_custom,
+    };
+
+    // A family emoji made of seven code points, and a flag made of two.
+    let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}\u{200d}\u{1f466}";
+    let x = format!("{family}\u{1f1ef}\u{1f1f5}");
+    let y = "\u{1f1ef}\u{1f1f5}";
+
+    let d: u8 = nw_distance(&x, y);
+    assert_eq!(d, 7);
+    let d: u8 = nw_distance_graphemes(&x, y);
+    assert_eq!(d, 1);
+    let d: u8 = nw_distance_graphemes_custom(Penalties::new(0, 1, 5), AlignmentMode::Global)(&x, y);
+    assert_eq!(d, 5);
+
+    // Combining accents stay with their base letters.
+    let ([x_to_y, y_to_x], d) = edits_graphemes::<u8>("re\u{301}sume\u{301}", "resume");
+    assert_eq!(d, 2);
+    assert!(matches!(&x_to_y[..], [Edit::Sub(1, e), Edit::Sub(5, f)] if e == "e" && f == "e"));
+    assert!(y_to_x
+        .iter()
+        .all(|edit| matches!(edit, Edit::Sub(_, e) if e == "e\u{301}")));
+}
+
+/// A function computing the edits between two strings.
+type EditsFn = Box<dyn Fn(&str, &str) -> ([Vec<Edit>; 2], u32)>;
+
+#[test_case("ACGT", 44; "dna")]
+#[test_case("aéαβ木", 45; "unicode")]
+#[test_case("A-C-", 46; "dashes")]
+fn edit_scripts_round_trip(alphabet: &str, seed: u64) {
+    let strings = random_data::random_string(15, 0, 25, alphabet, seed);
+    let scripts: [EditsFn; 6] = [
+        Box::new(edits_iterative),
+        Box::new(edits_recursive),
+        Box::new(edits_hirschberg),
+        Box::new(edits_iterative_custom(
+            Penalties::new(0, 3, 1),
+            AlignmentMode::Global,
+        )),
+        Box::new(edits_iterative_affine(
+            AffinePenalties::new(0, 2, 3, 1),
+            AlignmentMode::Global,
+        )),
+        Box::new(edits_recursive_affine(
+            AffinePenalties::default(),
+            AlignmentMode::Global,
+        )),
+    ];
+
+    for x in strings.iter() {
+        for y in strings.iter() {
+            for edits in &scripts {
+                let ([x_to_y, y_to_x], _) = edits(x, y);
+                assert_eq!(apply_edits(x, &x_to_y), *y, "{x} {y}");
+                assert_eq!(apply_edits(y, &y_to_x), *x, "{x} {y}");
+                assert_eq!(apply_edits(y, &invert_edits(x, &x_to_y)), *x, "{x} {y}");
+            }
+
+            let ([x_to_y, _], _) = edits_iterative::<u32>(x, y);
+            for z in strings.iter().take(5) {
+                let ([y_to_z, _], _) = edits_iterative::<u32>(y, z);
+                let x_to_z = compose_edits(&x_to_y, &y_to_z);
+                assert!(x_to_z.len() <= x_to_y.len() + y_to_z.len());
+                assert_eq!(apply_edits(x, &x_to_z), *z, "{x} {y} {z}");
+            }
+
+            let (x, y) = (x.chars().collect::<Vec<_>>(), y.chars().collect::<Vec<_>>());
+            let ([x_to_y, y_to_x], _) = sequences::edits::<_, u32>(&x, &y);
+            assert_eq!(sequences::apply_edits(&x, &x_to_y), y);
+            assert_eq!(sequences::apply_edits(&y, &y_to_x), x);
+            assert_eq!(
+                sequences::apply_edits(&y, &sequences::invert_edits(&x, &x_to_y)),
+                x
+            );
+        }
+    }
+}
+
+#[test_case("ACGT", 46; "dna")]
+#[test_case("aéαβ木", 47; "unicode")]
+fn cigar_round_trip(alphabet: &str, seed: u64) {
+    let strings = random_data::random_string(15, 0, 25, alphabet, seed);
+
+    for x in strings.iter() {
+        for y in strings.iter() {
+            let (cigar, distance) = nw_cigar::<usize>(x, y);
+            let ([x_to_y, y_to_x], d) = edits_iterative::<usize>(x, y);
+            assert_eq!(distance, d);
+
+            assert_eq!(cigar.reference_len(), x.chars().count());
+            assert_eq!(cigar.query_len(), y.chars().count());
+            assert_eq!(cigar.to_string().parse(), Ok(cigar.clone()));
+            assert_eq!(Cigar::from_edits(x, &x_to_y), cigar, "{x} {y}");
+            assert_eq!(cigar.to_edits(x, y), Ok([x_to_y, y_to_x]));
+
+            let (aligned_x, aligned_y) = cigar.to_aligned(x, y).unwrap_or_default();
+            assert_eq!(Cigar::from_aligned(&aligned_x, &aligned_y), cigar);
+
+            // Without `=` and `X`, the alignment is the same.
+            let m_cigar = cigar.to_string().replace(['=', 'X'], "M").parse::<Cigar>();
+            assert_eq!(
+                m_cigar.map(|c| c.to_aligned(x, y)),
+                Ok(Ok((aligned_x, aligned_y)))
+            );
+
+            // Every mismatched or deleted character of the reference is in
+            // the MD tag, and the numbers add up to the matches.
+            let md = cigar.md_tag(x, y).unwrap_or_default();
+            let count = |op| {
+                cigar
+                    .ops()
+                    .iter()
+                    .filter(|&&(_, o)| o == op)
+                    .map(|&(n, _)| n)
+                    .sum::<usize>()
+            };
+            let letters = md.chars().filter(|c| !c.is_ascii_digit() && *c != '^');
+            assert_eq!(
+                letters.count(),
+                count(CigarOp::Mismatch) + count(CigarOp::Deletion)
+            );
+            let numbers = md
+                .split(|c: char| !c.is_ascii_digit())
+                .filter(|n| !n.is_empty())
+                .map(|n| n.parse::<usize>().unwrap_or_default())
+                .sum::<usize>();
+            assert_eq!(numbers, count(CigarOp::Equal), "{md}");
+        }
+    }
+
+    // Composed edits do not come from an alignment, but they still give one.
+    for x in strings.iter().take(5) {
+        for y in strings.iter().take(5) {
+            for z in strings.iter().take(5) {
+                let ([x_to_y, _], _) = edits_iterative::<usize>(x, y);
+                let ([y_to_z, _], _) = edits_iterative::<usize>(y, z);
+                let x_to_z = compose_edits(&x_to_y, &y_to_z);
+                let cigar = Cigar::from_edits(x, &x_to_z);
+                let edited = cigar
+                    .to_edits(x, z)
+                    .map(|[x_to_z, _]| apply_edits(x, &x_to_z));
+                assert_eq!(edited, Ok(z.clone()));
+            }
+        }
+    }
+}
+
+#[test_case("ACGT", 48; "dna")]
+#[test_case("aéαβ木", 49; "unicode")]
+fn co_optimal_alignments_are_all_optimal(alphabet: &str, seed: u64) {
+    use rand::SeedableRng;
+
+    let strings = random_data::random_string(12, 0, 10, alphabet, seed);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+
+    for x in strings.iter() {
+        for y in strings.iter() {
+            let distance = nw_distance::<usize>(x, y);
+            let mut alignments = co_optimal_alignments(x, y).collect::<Vec<_>>();
+            assert_eq!(alignments.len() as u128, co_optimal_count(x, y));
+
+            // The first alignment is the one found by the trace back.
+            let (cigar, _) = nw_cigar::<usize>(x, y);
+            assert_eq!(Cigar::from_alignment(&alignments[0]), cigar);
+
+            for alignment in &alignments {
+                let (aligned_x, aligned_y): (String, String) = alignment
+                    .iter()
+                    .map(|pair| match *pair {
+                        AlignedPair::Match(a, b) | AlignedPair::Mismatch(a, b) => {
+                            (a.to_string(), b.to_string())
+                        }
+                        AlignedPair::GapInX(b) => (String::new(), b.to_string()),
+                        AlignedPair::GapInY(a) => (a.to_string(), String::new()),
+                    })
+                    .unzip();
+                assert_eq!(aligned_x, *x);
+                assert_eq!(aligned_y, *y);
+                let cost = alignment
+                    .iter()
+                    .filter(|pair| !matches!(pair, AlignedPair::Match(..)))
+                    .count();
+                assert_eq!(cost, distance, "{alignment:?}");
+            }
+
+            let sample = random_co_optimal_alignment(x, y, &mut rng);
+            assert!(alignments.contains(&sample));
+
+            // The sequence versions find the same alignments.
+            let (x_chars, y_chars) = (x.chars().collect::<Vec<_>>(), y.chars().collect::<Vec<_>>());
+            assert!(sequences::co_optimal_alignments(&x_chars, &y_chars).eq(alignments.clone()));
+            assert_eq!(
+                sequences::co_optimal_count(&x_chars, &y_chars),
+                alignments.len() as u128
+            );
+            let sample = sequences::random_co_optimal_alignment(&x_chars, &y_chars, &mut rng);
+            assert!(alignments.contains(&sample));
+
+            let count = alignments.len();
+            alignments.sort_by_key(|alignment| format!("{alignment:?}"));
+            alignments.dedup();
+            assert_eq!(alignments.len(), count);
+        }
+    }
+
+    // With expensive mismatches, "AB" and "BA" only align with gaps.
+    let penalties = Penalties::new(0_u32, 3, 1);
+    assert_eq!(
+        co_optimal_count_custom(penalties, AlignmentMode::Global)("AB", "BA"),
+        2
+    );
+    assert_eq!(
+        co_optimal_alignments_custom(penalties, AlignmentMode::Global)("AB", "BA")
+            .map(|alignment| Cigar::from_alignment(&alignment).to_string())
+            .collect::<Vec<_>>(),
+        ["1D1=1I", "1I1=1D"]
+    );
+}
+
+#[test_case(AlignmentMode::Global; "global")]
+#[test_case(AlignmentMode::SemiGlobal; "semi_global")]
+#[test_case(AlignmentMode::Overlap { min_overlap: 0 }; "overlap")]
+#[test_case(AlignmentMode::Overlap { min_overlap: 3 }; "min_overlap")]
+#[test_case(AlignmentMode::Glocal; "glocal")]
+fn alignment_modes(mode: AlignmentMode) {
+    let strings = random_data::random_string(15, 0, 12, "ACGT", 50);
+
+    let penalties = Penalties::<usize>::default();
+    let metric = nw_distance_custom(penalties, mode);
+    let iterative = edits_iterative_custom(penalties, mode);
+    let recursive = edits_recursive_custom(penalties, mode);
+    let hirschberg = edits_hirschberg_custom(penalties, mode);
+    let cigar = nw_cigar_custom(penalties, mode);
+    let count = co_optimal_count_custom(penalties, mode);
+    let alignments = co_optimal_alignments_custom(penalties, mode);
+    let affine_penalties = AffinePenalties::new(0, 1, 2, 1);
+    let affine = nw_distance_affine(affine_penalties, mode);
+    let affine_global = nw_distance_affine(affine_penalties, AlignmentMode::Global);
+    let seq_alignment = sequences::nw_alignment_custom(penalties, mode);
+    let seq_hirschberg = sequences::nw_alignment_hirschberg_custom(penalties, mode);
+
+    // The best global distance between `y` and the substrings of `x` that
+    // leave out at most `before` and `after` characters at either end.
+    // The strings are ASCII, so that the byte ranges are character ranges.
+    let trimmed =
+        |global: &dyn Fn(&str, &str) -> usize, x: &str, y: &str, [before, after]: [usize; 2]| {
+            (0..=before.min(x.len()))
+                .flat_map(|i| (x.len().saturating_sub(after).max(i)..=x.len()).map(move |j| (i, j)))
+                .map(|(i, j)| global(&x[i..j], y))
+                .min()
+                .unwrap_or_default()
+        };
+    let brute_force = |global: &dyn Fn(&str, &str) -> usize, x: &str, y: &str| match mode {
+        AlignmentMode::Global => global(x, y),
+        AlignmentMode::SemiGlobal => trimmed(global, x, y, [x.len(), x.len()]),
+        AlignmentMode::Glocal => trimmed(global, x, y, [0, x.len()]),
+        AlignmentMode::Overlap { min_overlap } => {
+            // Only one of the strings overhangs at each end.
+            let [x_free, y_free] = [x, y].map(|s| s.len().saturating_sub(min_overlap));
+            let [x_ends, y_ends] = [[x_free, 0], [0, y_free]];
+            [
+                [x_ends, x_ends],
+                [x_ends, y_ends],
+                [y_ends, x_ends],
+                [y_ends, y_ends],
+            ]
+            .into_iter()
+            .flat_map(|[[x_before, y_before], [x_after, y_after]]| {
+                (0..=y_before).flat_map(move |k| {
+                    (y.len().saturating_sub(y_after).max(k)..=y.len())
+                        .map(move |l| (k, l, [x_before, x_after]))
+                })
+            })
+            .map(|(k, l, ends)| trimmed(global, x, &y[k..l], ends))
+            .min()
+            .unwrap_or_default()
+        }
+    };
+
+    for x in strings.iter() {
+        for y in strings.iter() {
+            let expected = brute_force(&nw_distance, x, y);
+            assert_eq!(metric(x, y), expected, "{x} {y}");
+            assert_eq!(affine(x, y), brute_force(&affine_global, x, y), "{x} {y}");
+
+            for ([x_to_y, y_to_x], d) in [iterative(x, y), recursive(x, y), hirschberg(x, y)] {
+                assert_eq!(d, expected);
+                assert_eq!(apply_edits(x, &x_to_y), *y);
+                assert_eq!(apply_edits(y, &y_to_x), *x);
+            }
+
+            // The edits cover both strings in full, with the free characters
+            // against gaps.
+            let (cigar, d) = cigar(x, y);
+            assert_eq!(d, expected);
+            assert_eq!(cigar.reference_len(), x.len());
+            assert_eq!(cigar.query_len(), y.len());
+
+            let all = alignments(x, y).collect::<Vec<_>>();
+            assert_eq!(all.len() as u128, count(x, y));
+            assert_eq!(Cigar::from_alignment(&all[0]), cigar);
+
+            let (x_chars, y_chars) = (x.chars().collect::<Vec<_>>(), y.chars().collect::<Vec<_>>());
+            let (alignment, d) = seq_alignment(&x_chars, &y_chars);
+            assert_eq!(d, expected);
+            assert_eq!(Cigar::from_alignment(&alignment), cigar);
+            assert_eq!(seq_hirschberg(&x_chars, &y_chars).1, expected);
+        }
+    }
+}
+
+/// The affine NW distance by exhaustive search over all alignments.
+///
+/// `state` is 0 after a substitution, 1 after a gap in `x` and 2 after a gap
+/// in `y`.
+fn affine_naive(x: &[char], y: &[char], state: usize, penalties: (u32, u32, u32, u32)) -> u32 {
+    let (match_, mismatch, open, extend) = penalties;
+    let mut best = u32::MAX;
+    if x.is_empty() && y.is_empty() {
+        return 0;
+    }
+    if let (Some(a), Some(b)) = (x.first(), y.first()) {
+        let cost = if a == b { match_ } else { mismatch };
+        best = best.min(cost + affine_naive(&x[1..], &y[1..], 0, penalties));
+    }
+    if !y.is_empty() {
+        let cost = if state == 1 { extend } else { open + extend };
+        best = best.min(cost + affine_naive(x, &y[1..], 1, penalties));
+    }
+    if !x.is_empty() {
+        let cost = if state == 2 { extend } else { open + extend };
+        best = best.min(cost + affine_naive(&x[1..], y, 2, penalties));
+    }
+    best
+}
+
+#[test_case((0, 1, 1, 1); "default")]
+#[test_case((0, 2, 3, 1); "expensive_open")]
+#[test_case((1, 4, 2, 2); "nonzero_match")]
+fn nw_affine(penalties: (u32, u32, u32, u32)) {
+    let strings = random_data::random_string(15, 0, 6, "ACGé", 23);
+    let (match_, mismatch, open, extend) = penalties;
+    let penalties_struct = AffinePenalties::new(match_, mismatch, open, extend);
+    let metric = nw_distance_affine(penalties_struct, AlignmentMode::Global);
+    let iterative = edits_iterative_affine(penalties_struct, AlignmentMode::Global);
+    let recursive = edits_recursive_affine(penalties_struct, AlignmentMode::Global);
+
+    for x in strings.iter() {
+        for y in strings.iter() {
+            let (x_chars, y_chars) = (x.chars().collect::<Vec<_>>(), y.chars().collect::<Vec<_>>());
+            let expected = affine_naive(&x_chars, &y_chars, 0, penalties);
+            assert_eq!(metric(x, y), expected, "{x} {y}");
+            assert_eq!(metric(y, x), expected, "not symmetric");
+
+            let ([x_to_y, y_to_x], d) = iterative(x, y);
+            assert_eq!(d, expected);
+            let ([r_x_to_y, r_y_to_x], r_d) = recursive(x, y);
+            assert_eq!(r_d, expected);
+            assert_eq!(
+                (x_to_y.len(), y_to_x.len()),
+                (r_x_to_y.len(), r_y_to_x.len())
+            );
+        }
+    }
+}
+
+#[test]
+fn nw_affine_without_opening() {
+    let strings = random_data::random_string(30, 0, 20, "ACGT", 29);
+    let affine = nw_distance_affine(
+        AffinePenalties::from_penalties(Penalties::new(0, 2, 1), 0),
+        AlignmentMode::Global,
+    );
+    let linear = nw_distance_custom(Penalties::new(0, 2, 1), AlignmentMode::Global);
+
+    for x in strings.iter() {
+        for y in strings.iter() {
+            let d: u16 = affine(x, y);
+            assert_eq!(d, linear(x, y), "{x} {y}");
+        }
+    }
+}
+
+#[test_case(SubstitutionMatrix::blosum62(), "ARNDCQEGHILKMFPSTWYV"; "blosum62")]
+#[test_case(SubstitutionMatrix::blosum45(), "ARNDCQEGHILKMFPSTWYV"; "blosum45")]
+#[test_case(SubstitutionMatrix::pam250(), "ARNDCQEGHILKMFPSTWYV"; "pam250")]
+#[test_case(SubstitutionMatrix::dna_transition_transversion(), "ACGT"; "dna")]
+fn substitution_matrices(matrix: SubstitutionMatrix, alphabet: &str) {
+    let penalties = MatrixPenalties::<u32>::new(&matrix, 8);
+    let lev = levenshtein_custom(&penalties);
+    let nw = nw_distance_custom(&penalties, AlignmentMode::Global);
+
+    for a in matrix.alphabet() {
+        assert_eq!(penalties.substitution(a, a), 0);
+        for b in matrix.alphabet() {
+            assert_eq!(penalties.substitution(a, b), penalties.substitution(b, a));
+        }
+    }
+
+    let strings = random_data::random_string(20, 0, 12, alphabet, 31);
+    for x in strings.iter() {
+        assert_eq!(lev(x, x), 0);
+        for y in strings.iter() {
+            let (x_chars, y_chars) = (x.chars().collect::<Vec<_>>(), y.chars().collect::<Vec<_>>());
+            let mut cur = (0..=y_chars.len())
+                .map(|j| j as u32 * 8)
+                .collect::<Vec<_>>();
+            for (i, a) in x_chars.iter().enumerate() {
+                let mut pre = cur[0];
+                cur[0] = (i as u32 + 1) * 8;
+                for (j, b) in y_chars.iter().enumerate() {
+                    let tmp = cur[j + 1];
+                    let diagonal = pre + penalties.substitution(a, b);
+                    cur[j + 1] = (tmp + 8).min(cur[j] + 8).min(diagonal);
+                    pre = tmp;
+                }
+            }
+            let expected = cur[y_chars.len()];
+
+            assert_eq!(lev(x, y), expected, "{x} {y}");
+            assert_eq!(lev(y, x), expected, "not symmetric");
+            assert_eq!(nw(x, y), expected, "{x} {y}");
+        }
+    }
+}
+
+#[test]
+fn substitution_matrix_penalties() {
+    let blosum62 = MatrixPenalties::<u16>::new(&SubstitutionMatrix::blosum62(), 10);
+
+    // max(s(W, W), s(C, C)) - s(W, C) = 11 + 2
+    assert_eq!(blosum62.substitution(&'W', &'C'), 13);
+    // Characters outside the alphabet.
+    assert_eq!(blosum62.substitution(&'é', &'é'), 0);
+    assert_eq!(blosum62.substitution(&'é', &'A'), 15);
+
+    let matrix = SubstitutionMatrix::from_ncbi("   a  b\na  5  6\nb  6  1").unwrap();
+    let penalties = MatrixPenalties::<u16>::new(&matrix, 1);
+    assert_eq!(penalties.substitution(&'a', &'b'), 0);
+    assert_eq!(penalties.gap(), 1);
+
+    // The flat penalties are the special case of a matrix with one mismatch score.
+    let flat = SubstitutionMatrix::from_ncbi("   A  C\nA  0 -3\nC -3  0").unwrap();
+    let metric = levenshtein_custom(MatrixPenalties::<u16>::new(&flat, 2));
+    let reference = levenshtein_custom(Penalties::<u16>::new(0, 3, 2));
+    for (x, y) in [("ACCA", "CAAC"), ("", "AC"), ("AAAA", "CC")] {
+        assert_eq!(metric(x, y), reference(x, y));
+    }
+}
+
+#[test]
+fn hirschberg() {
+    let strings = random_data::random_string(30, 0, 40, "ACGT", 37);
+    let matrix = MatrixPenalties::<u32>::new(&SubstitutionMatrix::dna_transition_transversion(), 3);
+    let nw_matrix = nw_distance_custom(&matrix, AlignmentMode::Global);
+    let hirschberg_matrix = edits_hirschberg_custom(&matrix, AlignmentMode::Global);
+
+    for x in strings.iter() {
+        for y in strings.iter() {
+            let ([x_to_y, y_to_x], d) = edits_hirschberg::<u32>(x, y);
+            let ([it_x_to_y, it_y_to_x], it_d) = edits_iterative::<u32>(x, y);
+            assert_eq!(d, it_d, "{x} {y}");
+            // Every edit costs one with the default penalties.
+            assert_eq!(
+                (x_to_y.len(), y_to_x.len()),
+                (it_x_to_y.len(), it_y_to_x.len())
+            );
+
+            let (_, d) = hirschberg_matrix(x, y);
+            assert_eq!(d, nw_matrix(x, y), "{x} {y}");
+        }
+    }
+
+    // Long sequences, whose full table would need hundreds of megabytes.
+    let long = random_data::random_string(2, 6_000, 6_000, "ACGT", 41);
+    let ([edits, _], d) = edits_hirschberg::<u32>(&long[0], &long[1]);
+    assert_eq!(d, levenshtein::<u32>(&long[0], &long[1]));
+    assert_eq!(edits.len(), d as usize);
+}
+
+#[test_case(Penalties::default(); "default")]
+#[test_case(Penalties::new(0, 3, 2); "expensive_mismatch")]
+#[test_case(Penalties::new(1, 1, 3); "expensive_gap")]
+fn sequences_match_strings(penalties: Penalties<u32>) {
+    // `-` is an ordinary character, and not a gap.
+    let strings = random_data::random_string(30, 0, 20, "AC-T", 43);
+    let lev = levenshtein_custom(penalties);
+    let nw = nw_distance_custom(penalties, AlignmentMode::Global);
+    let iterative = edits_iterative_custom(penalties, AlignmentMode::Global);
+    let seq_lev = sequences::levenshtein_custom(penalties);
+    let seq_nw = sequences::nw_distance_custom(penalties, AlignmentMode::Global);
+    let seq_alignment = sequences::nw_alignment_custom(penalties, AlignmentMode::Global);
+    let seq_edits = sequences::edits_custom(penalties, AlignmentMode::Global);
+    let hirschberg = edits_hirschberg_custom(penalties, AlignmentMode::Global);
+    let seq_hirschberg = sequences::edits_hirschberg_custom(penalties, AlignmentMode::Global);
+    let affine = AffinePenalties::from_penalties(penalties, 2);
+    let seq_affine = sequences::edits_affine(affine, AlignmentMode::Global);
+
+    for x in strings.iter() {
+        for y in strings.iter() {
+            let (x_chars, y_chars) = (x.chars().collect::<Vec<_>>(), y.chars().collect::<Vec<_>>());
+            let d = nw(x, y);
+            assert_eq!(seq_lev(&x_chars, &y_chars), lev(x, y), "{x} {y}");
+            assert_eq!(seq_nw(&x_chars, &y_chars), d, "{x} {y}");
+            assert_eq!(
+                sequences::hamming::<_, u32>(&x_chars, &y_chars),
+                hamming::<u32>(x, y)
+            );
+
+            let (alignment, seq_d) = seq_alignment(&x_chars, &y_chars);
+            assert_eq!(seq_d, d);
+            let (mut aligned_x, mut aligned_y, mut cost) = (Vec::new(), Vec::new(), 0);
+            for pair in alignment {
+                match pair {
+                    AlignedPair::Match(a, b) => {
+                        assert_eq!(a, b);
+                        cost += penalties.substitution(&a, &b);
+                        aligned_x.push(a);
+                        aligned_y.push(b);
+                    }
+                    AlignedPair::Mismatch(a, b) => {
+                        assert_ne!(a, b);
+                        cost += penalties.substitution(&a, &b);
+                        aligned_x.push(a);
+                        aligned_y.push(b);
+                    }
+                    AlignedPair::GapInX(b) => {
+                        cost += penalties.gap();
+                        aligned_y.push(b);
+                    }
+                    AlignedPair::GapInY(a) => {
+                        cost += penalties.gap();
+                        aligned_x.push(a);
+                    }
+                }
+            }
+            assert_eq!((aligned_x, aligned_y), (x_chars.clone(), y_chars.clone()));
+            assert_eq!(cost, d);
+
+            let (edits, _) = iterative(x, y);
+            assert_eq!(seq_edits(&x_chars, &y_chars).0, edits, "{x} {y}");
+            assert_eq!(apply_edits(x, &edits[0]), *y, "{x} {y}");
+            assert_eq!(
+                seq_hirschberg(&x_chars, &y_chars),
+                hirschberg(x, y),
+                "{x} {y}"
+            );
+            assert_eq!(
+                seq_affine(&x_chars, &y_chars),
+                edits_iterative_affine(affine, AlignmentMode::Global)(x, y),
+                "{x} {y}"
+            );
+        }
+    }
+}
+
+#[test]
+fn strings_with_dashes() {
+    // A `-` in the input is a character like any other.
+    let ([x_to_y, y_to_x], d) = edits_iterative::<u16>("a-b", "ab");
+    assert_eq!(d, 1);
+    assert_eq!(x_to_y, [Edit::Del(1)]);
+    assert_eq!(y_to_x, [Edit::Ins(1, '-')]);
+    assert_eq!(apply_edits("a-b", &x_to_y), "ab");
+
+    let (cigar, _) = nw_cigar::<u16>("a-b", "ab");
+    assert_eq!(cigar.to_string(), "1=1D1=");
+    assert_eq!(cigar.to_edits("a-b", "ab").unwrap(), [x_to_y, y_to_x]);
+}
+
+#[test]
+fn sequences_of_tokens() {
+    let x = "the quick brown fox jumps over the lazy dog"
+        .split(' ')
+        .collect::<Vec<_>>();
+    let y = "the quick red fox jumped over the dog"
+        .split(' ')
+        .collect::<Vec<_>>();
+
+    let d: u16 = sequences::levenshtein(&x, &y);
+    assert_eq!(d, 3);
+    // Only the first eight words are compared.
+    let d: u16 = sequences::hamming(&x, &y);
+    assert_eq!(d, 3);
+
+    let (alignment, d) = sequences::nw_alignment::<_, u16>(&x, &y);
+    assert_eq!(d, 3);
+    assert_eq!(alignment[2], AlignedPair::Mismatch("brown", "red"));
+    assert_eq!(alignment[7], AlignedPair::GapInY("lazy"));
+
+    // Sequences of codes which would not fit in a `char`.
+    let x = [u32::MAX, 7, 7, 0];
+    let y = [7, 7, 0, u32::MAX];
+    let (alignment, d) = sequences::nw_alignment::<_, u8>(&x, &y);
+    assert_eq!(d, 2);
+    assert_eq!(alignment.first(), Some(&AlignedPair::GapInY(u32::MAX)));
+    assert_eq!(alignment.last(), Some(&AlignedPair::GapInX(u32::MAX)));
+
+    let ([x_to_y, y_to_x], d) = sequences::edits::<_, u8>(&x, &y);
+    assert_eq!(d, 2);
+    assert_eq!((x_to_y.len(), y_to_x.len()), (2, 2));
+}
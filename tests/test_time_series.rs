@@ -0,0 +1,273 @@
+use rand::prelude::*;
+use test_case::test_case;
+
+use distances::{
+    time_series::{absolute_difference, dtw, dtw_custom, dtw_with_path, erp, msm, try_msm, Window},
+    DistanceError,
+};
+
+/// Generates random time series with lengths in `1..=max_len`.
+fn random_series(cardinality: usize, max_len: usize, seed: u64) -> Vec<Vec<i32>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..cardinality)
+        .map(|_| {
+            let len = rng.gen_range(1..=max_len);
+            (0..len).map(|_| rng.gen_range(-20..=20)).collect()
+        })
+        .collect()
+}
+
+/// Whether `x[i]` and `y[j]` may be aligned under the window.
+fn allowed(window: Option<Window>, i: usize, j: usize, n: usize, m: usize) -> bool {
+    match window {
+        None => true,
+        Some(Window::SakoeChiba(r)) => i.abs_diff(j) <= r.max(n.abs_diff(m)),
+        Some(Window::Itakura(_)) => unreachable!("checked separately"),
+    }
+}
+
+/// DTW with the full cost matrix and the window checked cell by cell.
+fn dtw_naive(x: &[i32], y: &[i32], window: Option<Window>) -> u64 {
+    let (n, m) = (x.len(), y.len());
+    let mut d = vec![vec![u64::MAX; m + 1]; n + 1];
+    d[0][0] = 0;
+    for i in 1..=n {
+        for j in 1..=m {
+            if !allowed(window, i - 1, j - 1, n, m) {
+                continue;
+            }
+            let best = d[i - 1][j - 1].min(d[i - 1][j]).min(d[i][j - 1]);
+            if best != u64::MAX {
+                d[i][j] = best + u64::from(x[i - 1].abs_diff(y[j - 1]));
+            }
+        }
+    }
+    d[n][m]
+}
+
+#[test_case(None; "unconstrained")]
+#[test_case(Some(Window::SakoeChiba(0)); "sakoe_chiba_0")]
+#[test_case(Some(Window::SakoeChiba(2)); "sakoe_chiba_2")]
+#[test_case(Some(Window::SakoeChiba(100)); "sakoe_chiba_100")]
+fn dtw_matches_naive(window: Option<Window>) {
+    let data = random_series(30, 25, 42);
+    let metric = dtw_custom(absolute_difference::<i32, u64>, window);
+
+    for x in data.iter() {
+        assert_eq!(metric(x, x), 0_u64);
+        for y in data.iter() {
+            let expected = dtw_naive(x, y, window);
+            assert_eq!(metric(x, y), expected, "{x:?} {y:?}");
+            assert_eq!(metric(y, x), expected, "not symmetric");
+        }
+    }
+}
+
+#[test_case(None; "unconstrained")]
+#[test_case(Some(Window::SakoeChiba(1)); "sakoe_chiba")]
+#[test_case(Some(Window::Itakura(1.0)); "itakura_1")]
+#[test_case(Some(Window::Itakura(2.0)); "itakura_2")]
+fn dtw_paths(window: Option<Window>) {
+    let data = random_series(30, 25, 7);
+    let metric = dtw_custom(absolute_difference::<i32, u64>, window);
+    let metric_with_path = dtw_with_path(absolute_difference::<i32, u64>, window);
+
+    for x in data.iter() {
+        for y in data.iter() {
+            let (distance, path): (u64, _) = metric_with_path(x, y);
+            assert_eq!(distance, metric(x, y));
+
+            assert_eq!(path.first(), Some(&(0, 0)));
+            assert_eq!(path.last(), Some(&(x.len() - 1, y.len() - 1)));
+            for w in path.windows(2) {
+                let ((i, j), (k, l)) = (w[0], w[1]);
+                assert!(
+                    (k - i, l - j) == (1, 1)
+                        || (k - i, l - j) == (1, 0)
+                        || (k - i, l - j) == (0, 1)
+                );
+            }
+
+            let cost = path
+                .iter()
+                .map(|&(i, j)| u64::from(x[i].abs_diff(y[j])))
+                .sum::<u64>();
+            assert_eq!(cost, distance);
+        }
+    }
+}
+
+#[test]
+fn itakura() {
+    let data = random_series(30, 25, 3);
+    let unconstrained = dtw_custom(absolute_difference::<i32, u64>, None);
+    let narrow = dtw_custom(absolute_difference::<i32, u64>, Some(Window::Itakura(1.5)));
+    let wide = dtw_custom(absolute_difference::<i32, u64>, Some(Window::Itakura(3.0)));
+
+    for x in data.iter() {
+        for y in data.iter() {
+            let (d, d_narrow, d_wide): (u64, u64, u64) =
+                (unconstrained(x, y), narrow(x, y), wide(x, y));
+            assert!(d <= d_wide && d_wide <= d_narrow);
+            assert_eq!(d_narrow, narrow(y, x), "not symmetric");
+        }
+    }
+
+    // A slope of one only allows the cells nearest the diagonal.
+    let x = [0, 5, 0, 0];
+    let y = [0, 0, 5, 0];
+    let diagonal = dtw_custom(absolute_difference::<i32, u32>, Some(Window::Itakura(1.0)));
+    assert_eq!(dtw::<_, u32>(&x, &y), 0);
+    assert_eq!(diagonal(&x, &y), 10_u32);
+}
+
+#[test]
+fn dtw_floats() {
+    let x = [0.0_f64, 1.0, 2.0, 1.0, 0.0];
+    let y = [0.0_f64, 0.5, 1.0, 1.5, 2.0, 1.0, 0.0];
+
+    let d: f64 = dtw(&x, &y);
+    assert!((d - 1.0).abs() <= 1e-12);
+
+    let d: f64 = dtw(&x, &[]);
+    assert_eq!(d, 0.0);
+}
+
+/// MSM with the full cost matrix, following the recursion in the paper.
+fn msm_naive(x: &[i32], y: &[i32], c: f64) -> f64 {
+    let cost = |new: f64, prev: f64, other: f64| {
+        if (prev <= new && new <= other) || (prev >= new && new >= other) {
+            c
+        } else {
+            c + (new - prev).abs().min((new - other).abs())
+        }
+    };
+    let x = x.iter().map(|&v| f64::from(v)).collect::<Vec<_>>();
+    let y = y.iter().map(|&v| f64::from(v)).collect::<Vec<_>>();
+    let (n, m) = (x.len(), y.len());
+
+    let mut d = vec![vec![0.0; m]; n];
+    d[0][0] = (x[0] - y[0]).abs();
+    for i in 1..n {
+        d[i][0] = d[i - 1][0] + cost(x[i], x[i - 1], y[0]);
+    }
+    for j in 1..m {
+        d[0][j] = d[0][j - 1] + cost(y[j], y[j - 1], x[0]);
+    }
+    for i in 1..n {
+        for j in 1..m {
+            d[i][j] = (d[i - 1][j - 1] + (x[i] - y[j]).abs())
+                .min(d[i - 1][j] + cost(x[i], x[i - 1], y[j]))
+                .min(d[i][j - 1] + cost(y[j], y[j - 1], x[i]));
+        }
+    }
+    d[n - 1][m - 1]
+}
+
+/// ERP with the full cost matrix.
+fn erp_naive(x: &[i32], y: &[i32], g: i32) -> f64 {
+    let gap = |v: i32| f64::from(v.abs_diff(g));
+    let (n, m) = (x.len(), y.len());
+
+    let mut d = vec![vec![0.0; m + 1]; n + 1];
+    for i in 1..=n {
+        d[i][0] = d[i - 1][0] + gap(x[i - 1]);
+    }
+    for j in 1..=m {
+        d[0][j] = d[0][j - 1] + gap(y[j - 1]);
+    }
+    for i in 1..=n {
+        for j in 1..=m {
+            d[i][j] = (d[i - 1][j - 1] + f64::from(x[i - 1].abs_diff(y[j - 1])))
+                .min(d[i - 1][j] + gap(x[i - 1]))
+                .min(d[i][j - 1] + gap(y[j - 1]));
+        }
+    }
+    d[n][m]
+}
+
+#[test]
+fn msm_erp_match_naive() {
+    let data = random_series(30, 20, 11);
+    let msm_metric = msm::<_, f64>(0.5, None);
+    let erp_metric = erp::<_, f64>(3, None);
+
+    for x in data.iter() {
+        for y in data.iter() {
+            assert!((msm_metric(x, y) - msm_naive(x, y, 0.5)).abs() <= 1e-9);
+            assert!((erp_metric(x, y) - erp_naive(x, y, 3)).abs() <= 1e-9);
+        }
+    }
+
+    let d: f64 = erp(3, None)(&[1, 5], &[]);
+    assert!((d - 4.0).abs() <= 1e-12);
+}
+
+#[test]
+fn msm_empty() {
+    // MSM is not defined for empty series; the unchecked version returns zero.
+    let d: f64 = msm(0.5, None)(&[1, 2, 3], &[]);
+    assert!(d.abs() <= f64::EPSILON);
+
+    let metric = try_msm(0.5, None);
+    for (x, y) in [(&[1, 2, 3][..], &[][..]), (&[], &[1, 2, 3]), (&[], &[])] {
+        let d: Result<f64, _> = metric(x, y);
+        assert_eq!(d, Err(DistanceError::EmptyInput));
+    }
+
+    let d: Result<f64, _> = try_msm(0.5, None)(&[1.0, f64::NAN], &[1.0]);
+    assert_eq!(d, Err(DistanceError::NanInput));
+
+    let d: Result<f64, _> = metric(&[1, 2, 3], &[1, 5, 3]);
+    assert_eq!(d, Ok(3.0));
+}
+
+#[test_case(msm(0.1, None); "msm_0.1")]
+#[test_case(msm(2.0, None); "msm_2")]
+#[test_case(erp(0, None); "erp_0")]
+#[test_case(erp(-5, None); "erp_-5")]
+fn msm_erp_metrics(metric: impl Fn(&[i32], &[i32]) -> f64) {
+    let data = random_series(20, 15, 5);
+
+    for x in data.iter() {
+        assert!(metric(x, x).abs() <= 1e-12);
+        for y in data.iter() {
+            let d_xy = metric(x, y);
+            assert!((d_xy - metric(y, x)).abs() <= 1e-9, "not symmetric");
+            for z in data.iter() {
+                assert!(
+                    d_xy <= metric(x, z) + metric(z, y) + 1e-9,
+                    "triangle inequality"
+                );
+            }
+        }
+    }
+}
+
+#[test_case(Window::SakoeChiba(0); "sakoe_chiba_0")]
+#[test_case(Window::SakoeChiba(3); "sakoe_chiba_3")]
+#[test_case(Window::Itakura(2.0); "itakura")]
+fn msm_erp_windows(window: Window) {
+    let data = random_series(30, 20, 13);
+    let (msm_full, msm_windowed) = (msm::<_, f64>(1.0, None), msm::<_, f64>(1.0, Some(window)));
+    let (erp_full, erp_windowed) = (erp::<_, f64>(0, None), erp::<_, f64>(0, Some(window)));
+
+    for x in data.iter() {
+        for y in data.iter() {
+            assert!(msm_full(x, y) <= msm_windowed(x, y) + 1e-9);
+            assert!(erp_full(x, y) <= erp_windowed(x, y) + 1e-9);
+        }
+    }
+
+    // A wide enough band does not change the distances.
+    let (msm_wide, erp_wide) = (
+        msm::<_, f64>(1.0, Some(Window::SakoeChiba(20))),
+        erp::<_, f64>(0, Some(Window::SakoeChiba(21))),
+    );
+    for x in data.iter() {
+        for y in data.iter() {
+            assert!((msm_full(x, y) - msm_wide(x, y)).abs() <= 1e-9);
+            assert!((erp_full(x, y) - erp_wide(x, y)).abs() <= 1e-9);
+        }
+    }
+}
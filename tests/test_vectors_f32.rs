@@ -1,8 +1,8 @@
-use core::f32::EPSILON;
-
 use symagen::random_data;
 
-use distances::vectors::{chebyshev, euclidean, euclidean_sq, l3_norm, l4_norm, manhattan};
+use distances::vectors::{
+    chebyshev, euclidean, euclidean_sq, l3_norm, l4_norm, manhattan, pearson,
+};
 
 fn l1(x: &[f32], y: &[f32]) -> f32 {
     x.iter()
@@ -60,7 +60,7 @@ fn lp_f32() {
             let e_l1 = l1(x, y);
             let a_l1: f32 = manhattan(x, y);
             assert!(
-                (e_l1 - a_l1).abs() <= EPSILON,
+                (e_l1 - a_l1).abs() <= f32::EPSILON,
                 "Manhattan: expected: {}, actual: {}",
                 e_l1,
                 a_l1
@@ -69,7 +69,7 @@ fn lp_f32() {
             let expected = l2_sq(x, y);
             let actual: f32 = euclidean_sq(x, y);
             assert!(
-                (expected - actual).abs() <= EPSILON,
+                (expected - actual).abs() <= f32::EPSILON,
                 "Euclidean squared: expected: {}, actual: {}",
                 expected,
                 actual
@@ -78,7 +78,7 @@ fn lp_f32() {
             let expected = l2(x, y);
             let actual: f32 = euclidean(x, y);
             assert!(
-                (expected - actual).abs() <= EPSILON,
+                (expected - actual).abs() <= f32::EPSILON,
                 "Euclidean: expected: {}, actual: {}",
                 expected,
                 actual
@@ -87,7 +87,7 @@ fn lp_f32() {
             let e_l3 = l3(x, y);
             let a_l3: f32 = l3_norm(x, y);
             assert!(
-                (e_l3 - a_l3).abs() <= EPSILON,
+                (e_l3 - a_l3).abs() <= f32::EPSILON,
                 "L3 norm: expected: {}, actual: {}",
                 e_l3,
                 a_l3
@@ -96,7 +96,7 @@ fn lp_f32() {
             let e_l4 = l4(x, y);
             let a_l4: f32 = l4_norm(x, y);
             assert!(
-                (e_l4 - a_l4).abs() <= EPSILON,
+                (e_l4 - a_l4).abs() <= f32::EPSILON,
                 "L4 norm: expected: {}, actual: {}",
                 e_l4,
                 a_l4
@@ -105,7 +105,7 @@ fn lp_f32() {
             let e_l_inf = l_inf(x, y);
             let a_l_inf: f32 = chebyshev(x, y);
             assert!(
-                (e_l_inf - a_l_inf).abs() <= EPSILON,
+                (e_l_inf - a_l_inf).abs() <= f32::EPSILON,
                 "Chebyshev: expected: {}, actual: {}",
                 e_l_inf,
                 a_l_inf
@@ -113,3 +113,45 @@ fn lp_f32() {
         }
     }
 }
+
+fn pearson_two_pass(x: &[f32], y: &[f32]) -> f64 {
+    let n = x.len() as f64;
+    let mean_x = x.iter().map(|&v| f64::from(v)).sum::<f64>() / n;
+    let mean_y = y.iter().map(|&v| f64::from(v)).sum::<f64>() / n;
+    let [xx, yy, xy] = x
+        .iter()
+        .zip(y.iter())
+        .fold([0.; 3], |[xx, yy, xy], (&a, &b)| {
+            let (a, b) = (f64::from(a) - mean_x, f64::from(b) - mean_y);
+            [xx + a * a, yy + b * b, xy + a * b]
+        });
+    1. - xy / (xx * yy).sqrt()
+}
+
+#[test]
+fn pearson_f32() {
+    let seed = 42;
+    let (cardinality, dimensionality) = (20, 1_000);
+
+    let data =
+        random_data::random_tabular_seedable::<f32>(cardinality, dimensionality, -10., 10., seed);
+
+    for x in data.iter() {
+        for y in data.iter() {
+            let expected = pearson_two_pass(x, y);
+            let actual: f64 = pearson(x, y);
+            assert!(
+                (expected - actual).abs() <= 1e-9,
+                "Pearson: expected: {expected}, actual: {actual}"
+            );
+
+            // The correlation is invariant to shifting by a large offset.
+            let x_shifted = x.iter().map(|&v| v + 1e4).collect::<Vec<_>>();
+            let actual: f64 = pearson(&x_shifted, y);
+            assert!(
+                (expected - actual).abs() <= 1e-3,
+                "Shifted Pearson: expected: {expected}, actual: {actual}"
+            );
+        }
+    }
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "half")]
+
+use half::{bf16, f16};
+use symagen::random_data;
+
+use distances::{
+    number::Float,
+    vectors::{cosine, euclidean, manhattan},
+    Number,
+};
+
+#[test]
+fn half_f32_agree() {
+    let seed = 42;
+    let (cardinality, dimensionality) = (10, 1_000);
+
+    let data =
+        random_data::random_tabular_seedable::<f32>(cardinality, dimensionality, -10.0, 10.0, seed);
+    let data_f16 = data
+        .iter()
+        .map(|x| x.iter().map(|&v| f16::from_f32(v)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let data_bf16 = data
+        .iter()
+        .map(|x| x.iter().map(|&v| bf16::from_f32(v)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    for (i, x) in data.iter().enumerate() {
+        for (j, y) in data.iter().enumerate() {
+            let expected: f32 = euclidean(x, y);
+            let actual: f32 = euclidean(&data_f16[i], &data_f16[j]);
+            assert!((expected - actual).abs() <= 1e-2 * expected.max(1.0));
+            let actual: f32 = euclidean(&data_bf16[i], &data_bf16[j]);
+            assert!((expected - actual).abs() <= 1e-1 * expected.max(1.0));
+
+            let expected: f32 = manhattan(x, y);
+            let actual: f32 = manhattan(&data_f16[i], &data_f16[j]);
+            assert!((expected - actual).abs() <= 1e-2 * expected.max(1.0));
+
+            let expected: f32 = cosine(x, y);
+            let actual: f32 = cosine(&data_f16[i], &data_f16[j]);
+            assert!((expected - actual).abs() <= 1e-2);
+            let actual: f32 = cosine(&data_bf16[i], &data_bf16[j]);
+            assert!((expected - actual).abs() <= 1e-1);
+        }
+    }
+}
+
+#[test]
+fn half_output() {
+    let x = [f16::from_f32(1.0), f16::from_f32(2.0), f16::from_f32(3.0)];
+    let y = [f16::from_f32(4.0), f16::from_f32(5.0), f16::from_f32(6.0)];
+
+    let distance: f16 = euclidean(&x, &y);
+    assert_eq!(distance, f16::from_f32(27_f32.sqrt()));
+
+    assert_eq!(Float::sqrt(bf16::from_f32(4.0)), bf16::from_f32(2.0));
+    assert_eq!(Number::abs(f16::from_f32(-1.5)), f16::from_f32(1.5));
+    assert_eq!(
+        Number::abs_diff(bf16::from_f32(-1.5), bf16::ONE),
+        bf16::from_f32(2.5)
+    );
+    assert!(Number::is_nan(f16::NAN));
+}
@@ -0,0 +1,75 @@
+//! Tests that distances over narrow integer types do not overflow.
+
+use test_case::test_case;
+
+use distances::{
+    vectors::{bray_curtis, chebyshev, cosine, euclidean, euclidean_sq, manhattan, minkowski_p},
+    Number,
+};
+
+#[test_case(1; "dim_1")]
+#[test_case(100; "dim_100")]
+#[test_case(10_000; "dim_10_000")]
+fn lp_u8(dimensionality: usize) {
+    let x = vec![0_u8; dimensionality];
+    let y = vec![u8::MAX; dimensionality];
+    let n = dimensionality as u64;
+
+    let l1: u64 = manhattan(&x, &y);
+    assert_eq!(l1, 255 * n);
+
+    let l2_sq: u64 = euclidean_sq(&x, &y);
+    assert_eq!(l2_sq, 255 * 255 * n);
+
+    let l2: f64 = euclidean(&x, &y);
+    assert!((l2 - 255.0 * (n as f64).sqrt()).abs() <= 1e-9);
+
+    let l3_p: f64 = minkowski_p(3)(&x, &y);
+    assert!((l3_p - 255_f64.powi(3) * n as f64).abs() <= 1e-6);
+
+    let l_inf: u8 = chebyshev(&x, &y);
+    assert_eq!(l_inf, u8::MAX);
+
+    let bc: f64 = bray_curtis(&x, &y);
+    assert!((bc - 1.0).abs() <= f64::EPSILON);
+}
+
+#[test_case(1; "dim_1")]
+#[test_case(100; "dim_100")]
+#[test_case(10_000; "dim_10_000")]
+fn lp_i8(dimensionality: usize) {
+    let x = vec![i8::MIN; dimensionality];
+    let y = vec![i8::MAX; dimensionality];
+    let n = dimensionality as i64;
+
+    let l1: i64 = manhattan(&x, &y);
+    assert_eq!(l1, 255 * n);
+
+    let l2_sq: i64 = euclidean_sq(&x, &y);
+    assert_eq!(l2_sq, 255 * 255 * n);
+
+    let l_inf: i64 = chebyshev(&x, &y);
+    assert_eq!(l_inf, 255);
+
+    // Without an accumulator, the difference saturates instead of overflowing.
+    assert_eq!(Number::abs_diff(i8::MIN, i8::MAX), i8::MAX);
+}
+
+#[test]
+fn cosine_i8() {
+    let x = vec![i8::MAX; 1_000];
+    let y = vec![i8::MIN; 1_000];
+
+    let d: f64 = cosine(&x, &x);
+    assert!(d.abs() <= f64::EPSILON);
+
+    // Anti-parallel vectors have a negative dot product, which we treat the
+    // same way as orthogonal vectors.
+    let d: f64 = cosine(&x, &y);
+    assert!((d - 1.0).abs() <= f64::EPSILON);
+
+    let x = vec![200_u8; 1_000];
+    let y = vec![100_u8; 1_000];
+    let d: f32 = cosine(&x, &y);
+    assert!(d.abs() <= f32::EPSILON);
+}